@@ -0,0 +1,53 @@
+use crate::battle::{
+    actions::Action, battle::Battle, battle_input::SingleInput,
+    battle_request::SingleBattleRequest, damage_calculator::DamageRolls,
+    side_observation::SideObservation,
+};
+
+// What one side sees when deciding for one of its active slots
+pub struct Observation<'a> {
+    battle: &'a Battle,
+    pub side_observation: SideObservation, // only what this side could know
+    pub is_trainer_1: bool,
+    pub slot: usize,
+}
+
+impl<'a> Observation<'a> {
+    pub fn new(battle: &'a Battle, is_trainer_1: bool, slot: usize) -> Self {
+        Self {
+            battle,
+            side_observation: SideObservation::new(battle.battle_state(), is_trainer_1),
+            is_trainer_1,
+            slot,
+        }
+    }
+
+    // The full battle, the opponent's exact sets included, for agents that search ahead by
+    //  playing out copies of it
+    pub fn clone_battle_for_search(&self) -> Battle {
+        self.battle.clone()
+    }
+
+    // Damage rolls against what has been revealed of the opponent's sets
+    pub fn get_damage_rolls(&self, action: &Action) -> Option<DamageRolls> {
+        self.battle
+            .with_hidden_opponent_sets(self.is_trainer_1)
+            .get_damage_rolls(self.is_trainer_1, self.slot, action)
+    }
+
+    // Opposing active slots that still have a pokemon standing
+    pub fn get_opponent_slots(&self) -> Vec<usize> {
+        let opponent_side = self.battle.battle_state().get_side(!self.is_trainer_1);
+        (0..opponent_side.num_active_slots())
+            .filter(|slot| !opponent_side.get_active_pokemon(*slot).is_fainted())
+            .collect()
+    }
+}
+
+pub trait Agent {
+    fn choose_input(
+        &mut self,
+        observation: &Observation,
+        request: &SingleBattleRequest,
+    ) -> SingleInput;
+}
@@ -0,0 +1,110 @@
+use crate::battle::{
+    actions::{Action, MoveModifier, MoveSlot, SwitchSlot},
+    battle_input::{SideInput, SingleInput},
+    battle_request::{SideRequest, SingleBattleRequest},
+    valid_actions::ValidMoves,
+};
+
+// A single slot's choice for search agents, kept apart from Action so it can be hashed
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Choice {
+    Move(usize),
+    Struggle,
+    Locked,
+    Switch(usize),
+    SwitchIn(usize),
+}
+
+impl Choice {
+    pub fn to_input(self) -> SingleInput {
+        match self {
+            Choice::Move(idx) => SingleInput::StandardInput(Action::Move(
+                MoveSlot::from_index(idx),
+                MoveModifier::None,
+            )),
+            Choice::Struggle => SingleInput::StandardInput(Action::Struggle),
+            Choice::Locked => SingleInput::StandardInput(Action::Locked),
+            Choice::Switch(idx) => {
+                SingleInput::StandardInput(Action::Switch(SwitchSlot::from_index(idx)))
+            }
+            Choice::SwitchIn(idx) => SingleInput::SwitchInInput(SwitchSlot::from_index(idx)),
+        }
+    }
+
+    pub fn get_choices(request: &SingleBattleRequest) -> Vec<Choice> {
+        match request {
+            SingleBattleRequest::SwitchInRequest(valid_switches) => valid_switches
+                .switches
+                .iter()
+                .map(|idx| Choice::SwitchIn(*idx))
+                .collect(),
+            SingleBattleRequest::ActionRequest(valid_actions) => {
+                let mut choices = match &valid_actions.valid_moves {
+                    ValidMoves::Struggle => vec![Choice::Struggle],
+                    ValidMoves::Locked => vec![Choice::Locked],
+                    ValidMoves::Moves(move_idxs) => {
+                        move_idxs.iter().map(|idx| Choice::Move(*idx)).collect()
+                    }
+                };
+                if let Some(valid_switches) = &valid_actions.valid_switches {
+                    choices.extend(
+                        valid_switches
+                            .switches
+                            .iter()
+                            .map(|idx| Choice::Switch(*idx)),
+                    );
+                }
+                choices
+            }
+        }
+    }
+}
+
+// One choice per active slot, None for slots without a request
+pub type SideChoice = Vec<Option<Choice>>;
+
+pub fn get_side_choices(side_request: &SideRequest) -> Vec<SideChoice> {
+    side_request
+        .iter()
+        .fold(vec![vec![]], |side_choices, request| {
+            let slot_choices = match request {
+                Some(request) => Choice::get_choices(request).into_iter().map(Some).collect(),
+                None => vec![None],
+            };
+            side_choices
+                .iter()
+                .flat_map(|side_choice| {
+                    slot_choices.iter().map(move |choice| {
+                        let mut side_choice = side_choice.clone();
+                        side_choice.push(*choice);
+                        side_choice
+                    })
+                })
+                .collect()
+        })
+}
+
+pub fn to_side_input(side_choice: &SideChoice) -> SideInput {
+    side_choice
+        .iter()
+        .map(|choice| choice.map(Choice::to_input))
+        .collect()
+}
+
+// The rest of a side's last searched choice. Search agents pick for every slot at once,
+//  so later slots take their part from it instead of searching again
+#[derive(Default)]
+pub struct PendingInputs(SideInput);
+
+impl PendingInputs {
+    pub fn take(&mut self, slot: usize) -> Option<SingleInput> {
+        self.0.get_mut(slot).and_then(Option::take)
+    }
+
+    // Keeps the new choice and takes the slot's part of it
+    pub fn replace(&mut self, side_choice: &SideChoice, slot: usize) -> SingleInput {
+        self.0 = to_side_input(side_choice);
+        self.take(slot)
+            .expect("Searched choice is missing a requested slot")
+    }
+}
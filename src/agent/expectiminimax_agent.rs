@@ -0,0 +1,190 @@
+use rand::{SeedableRng, distributions::WeightedIndex, prelude::Distribution, rngs::StdRng};
+use typed_builder::TypedBuilder;
+
+use crate::{
+    agent::{
+        agent::{Agent, Observation},
+        choice::{self, PendingInputs, SideChoice},
+        matrix_game,
+    },
+    battle::{
+        battle::Battle,
+        battle_input::SingleInput,
+        battle_request::{BattleRequest, SideRequest, SingleBattleRequest, Winner},
+        state::BattleState,
+    },
+};
+
+// Trainer 1's chances of winning from a state, between 0 and 1
+pub type Evaluation = Box<dyn Fn(&BattleState) -> f64>;
+
+// A side's choices with the probability of playing each
+pub type SideStrategy = (Vec<SideChoice>, Vec<f64>);
+
+// Favours the side with more of its team's hp left
+pub fn hp_evaluation(battle_state: &BattleState) -> f64 {
+    let hp_difference = battle_state.get_side(true).remaining_hp_fraction()
+        - battle_state.get_side(false).remaining_hp_fraction();
+    0.5 + 0.5 * hp_difference as f64
+}
+
+#[derive(Clone, TypedBuilder)]
+pub struct ExpectiminimaxConfig {
+    #[builder(default = 1)]
+    pub depth: u32, // number of inputs searched ahead, switch-ins included
+    #[builder(default)]
+    pub seed: u64,
+}
+
+// Searches every joint choice and every chance outcome to a fixed depth, assuming both
+//  sides can see everything. Each node is a simultaneous move game, solved for a mixed
+//  strategy, and the agent samples its choice from that strategy
+pub struct ExpectiminimaxAgent {
+    config: ExpectiminimaxConfig,
+    evaluation: Evaluation,
+    rng: StdRng,
+    pending_inputs: PendingInputs,
+}
+
+impl ExpectiminimaxAgent {
+    pub fn new(config: ExpectiminimaxConfig, evaluation: Evaluation) -> Self {
+        let rng = StdRng::seed_from_u64(config.seed);
+        Self {
+            config,
+            evaluation,
+            rng,
+            pending_inputs: PendingInputs::default(),
+        }
+    }
+
+    pub fn with_hp_evaluation(config: ExpectiminimaxConfig) -> Self {
+        Self::new(config, Box::new(hp_evaluation))
+    }
+
+    // Trainer 1's value for the node, with each side's choices and mixed strategy
+    fn solve_node(
+        &self,
+        battle: &Battle,
+        requests: (&SideRequest, &SideRequest),
+        depth: u32,
+    ) -> (f64, [SideStrategy; 2]) {
+        let side_1_choices = choice::get_side_choices(requests.0);
+        let side_2_choices = choice::get_side_choices(requests.1);
+
+        let payoffs: Vec<Vec<f64>> = side_1_choices
+            .iter()
+            .map(|side_1_choice| {
+                side_2_choices
+                    .iter()
+                    .map(|side_2_choice| {
+                        let input = (
+                            choice::to_side_input(side_1_choice),
+                            choice::to_side_input(side_2_choice),
+                        );
+                        battle
+                            .get_outcomes(&input)
+                            .into_iter()
+                            .map(|(probability, outcome, request)| {
+                                probability * self.get_value(&outcome, request, depth - 1)
+                            })
+                            .sum()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let (value, side_1_strategy, side_2_strategy) = matrix_game::solve_matrix_game(&payoffs);
+        (
+            value,
+            [
+                (side_1_choices, side_1_strategy),
+                (side_2_choices, side_2_strategy),
+            ],
+        )
+    }
+
+    fn get_value(&self, battle: &Battle, request: BattleRequest, depth: u32) -> f64 {
+        match request {
+            BattleRequest::BattleEnded(winner) => match winner {
+                Winner::Trainer1 => 1.0,
+                Winner::Trainer2 => 0.0,
+                Winner::Draw => 0.5,
+            },
+            BattleRequest::TeamPreview(_, _) => panic!("Search cannot plan team preview"),
+            BattleRequest::Request(_, _) if depth == 0 => (self.evaluation)(battle.battle_state()),
+            BattleRequest::Request(side_1_request, side_2_request) => {
+                self.solve_node(battle, (&side_1_request, &side_2_request), depth)
+                    .0
+            }
+        }
+    }
+
+    fn search(&mut self, root: &Battle, is_trainer_1: bool) -> SideChoice {
+        let BattleRequest::Request(side_1_request, side_2_request) = root.get_request() else {
+            panic!("Search started without a move or switch request");
+        };
+        let (_, [side_1, side_2]) = self.solve_node(
+            root,
+            (&side_1_request, &side_2_request),
+            self.config.depth.max(1),
+        );
+
+        let (mut side_choices, strategy) = if is_trainer_1 { side_1 } else { side_2 };
+        let idx = WeightedIndex::new(strategy.iter().map(|p| p.max(0.0)))
+            .map_or(0, |distribution| distribution.sample(&mut self.rng));
+        side_choices.swap_remove(idx)
+    }
+}
+
+impl Agent for ExpectiminimaxAgent {
+    fn choose_input(
+        &mut self,
+        observation: &Observation,
+        _request: &SingleBattleRequest,
+    ) -> SingleInput {
+        if let Some(input) = self.pending_inputs.take(observation.slot) {
+            return input;
+        }
+
+        let side_choice = self.search(
+            &observation.clone_battle_for_search(),
+            observation.is_trainer_1,
+        );
+        self.pending_inputs.replace(&side_choice, observation.slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        agent::test_utils::{assert_same_choice, get_chosen_slot, get_search_battle},
+        battle::{actions::MoveSlot, test_utils},
+    };
+
+    #[test]
+    fn takes_a_guaranteed_ko_at_depth_1() {
+        let battle = get_search_battle();
+        let mut agent =
+            ExpectiminimaxAgent::with_hp_evaluation(ExpectiminimaxConfig::builder().build());
+        assert_eq!(get_chosen_slot(&mut agent, &battle), Some(MoveSlot::Slot1));
+    }
+
+    #[test]
+    fn same_seed_searches_the_same() {
+        let battle = test_utils::get_battle(
+            "Garchomp\nAbility: Sand Force\n- Swords Dance\n- Earthquake",
+            "Pikachu\nAbility: Technician\n- Tackle\n- Growl",
+            0,
+        );
+        let config = ExpectiminimaxConfig::builder().depth(2).seed(7).build();
+        assert_same_choice(&battle, || {
+            ExpectiminimaxAgent::with_hp_evaluation(config.clone())
+        });
+    }
+
+    #[test]
+    fn hp_evaluation_favours_the_side_with_more_hp() {
+        assert!(hp_evaluation(get_search_battle().battle_state()) > 0.5);
+    }
+}
@@ -0,0 +1,176 @@
+// Solves a zero-sum game where the row player maximizes the payoff and the column player
+//  minimizes it. Returns the game value and both players' mixed strategies
+pub fn solve_matrix_game(payoffs: &[Vec<f64>]) -> (f64, Vec<f64>, Vec<f64>) {
+    let num_rows = payoffs.len();
+    let num_cols = payoffs[0].len();
+
+    // a pure saddle point needs no solving, which covers every one-sided decision
+    let row_mins: Vec<f64> = payoffs
+        .iter()
+        .map(|row| row.iter().copied().fold(f64::INFINITY, f64::min))
+        .collect();
+    let col_maxes: Vec<f64> = (0..num_cols)
+        .map(|col| {
+            payoffs
+                .iter()
+                .map(|row| row[col])
+                .fold(f64::NEG_INFINITY, f64::max)
+        })
+        .collect();
+    let (best_row, maximin) = argmax(&row_mins);
+    let (best_col, neg_minimax) = argmax(&col_maxes.iter().map(|v| -v).collect::<Vec<f64>>());
+    if maximin >= -neg_minimax - EPSILON {
+        return (
+            maximin,
+            pure_strategy(num_rows, best_row),
+            pure_strategy(num_cols, best_col),
+        );
+    }
+
+    // Shifting every payoff above zero makes the value positive, so the column player's
+    //  problem becomes: maximize sum(y) subject to payoffs * y <= 1, y >= 0.
+    //  Its optimal tableau also holds the row player's strategy under the slack columns
+    let min_payoff = row_mins.iter().copied().fold(f64::INFINITY, f64::min);
+    let shift = 1.0 - min_payoff;
+
+    let width = num_cols + num_rows + 1;
+    let mut tableau: Vec<Vec<f64>> = payoffs
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut tableau_row = vec![0.0; width];
+            for (j, payoff) in row.iter().enumerate() {
+                tableau_row[j] = payoff + shift;
+            }
+            tableau_row[num_cols + i] = 1.0;
+            tableau_row[width - 1] = 1.0;
+            tableau_row
+        })
+        .collect();
+    let mut objective = vec![0.0; width];
+    objective[..num_cols].fill(-1.0);
+    let mut basis: Vec<usize> = (num_cols..num_cols + num_rows).collect();
+
+    // Bland's rule, the lowest index improving column, can't cycle
+    while let Some(pivot_col) = (0..width - 1).find(|col| objective[*col] < -EPSILON) {
+        let Some(pivot_row) = (0..num_rows)
+            .filter(|row| tableau[*row][pivot_col] > EPSILON)
+            .min_by(|a, b| {
+                let ratio = |row: usize| tableau[row][width - 1] / tableau[row][pivot_col];
+                ratio(*a).total_cmp(&ratio(*b))
+            })
+        else {
+            panic!("Matrix game program should always be bounded");
+        };
+
+        let pivot = tableau[pivot_row][pivot_col];
+        tableau[pivot_row].iter_mut().for_each(|v| *v /= pivot);
+        let pivot_values = tableau[pivot_row].clone();
+        for (row, tableau_row) in tableau.iter_mut().enumerate() {
+            if row != pivot_row {
+                let factor = tableau_row[pivot_col];
+                for (v, pivot_value) in tableau_row.iter_mut().zip(&pivot_values) {
+                    *v -= factor * pivot_value;
+                }
+            }
+        }
+        let factor = objective[pivot_col];
+        for (v, pivot_value) in objective.iter_mut().zip(&pivot_values) {
+            *v -= factor * pivot_value;
+        }
+        basis[pivot_row] = pivot_col;
+    }
+
+    let total = objective[width - 1];
+    let mut col_strategy = vec![0.0; num_cols];
+    for (row, col) in basis.iter().enumerate() {
+        if *col < num_cols {
+            col_strategy[*col] = tableau[row][width - 1] / total;
+        }
+    }
+    let row_strategy = (0..num_rows)
+        .map(|row| objective[num_cols + row] / total)
+        .collect();
+
+    (1.0 / total - shift, row_strategy, col_strategy)
+}
+
+const EPSILON: f64 = 1e-9;
+
+fn argmax(values: &[f64]) -> (usize, f64) {
+    values
+        .iter()
+        .copied()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .expect("Matrix game without any strategies")
+}
+
+fn pure_strategy(num_strategies: usize, idx: usize) -> Vec<f64> {
+    let mut strategy = vec![0.0; num_strategies];
+    strategy[idx] = 1.0;
+    strategy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn matching_pennies_mixes_evenly() {
+        let (value, row_strategy, col_strategy) =
+            solve_matrix_game(&[vec![1.0, -1.0], vec![-1.0, 1.0]]);
+        assert!(value.abs() < 1e-6);
+        assert_close(&row_strategy, &[0.5, 0.5]);
+        assert_close(&col_strategy, &[0.5, 0.5]);
+    }
+
+    #[test]
+    fn rock_paper_scissors_is_played_uniformly() {
+        let (value, row_strategy, col_strategy) = solve_matrix_game(&[
+            vec![0.0, -1.0, 1.0],
+            vec![1.0, 0.0, -1.0],
+            vec![-1.0, 1.0, 0.0],
+        ]);
+        let third = 1.0 / 3.0;
+        assert!(value.abs() < 1e-6);
+        assert_close(&row_strategy, &[third, third, third]);
+        assert_close(&col_strategy, &[third, third, third]);
+    }
+
+    #[test]
+    fn saddle_points_are_played_purely() {
+        // the row player's second row guarantees 2, and the column player's first column
+        //  concedes no more than 2
+        let (value, row_strategy, col_strategy) =
+            solve_matrix_game(&[vec![1.0, 5.0], vec![2.0, 3.0], vec![0.0, 4.0]]);
+        assert!((value - 2.0).abs() < 1e-6);
+        assert_close(&row_strategy, &[0.0, 1.0, 0.0]);
+        assert_close(&col_strategy, &[1.0, 0.0]);
+    }
+
+    #[test]
+    fn equal_payoffs_still_give_strategies() {
+        let (value, row_strategy, col_strategy) = solve_matrix_game(&vec![vec![0.5; 3]; 2]);
+        assert!((value - 0.5).abs() < 1e-6);
+        assert!((row_strategy.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+        assert!((col_strategy.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn mixed_strategies_have_the_game_value() {
+        // each side mixes so the other is indifferent between its two strategies
+        let payoffs = [vec![3.0, -1.0], vec![-2.0, 1.0]];
+        let (value, row_strategy, col_strategy) = solve_matrix_game(&payoffs);
+        assert_close(&row_strategy, &[3.0 / 7.0, 4.0 / 7.0]);
+        assert_close(&col_strategy, &[2.0 / 7.0, 5.0 / 7.0]);
+        assert!((value - 1.0 / 7.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{
+    agent::agent::{Agent, Observation},
+    battle::{
+        actions::{Action, MoveModifier, MoveSlot, SwitchSlot, TargetLoc},
+        battle_input::SingleInput,
+        battle_request::SingleBattleRequest,
+        valid_actions::ValidMoves,
+    },
+};
+
+// Uses whichever move and target deals the most damage on an average non-crit roll,
+//  against what has been revealed of the opponent. Only switches when forced to, and
+//  leaves gimmicks unused
+pub struct MaxDamageAgent;
+
+impl MaxDamageAgent {
+    fn choose_move(observation: &Observation, move_idxs: &[usize]) -> Action {
+        let targets: Vec<TargetLoc> = observation
+            .get_opponent_slots()
+            .into_iter()
+            .map(TargetLoc::Opponent)
+            .collect();
+
+        let mut best: Option<(f64, Action)> = None;
+        for idx in move_idxs {
+            for target in &targets {
+                let action =
+                    Action::TargetedMove(MoveSlot::from_index(*idx), *target, MoveModifier::None);
+                let damage = observation
+                    .get_damage_rolls(&action)
+                    .map_or(0.0, |damage_rolls| damage_rolls.get_mean_roll());
+                if best
+                    .as_ref()
+                    .is_none_or(|(best_damage, _)| damage > *best_damage)
+                {
+                    best = Some((damage, action));
+                }
+            }
+        }
+
+        match best {
+            Some((_, action)) => action,
+            None => Action::Move(MoveSlot::from_index(move_idxs[0]), MoveModifier::None),
+        }
+    }
+}
+
+impl Agent for MaxDamageAgent {
+    fn choose_input(
+        &mut self,
+        observation: &Observation,
+        request: &SingleBattleRequest,
+    ) -> SingleInput {
+        match request {
+            SingleBattleRequest::SwitchInRequest(valid_switches) => {
+                SingleInput::SwitchInInput(SwitchSlot::from_index(valid_switches.switches[0]))
+            }
+            SingleBattleRequest::ActionRequest(valid_actions) => {
+                let action = match &valid_actions.valid_moves {
+                    ValidMoves::Struggle => Action::Struggle,
+                    ValidMoves::Locked => Action::Locked,
+                    ValidMoves::Moves(move_idxs) => Self::choose_move(observation, move_idxs),
+                };
+                SingleInput::StandardInput(action)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::{battle_request::BattleRequest, test_utils};
+
+    fn get_chosen_action(team_2: &str) -> Action {
+        let battle = test_utils::get_battle(
+            "Alakazam\nAbility: Technician\n- Earthquake\n- Tackle\n- Swords Dance",
+            team_2,
+            0,
+        );
+        let BattleRequest::Request(mut side_request, _) = battle.get_request() else {
+            panic!("Battle should be waiting on a move");
+        };
+        let request = side_request[0].take().unwrap();
+        match MaxDamageAgent.choose_input(&Observation::new(&battle, true, 0), &request) {
+            SingleInput::StandardInput(action) => action,
+            _ => panic!("Agent should choose a move"),
+        }
+    }
+
+    #[test]
+    fn chooses_the_strongest_move() {
+        let action = get_chosen_action("Snorlax\nAbility: Thick Fat\n- Tackle");
+        assert_eq!(action.get_move_slot(), Some(MoveSlot::Slot0));
+    }
+
+    #[test]
+    fn skips_moves_the_target_is_immune_to() {
+        let action = get_chosen_action("Skarmory\nAbility: Technician\n- Tackle");
+        assert_eq!(action.get_move_slot(), Some(MoveSlot::Slot1));
+    }
+
+    #[test]
+    fn ignores_the_opponents_unrevealed_ability_and_spread() {
+        let get_rolls = |team_2: &str| {
+            let battle =
+                test_utils::get_battle("Alakazam\nAbility: Technician\n- Ember", team_2, 0);
+            Observation::new(&battle, true, 0)
+                .get_damage_rolls(&Action::Move(MoveSlot::Slot0, MoveModifier::None))
+                .expect("Ember should deal damage")
+                .rolls
+        };
+        assert_eq!(
+            get_rolls("Snorlax\nAbility: Thick Fat\nEVs: 252 HP / 252 SpD\n- Tackle"),
+            get_rolls("Snorlax\nAbility: Technician\n- Tackle")
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use typed_builder::TypedBuilder;
+
+use crate::{
+    agent::{
+        agent::{Agent, Observation},
+        choice::{self, PendingInputs, SideChoice},
+        expectiminimax_agent,
+        random_agent::RandomAgent,
+        runner,
+    },
+    battle::{
+        battle::Battle,
+        battle_input::SingleInput,
+        battle_request::{BattleRequest, SideRequest, SingleBattleRequest, Winner},
+    },
+};
+
+#[derive(Clone, TypedBuilder)]
+pub struct MctsConfig {
+    #[builder(default = Some(1000))]
+    pub iterations: Option<u32>, // the search stops at whichever budget runs out first
+    #[builder(default)]
+    pub time_limit: Option<Duration>,
+    #[builder(default = 1.4)]
+    pub exploration: f64,
+    #[builder(default = Some(20))]
+    pub rollout_turns: Option<u32>, // rollouts past this are scored by remaining hp
+    #[builder(default)]
+    pub seed: u64,
+}
+
+#[derive(Default)]
+struct ChoiceStats {
+    visits: u32,
+    total_reward: f64,
+}
+
+// Each side keeps its own statistics and picks independently of the other (decoupled UCT).
+//  Nodes are reached by the sequence of joint choices, not by state, so chance is
+//  sampled by replaying from the root with a new seed each iteration
+#[derive(Default)]
+struct Node {
+    visits: u32,
+    side_stats: [HashMap<SideChoice, ChoiceStats>; 2],
+    children: HashMap<(SideChoice, SideChoice), usize>,
+}
+
+// Simultaneous move Monte Carlo tree search over copies of the battle, assuming
+//  both sides can see everything
+pub struct MctsAgent {
+    config: MctsConfig,
+    rollout_policy: Box<dyn Agent>,
+    rng: StdRng,
+    pending_inputs: PendingInputs,
+}
+
+impl MctsAgent {
+    pub fn new(config: MctsConfig, rollout_policy: Box<dyn Agent>) -> Self {
+        let rng = StdRng::seed_from_u64(config.seed);
+        Self {
+            config,
+            rollout_policy,
+            rng,
+            pending_inputs: PendingInputs::default(),
+        }
+    }
+
+    pub fn with_random_rollouts(config: MctsConfig) -> Self {
+        let rollout_policy = Box::new(RandomAgent::new(config.seed));
+        Self::new(config, rollout_policy)
+    }
+
+    // Returns the most visited choice of the given side at the root
+    fn search(&mut self, root: &Battle, is_trainer_1: bool) -> SideChoice {
+        let start = Instant::now();
+        let mut nodes = vec![Node::default()];
+        let mut iterations = 0;
+        while self
+            .config
+            .iterations
+            .is_none_or(|limit| iterations < limit)
+            && self
+                .config
+                .time_limit
+                .is_none_or(|limit| start.elapsed() < limit)
+        {
+            self.run_iteration(root, &mut nodes);
+            iterations += 1;
+        }
+
+        let side = if is_trainer_1 { 0 } else { 1 };
+        let BattleRequest::Request(side_1_request, side_2_request) = root.get_request() else {
+            panic!("Search started without a move or switch request");
+        };
+        let side_request = if is_trainer_1 {
+            side_1_request
+        } else {
+            side_2_request
+        };
+        choice::get_side_choices(&side_request)
+            .into_iter()
+            .max_by_key(|side_choice| {
+                nodes[0].side_stats[side]
+                    .get(side_choice)
+                    .map_or(0, |stats| stats.visits)
+            })
+            .expect("Request without any choices")
+    }
+
+    fn run_iteration(&mut self, root: &Battle, nodes: &mut Vec<Node>) {
+        let mut battle = root.clone();
+        battle.set_seed(self.rng.r#gen());
+
+        let mut path: Vec<(usize, SideChoice, SideChoice)> = vec![];
+        let mut node_idx = 0;
+        let mut request = battle.get_request();
+        let reward = loop {
+            let (side_1_request, side_2_request) = match request {
+                BattleRequest::BattleEnded(winner) => break Self::get_reward(winner),
+                BattleRequest::TeamPreview(_, _) => panic!("Search cannot plan team preview"),
+                BattleRequest::Request(side_1_request, side_2_request) => {
+                    (side_1_request, side_2_request)
+                }
+            };
+            if nodes[node_idx].visits == 0 && node_idx != 0 {
+                break self.rollout(&mut battle, (side_1_request, side_2_request));
+            }
+
+            let side_1_choice = self.select_choice(
+                &nodes[node_idx],
+                0,
+                choice::get_side_choices(&side_1_request),
+            );
+            let side_2_choice = self.select_choice(
+                &nodes[node_idx],
+                1,
+                choice::get_side_choices(&side_2_request),
+            );
+            request = battle.process_input((
+                choice::to_side_input(&side_1_choice),
+                choice::to_side_input(&side_2_choice),
+            ));
+
+            let next_idx = nodes.len();
+            let child_idx = *nodes[node_idx]
+                .children
+                .entry((side_1_choice.clone(), side_2_choice.clone()))
+                .or_insert(next_idx);
+            if child_idx == next_idx {
+                nodes.push(Node::default());
+            }
+            path.push((node_idx, side_1_choice, side_2_choice));
+            node_idx = child_idx;
+        };
+
+        nodes[node_idx].visits += 1;
+        for (node_idx, side_1_choice, side_2_choice) in path {
+            let node = &mut nodes[node_idx];
+            node.visits += 1;
+            for (side, side_choice, side_reward) in
+                [(0, side_1_choice, reward), (1, side_2_choice, 1.0 - reward)]
+            {
+                let stats = node.side_stats[side].entry(side_choice).or_default();
+                stats.visits += 1;
+                stats.total_reward += side_reward;
+            }
+        }
+    }
+
+    // UCB1 over the choices legal in this iteration, trying each unvisited one first
+    fn select_choice(
+        &mut self,
+        node: &Node,
+        side: usize,
+        side_choices: Vec<SideChoice>,
+    ) -> SideChoice {
+        let stats = &node.side_stats[side];
+        let unvisited: Vec<&SideChoice> = side_choices
+            .iter()
+            .filter(|side_choice| stats.get(*side_choice).is_none_or(|s| s.visits == 0))
+            .collect();
+        if !unvisited.is_empty() {
+            return unvisited[self.rng.gen_range(0..unvisited.len())].clone();
+        }
+
+        let total_visits: u32 = side_choices.iter().map(|c| stats[c].visits).sum();
+        let log_visits = (total_visits as f64).ln();
+        side_choices
+            .into_iter()
+            .max_by(|a, b| {
+                let score = |side_choice: &SideChoice| {
+                    let s = &stats[side_choice];
+                    s.total_reward / s.visits as f64
+                        + self.config.exploration * (log_visits / s.visits as f64).sqrt()
+                };
+                score(a).total_cmp(&score(b))
+            })
+            .expect("Request without any choices")
+    }
+
+    // Plays on with the rollout policy, returning trainer 1's reward
+    fn rollout(&mut self, battle: &mut Battle, requests: (SideRequest, SideRequest)) -> f64 {
+        let start_turn = battle.battle_state().turn;
+        let (mut side_1_request, mut side_2_request) = requests;
+        loop {
+            if self
+                .config
+                .rollout_turns
+                .is_some_and(|limit| battle.battle_state().turn - start_turn >= limit)
+            {
+                // unfinished rollouts favour the side with more of its team's hp left
+                return expectiminimax_agent::hp_evaluation(battle.battle_state());
+            }
+
+            let input = (
+                runner::get_side_input(battle, self.rollout_policy.as_mut(), true, &side_1_request),
+                runner::get_side_input(
+                    battle,
+                    self.rollout_policy.as_mut(),
+                    false,
+                    &side_2_request,
+                ),
+            );
+            match battle.process_input(input) {
+                BattleRequest::BattleEnded(winner) => return Self::get_reward(winner),
+                BattleRequest::Request(side_1, side_2) => {
+                    side_1_request = side_1;
+                    side_2_request = side_2;
+                }
+                BattleRequest::TeamPreview(_, _) => panic!("Search cannot plan team preview"),
+            }
+        }
+    }
+
+    fn get_reward(winner: Winner) -> f64 {
+        match winner {
+            Winner::Trainer1 => 1.0,
+            Winner::Trainer2 => 0.0,
+            Winner::Draw => 0.5,
+        }
+    }
+}
+
+impl Agent for MctsAgent {
+    fn choose_input(
+        &mut self,
+        observation: &Observation,
+        _request: &SingleBattleRequest,
+    ) -> SingleInput {
+        if let Some(input) = self.pending_inputs.take(observation.slot) {
+            return input;
+        }
+
+        let side_choice = self.search(
+            &observation.clone_battle_for_search(),
+            observation.is_trainer_1,
+        );
+        self.pending_inputs.replace(&side_choice, observation.slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        agent::test_utils::{assert_same_choice, get_chosen_slot, get_search_battle},
+        battle::actions::MoveSlot,
+    };
+
+    #[test]
+    fn takes_a_guaranteed_ko() {
+        let battle = get_search_battle();
+        let mut agent =
+            MctsAgent::with_random_rollouts(MctsConfig::builder().iterations(Some(200)).build());
+        assert_eq!(get_chosen_slot(&mut agent, &battle), Some(MoveSlot::Slot1));
+    }
+
+    #[test]
+    fn same_seed_searches_the_same() {
+        let config = MctsConfig::builder().iterations(Some(50)).seed(7).build();
+        assert_same_choice(&get_search_battle(), || {
+            MctsAgent::with_random_rollouts(config.clone())
+        });
+    }
+
+    #[test]
+    fn finished_rollouts_score_the_winner() {
+        assert_eq!(MctsAgent::get_reward(Winner::Trainer1), 1.0);
+        assert_eq!(MctsAgent::get_reward(Winner::Draw), 0.5);
+    }
+}
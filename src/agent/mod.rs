@@ -0,0 +1,11 @@
+pub mod agent;
+pub mod choice;
+pub mod expectiminimax_agent;
+pub mod matrix_game;
+pub mod max_damage_agent;
+pub mod mcts_agent;
+pub mod random_agent;
+pub mod runner;
+pub mod set_inference;
+#[cfg(test)]
+pub mod test_utils;
@@ -0,0 +1,39 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{
+    agent::agent::{Agent, Observation},
+    battle::{actions::SwitchSlot, battle_input::SingleInput, battle_request::SingleBattleRequest},
+};
+
+// Picks uniformly between every legal move and switch
+pub struct RandomAgent {
+    rng: StdRng,
+}
+
+impl RandomAgent {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Agent for RandomAgent {
+    fn choose_input(
+        &mut self,
+        _observation: &Observation,
+        request: &SingleBattleRequest,
+    ) -> SingleInput {
+        match request {
+            SingleBattleRequest::SwitchInRequest(valid_switches) => {
+                let idx = self.rng.gen_range(0..valid_switches.switches.len());
+                SingleInput::SwitchInInput(SwitchSlot::from_index(valid_switches.switches[idx]))
+            }
+            SingleBattleRequest::ActionRequest(valid_actions) => {
+                let mut actions = valid_actions.get_actions();
+                let idx = self.rng.gen_range(0..actions.len());
+                SingleInput::StandardInput(actions.swap_remove(idx))
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    agent::agent::{Agent, Observation},
+    battle::{
+        battle::Battle,
+        battle_input::SideInput,
+        battle_request::{BattleRequest, SideRequest, Winner},
+    },
+    core::pokemon::pokemon::Pokemon,
+};
+
+// Stops two agents that can't damage each other from playing forever
+const TURN_LIMIT: u32 = 1000;
+
+// Plays a singles battle to the end, with every random roll drawn from the seed
+pub fn run_battle(
+    agent_1: &mut dyn Agent,
+    agent_2: &mut dyn Agent,
+    teams: (Vec<Pokemon>, Vec<Pokemon>),
+    seed: u64,
+) -> Winner {
+    let (trainer_1_mons, trainer_2_mons) = teams;
+    let mut battle = Battle::new(trainer_1_mons, trainer_2_mons);
+    battle.set_seed(seed);
+    battle.set_turn_limit(Some(TURN_LIMIT));
+
+    let mut request = battle.start();
+    loop {
+        request = match request {
+            BattleRequest::BattleEnded(winner) => return winner,
+            BattleRequest::Request(side_1_request, side_2_request) => {
+                let input = (
+                    get_side_input(&battle, agent_1, true, &side_1_request),
+                    get_side_input(&battle, agent_2, false, &side_2_request),
+                );
+                battle.process_input(input)
+            }
+            BattleRequest::TeamPreview(_, _) => {
+                panic!("Battles started without a format have no team preview")
+            }
+        };
+    }
+}
+
+pub fn get_side_input(
+    battle: &Battle,
+    agent: &mut dyn Agent,
+    is_trainer_1: bool,
+    side_request: &SideRequest,
+) -> SideInput {
+    side_request
+        .iter()
+        .enumerate()
+        .map(|(slot, request)| {
+            request.as_ref().map(|request| {
+                agent.choose_input(&Observation::new(battle, is_trainer_1, slot), request)
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,456 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashSet},
+};
+
+use rand::{Rng, distributions::WeightedIndex, prelude::Distribution};
+use typed_builder::TypedBuilder;
+
+use crate::{
+    battle::side_observation::{PokemonView, SideObservation},
+    core::{
+        ability::ability::Ability,
+        item::item::Item,
+        pokemon::{pokemon::Pokemon, species::Species},
+        pokemove::move_name::MoveName,
+        poketype::poketype::PokeType,
+    },
+    showdown::{
+        showdown_set::{self, ShowdownSet},
+        usage_stats::{SpeciesUsage, Spread, UsageStats},
+    },
+};
+
+// Keeps a move that every set runs from making other movesets impossible
+const MAX_MOVE_CHANCE: f64 = 0.99;
+
+// A full set the opponent could be using
+#[derive(Clone)]
+pub struct CandidateSet {
+    pub probability: f64,
+    pub pokemon: Pokemon,
+}
+
+// The most likely full sets for one opponent pokemon, most likely first. Probabilities
+//  are renormalized over the sets that were kept
+pub struct SetDistribution {
+    pub sets: Vec<CandidateSet>,
+}
+
+impl SetDistribution {
+    pub fn most_likely(&self) -> &Pokemon {
+        &self.sets[0].pokemon
+    }
+
+    pub fn sample(&self, rng: &mut impl Rng) -> &Pokemon {
+        let distribution = WeightedIndex::new(self.sets.iter().map(|set| set.probability))
+            .expect("Set distribution should have a positive total");
+        &self.sets[distribution.sample(rng)].pokemon
+    }
+
+    pub fn get_move_probability(&self, move_name: MoveName) -> f64 {
+        self.sets
+            .iter()
+            .filter(|set| set.pokemon.moves.contains(&move_name))
+            .map(|set| set.probability)
+            .sum()
+    }
+
+    pub fn get_item_probability(&self, item: Option<Item>) -> f64 {
+        self.sets
+            .iter()
+            .filter(|set| set.pokemon.item == item)
+            .map(|set| set.probability)
+            .sum()
+    }
+}
+
+#[derive(Clone, TypedBuilder)]
+pub struct SetInferenceConfig {
+    #[builder(default = 64)]
+    pub max_sets: usize,
+    #[builder(default = 12)]
+    pub max_candidate_moves: usize, // most used moves considered for unrevealed slots
+}
+
+// What is known about a pokemon before inferring the rest
+struct Revealed {
+    species: Species,
+    level: u8,
+    moves: Vec<MoveName>,
+    item: Option<Option<Item>>,
+    ability: Option<Ability>,
+    tera_type: Option<PokeType>,
+}
+
+// Infers opponent sets from usage stats, treating the ability, item, spread, tera type
+//  and moveset as independent and conditioning each on what has been revealed
+pub struct SetInference<'a> {
+    usage_stats: &'a UsageStats,
+    config: SetInferenceConfig,
+}
+
+impl<'a> SetInference<'a> {
+    pub fn new(usage_stats: &'a UsageStats, config: SetInferenceConfig) -> Self {
+        Self {
+            usage_stats,
+            config,
+        }
+    }
+
+    // One distribution per opposing pokemon seen so far, in the order of the observation
+    pub fn infer_opponent_sets(
+        &self,
+        observation: &SideObservation,
+    ) -> Vec<Option<SetDistribution>> {
+        observation
+            .opponent_side
+            .pokemon
+            .iter()
+            .map(|pokemon| self.infer(pokemon))
+            .collect()
+    }
+
+    // Sets consistent with everything revealed about a pokemon, None without usage stats
+    //  for its species
+    pub fn infer(&self, pokemon: &PokemonView) -> Option<SetDistribution> {
+        self.infer_revealed(&Revealed {
+            species: pokemon.species,
+            level: pokemon.level,
+            moves: pokemon.moves.iter().flatten().copied().collect(),
+            item: pokemon.item,
+            ability: pokemon.ability,
+            tera_type: pokemon.tera_type,
+        })
+    }
+
+    // Sets for a species only seen at team preview
+    pub fn infer_species(&self, species: Species, level: u8) -> Option<SetDistribution> {
+        self.infer_revealed(&Revealed {
+            species,
+            level,
+            moves: vec![],
+            item: None,
+            ability: None,
+            tera_type: None,
+        })
+    }
+
+    fn infer_revealed(&self, revealed: &Revealed) -> Option<SetDistribution> {
+        let usage = self.usage_stats.get(&revealed.species)?;
+
+        let abilities = match revealed.ability {
+            Some(ability) => vec![(ability, 1.0)],
+            None => usage.abilities.clone(),
+        };
+        let items = match revealed.item {
+            Some(item) => vec![(item, 1.0)],
+            None if usage.items.is_empty() => vec![(None, 1.0)],
+            None => usage.items.clone(),
+        };
+        let spreads = if usage.spreads.is_empty() {
+            vec![(
+                Spread {
+                    nature: showdown_set::DEFAULT_NATURE,
+                    evs: showdown_set::default_evs(),
+                },
+                1.0,
+            )]
+        } else {
+            usage.spreads.clone()
+        };
+        // None keeps the species' default tera type
+        let tera_types = match revealed.tera_type {
+            Some(tera_type) => vec![(Some(tera_type), 1.0)],
+            None if usage.tera_types.is_empty() => vec![(None, 1.0)],
+            None => usage
+                .tera_types
+                .iter()
+                .map(|(tera_type, probability)| (Some(*tera_type), *probability))
+                .collect(),
+        };
+        let movesets = self.get_movesets(usage, &revealed.moves);
+
+        let probabilities = [
+            get_probabilities(&abilities),
+            get_probabilities(&items),
+            get_probabilities(&spreads),
+            get_probabilities(&tera_types),
+            get_probabilities(&movesets),
+        ];
+        // sets that fail validation are skipped, so a few extra are enumerated
+        let mut sets: Vec<CandidateSet> =
+            get_top_combinations(&probabilities, 2 * self.config.max_sets)
+                .into_iter()
+                .filter_map(|(idxs, probability)| {
+                    let spread = &spreads[idxs[2]].0;
+                    ShowdownSet {
+                        species: revealed.species,
+                        item: items[idxs[1]].0,
+                        ability: abilities[idxs[0]].0,
+                        level: revealed.level,
+                        moves: movesets[idxs[4]].0.clone(),
+                        evs: spread.evs.clone(),
+                        ivs: showdown_set::default_ivs(),
+                        nature: spread.nature,
+                        tera_type: tera_types[idxs[3]].0,
+                    }
+                    .into_pokemon()
+                    .ok()
+                    .map(|pokemon| CandidateSet {
+                        probability,
+                        pokemon,
+                    })
+                })
+                .take(self.config.max_sets)
+                .collect();
+
+        let total: f64 = sets.iter().map(|set| set.probability).sum();
+        if sets.is_empty() || total <= 0.0 {
+            return None;
+        }
+        for set in sets.iter_mut() {
+            set.probability /= total;
+        }
+        Some(SetDistribution { sets })
+    }
+
+    // Movesets containing every revealed move, filled from the most used moves. Each move
+    //  is treated as an independent chance to be on the set, conditioned on the set having
+    //  exactly four moves
+    fn get_movesets(
+        &self,
+        usage: &SpeciesUsage,
+        revealed_moves: &[MoveName],
+    ) -> Vec<(Vec<MoveName>, f64)> {
+        let candidates: Vec<(MoveName, f64)> = usage
+            .moves
+            .iter()
+            .filter(|(move_name, _)| !revealed_moves.contains(move_name))
+            .take(self.config.max_candidate_moves)
+            .map(|(move_name, chance)| {
+                let chance = chance.min(MAX_MOVE_CHANCE);
+                (*move_name, chance / (1.0 - chance))
+            })
+            .collect();
+        let num_unrevealed = 4usize
+            .saturating_sub(revealed_moves.len())
+            .min(candidates.len());
+
+        let mut movesets = vec![];
+        let mut chosen = vec![];
+        add_movesets(&candidates, num_unrevealed, 0, &mut chosen, &mut movesets);
+
+        let mut movesets: Vec<(Vec<MoveName>, f64)> = movesets
+            .into_iter()
+            .map(|(idxs, odds)| {
+                let mut moveset = revealed_moves.to_vec();
+                moveset.extend(idxs.iter().map(|idx: &usize| candidates[*idx].0));
+                (moveset, odds)
+            })
+            .collect();
+        let total: f64 = movesets.iter().map(|(_, odds)| odds).sum();
+        if total <= 0.0 {
+            return vec![];
+        }
+        for (_, odds) in movesets.iter_mut() {
+            *odds /= total;
+        }
+        movesets.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        movesets
+    }
+}
+
+// Every way of picking num_moves of the candidates, with the product of their odds
+fn add_movesets(
+    candidates: &[(MoveName, f64)],
+    num_moves: usize,
+    start: usize,
+    chosen: &mut Vec<usize>,
+    movesets: &mut Vec<(Vec<usize>, f64)>,
+) {
+    if chosen.len() == num_moves {
+        let odds = chosen.iter().map(|idx| candidates[*idx].1).product();
+        movesets.push((chosen.clone(), odds));
+        return;
+    }
+    for idx in start..candidates.len() {
+        chosen.push(idx);
+        add_movesets(candidates, num_moves, idx + 1, chosen, movesets);
+        chosen.pop();
+    }
+}
+
+fn get_probabilities<T>(values: &[(T, f64)]) -> Vec<f64> {
+    values.iter().map(|(_, probability)| *probability).collect()
+}
+
+// A pick from each list with the product of their probabilities, ordered by it
+struct Combination {
+    probability: f64,
+    idxs: Vec<usize>,
+}
+
+impl PartialEq for Combination {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Combination {}
+
+impl PartialOrd for Combination {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Combination {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.probability.total_cmp(&other.probability)
+    }
+}
+
+// The k most likely picks of one entry from each list, for independent lists sorted
+//  from most likely. Best first search, where each pick leads to the ones with a single
+//  list moved one entry further down
+fn get_top_combinations(probabilities: &[Vec<f64>], k: usize) -> Vec<(Vec<usize>, f64)> {
+    if probabilities.iter().any(Vec::is_empty) {
+        return vec![];
+    }
+    let get_probability = |idxs: &[usize]| -> f64 {
+        idxs.iter()
+            .zip(probabilities)
+            .map(|(idx, list)| list[*idx])
+            .product()
+    };
+
+    let start = vec![0; probabilities.len()];
+    let mut visited = HashSet::from([start.clone()]);
+    let mut frontier = BinaryHeap::from([Combination {
+        probability: get_probability(&start),
+        idxs: start,
+    }]);
+    let mut combinations = vec![];
+    while combinations.len() < k {
+        let Some(Combination { probability, idxs }) = frontier.pop() else {
+            break;
+        };
+        for list_idx in 0..probabilities.len() {
+            if idxs[list_idx] + 1 >= probabilities[list_idx].len() {
+                continue;
+            }
+            let mut next = idxs.clone();
+            next[list_idx] += 1;
+            if visited.insert(next.clone()) {
+                frontier.push(Combination {
+                    probability: get_probability(&next),
+                    idxs: next,
+                });
+            }
+        }
+        combinations.push((idxs, probability));
+    }
+    combinations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USAGE_JSON: &str = r#"{"info": {}, "data": {"Snorlax": {
+        "Abilities": {"thickfat": 60, "technician": 40},
+        "Items": {"choiceband": 50, "choicescarf": 30, "nothing": 20},
+        "Spreads": {"Adamant:252/252/0/0/4/0": 70, "Careful:252/4/0/0/252/0": 30},
+        "Moves": {"return": 100, "earthquake": 80, "rest": 60, "facade": 50,
+            "swordsdance": 40, "growl": 10}
+    }}}"#;
+
+    fn get_usage_stats() -> UsageStats {
+        UsageStats::from_json(USAGE_JSON).expect("usage stats should parse")
+    }
+
+    fn assert_normalized(distribution: &SetDistribution) {
+        let total: f64 = distribution.sets.iter().map(|set| set.probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(
+            distribution
+                .sets
+                .windows(2)
+                .all(|sets| sets[0].probability >= sets[1].probability)
+        );
+    }
+
+    #[test]
+    fn top_combinations_come_most_likely_first() {
+        let combinations = get_top_combinations(&[vec![0.6, 0.4], vec![0.7, 0.2, 0.1]], 4);
+        let idxs: Vec<Vec<usize>> = combinations.iter().map(|(idxs, _)| idxs.clone()).collect();
+        assert_eq!(idxs, vec![vec![0, 0], vec![1, 0], vec![0, 1], vec![1, 1]]);
+        let probabilities: Vec<f64> = combinations.iter().map(|(_, p)| *p).collect();
+        for (probability, expected) in probabilities.iter().zip([0.42, 0.28, 0.12, 0.08]) {
+            assert!((probability - expected).abs() < 1e-9);
+        }
+        assert!(get_top_combinations(&[vec![1.0], vec![]], 4).is_empty());
+    }
+
+    #[test]
+    fn unrevealed_sets_follow_usage() {
+        let usage_stats = get_usage_stats();
+        let inference = SetInference::new(&usage_stats, SetInferenceConfig::builder().build());
+        let distribution = inference
+            .infer_species(Species::Snorlax, 100)
+            .expect("Snorlax has usage stats");
+        assert_normalized(&distribution);
+        assert!(distribution.sets.len() <= 64);
+
+        let pokemon = distribution.most_likely();
+        assert_eq!(pokemon.ability, Ability::ThickFat);
+        assert_eq!(pokemon.item, Some(Item::ChoiceBand));
+        assert!(pokemon.moves.contains(&MoveName::Return));
+        assert!(!pokemon.moves.contains(&MoveName::Growl));
+        assert!(
+            distribution.get_move_probability(MoveName::Return)
+                > distribution.get_move_probability(MoveName::Growl)
+        );
+    }
+
+    #[test]
+    fn revealed_details_constrain_every_set() {
+        let usage_stats = get_usage_stats();
+        let inference = SetInference::new(&usage_stats, SetInferenceConfig::builder().build());
+        let distribution = inference
+            .infer_revealed(&Revealed {
+                species: Species::Snorlax,
+                level: 100,
+                moves: vec![MoveName::Growl, MoveName::Rest],
+                item: Some(None),
+                ability: Some(Ability::Technician),
+                tera_type: None,
+            })
+            .expect("Snorlax has usage stats");
+        assert_normalized(&distribution);
+        for set in &distribution.sets {
+            assert_eq!(set.pokemon.ability, Ability::Technician);
+            assert_eq!(set.pokemon.item, None);
+            assert!(set.pokemon.moves.contains(&MoveName::Growl));
+            assert!(set.pokemon.moves.contains(&MoveName::Rest));
+        }
+        assert_eq!(distribution.get_item_probability(None), 1.0);
+    }
+
+    #[test]
+    fn species_without_usage_have_no_sets() {
+        let usage_stats = get_usage_stats();
+        let inference = SetInference::new(&usage_stats, SetInferenceConfig::builder().build());
+        assert!(inference.infer_species(Species::Pikachu, 100).is_none());
+    }
+
+    #[test]
+    fn movesets_without_any_odds_are_dropped() {
+        let usage_stats = get_usage_stats();
+        let inference = SetInference::new(&usage_stats, SetInferenceConfig::builder().build());
+        let mut usage = usage_stats.get(&Species::Snorlax).unwrap().clone();
+        usage.moves.iter_mut().for_each(|(_, chance)| *chance = 0.0);
+        assert!(inference.get_movesets(&usage, &[]).is_empty());
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{
+    agent::agent::{Agent, Observation},
+    battle::{
+        actions::MoveSlot, battle::Battle, battle_input::SingleInput,
+        battle_request::BattleRequest, test_utils,
+    },
+};
+
+// Garchomp against a Pikachu on 1 hp, where Earthquake (Slot1) is a guaranteed KO
+pub fn get_search_battle() -> Battle {
+    let mut battle = test_utils::get_battle(
+        "Garchomp\nAbility: Sand Force\n- Swords Dance\n- Earthquake",
+        "Pikachu\nAbility: Technician\n- Tackle",
+        0,
+    );
+    battle
+        .battle_context()
+        .battle_state
+        .get_active_pokemon_mut(false, 0)
+        .pokemon
+        .hp = 1;
+    battle
+}
+
+// Move slot the agent picks for trainer 1, None for anything but a move
+pub fn get_chosen_slot(agent: &mut dyn Agent, battle: &Battle) -> Option<MoveSlot> {
+    let BattleRequest::Request(mut side_request, _) = battle.get_request() else {
+        panic!("Battle should be waiting on a move");
+    };
+    let request = side_request[0].take().unwrap();
+    match agent.choose_input(&Observation::new(battle, true, 0), &request) {
+        SingleInput::StandardInput(action) => action.get_move_slot(),
+        _ => None,
+    }
+}
+
+// Two agents built the same way pick the same move
+pub fn assert_same_choice<A: Agent>(battle: &Battle, new_agent: impl Fn() -> A) {
+    let choices: Vec<Option<MoveSlot>> = (0..2)
+        .map(|_| get_chosen_slot(&mut new_agent(), battle))
+        .collect();
+    assert_eq!(choices[0], choices[1]);
+}
@@ -6,6 +6,18 @@ pub enum MoveSlot {
     Slot3,
 }
 
+impl MoveSlot {
+    pub fn from_index(idx: usize) -> Self {
+        match idx {
+            0 => MoveSlot::Slot0,
+            1 => MoveSlot::Slot1,
+            2 => MoveSlot::Slot2,
+            3 => MoveSlot::Slot3,
+            _ => panic!("Move index {idx} out of range"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SwitchSlot {
     Slot0,
@@ -16,16 +28,79 @@ pub enum SwitchSlot {
     Slot5,
 }
 
-#[derive(PartialEq, Eq)]
+impl SwitchSlot {
+    pub fn from_index(idx: usize) -> Self {
+        match idx {
+            0 => SwitchSlot::Slot0,
+            1 => SwitchSlot::Slot1,
+            2 => SwitchSlot::Slot2,
+            3 => SwitchSlot::Slot3,
+            4 => SwitchSlot::Slot4,
+            5 => SwitchSlot::Slot5,
+            _ => panic!("Switch index {idx} out of range"),
+        }
+    }
+}
+
+// Target chosen for a single target move, as an active slot on either side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetLoc {
+    Opponent(usize),
+    Ally(usize),
+}
+
+// Once per battle transformation used alongside a move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveModifier {
+    None,
+    MegaEvolve,
+    ZMove, // the move is used as the Z-Move of its type
+    Dynamax,
+    Terastallize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
-    Move(MoveSlot),
+    Move(MoveSlot, MoveModifier),
+    TargetedMove(MoveSlot, TargetLoc, MoveModifier),
     Switch(SwitchSlot),
     Struggle,
+    Locked, // continue the move the active pokemon is locked into
 }
 
 impl Action {
     pub fn is_move(&self) -> bool {
-        matches!(self, Action::Move(_))
+        matches!(self, Action::Move(_, _) | Action::TargetedMove(_, _, _))
+    }
+
+    pub fn get_move_slot(&self) -> Option<MoveSlot> {
+        match self {
+            Action::Move(slot, _) | Action::TargetedMove(slot, _, _) => Some(*slot),
+            _ => None,
+        }
+    }
+
+    pub fn get_move_index(&self) -> Option<usize> {
+        self.get_move_slot().map(|slot| match slot {
+            MoveSlot::Slot0 => 0,
+            MoveSlot::Slot1 => 1,
+            MoveSlot::Slot2 => 2,
+            MoveSlot::Slot3 => 3,
+        })
+    }
+
+    pub fn get_target_loc(&self) -> Option<TargetLoc> {
+        match self {
+            Action::TargetedMove(_, target_loc, _) => Some(*target_loc),
+            _ => None,
+        }
+    }
+
+    pub fn get_move_modifier(&self) -> MoveModifier {
+        match self {
+            Action::Move(_, modifier) | Action::TargetedMove(_, _, modifier) => *modifier,
+            _ => MoveModifier::None,
+        }
     }
 
     pub fn is_switch(&self) -> bool {
@@ -1,42 +1,69 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use super::actions::Action;
 use crate::{
     battle::{
-        actions::SwitchSlot,
+        actions::{MoveModifier, TargetLoc},
         battle_context::BattleContext,
         battle_engine::BattleEngine,
         battle_input::{BattleInput, SingleInput},
-        battle_request::{ActionResponse, BattleRequest, SingleBattleRequest, Winner},
+        battle_request::{
+            ActionResponse, BattleRequest, SideRequest, SingleBattleRequest, TeamPreviewRequest,
+            Winner,
+        },
+        damage_calculator::DamageRolls,
+        game_type::GameType,
+        scripted_rolls::ScriptedRolls,
         state::BattleState,
         static_battle_handler::StaticBattleHandler,
+        team_preview_error::{TeamPreviewError, TeamPreviewErrorKind},
         turn_state::TurnState,
+        valid_actions::{ValidMoves, ValidSwitches},
     },
     common::context::MoveContext,
     core::{
+        mechanics::{generation::Generation, mechanics_profile::MechanicsProfile},
         pokemon::{pokemon::Pokemon, stat_enum::StatEnum},
         pokemove::{move_name::MoveName, move_target::MoveTarget},
-        poketype::{effectiveness, poketype::PokeType},
-        util::damage_utils,
     },
-    dex::pokemove::move_dex,
-    event::{
-        event_bus::EventBus,
-        event_queue::EventQueue,
-        event_type::{BeforeMoveEvent, CanApplyMoveEvent, DamageEvent, Event, OnPriorityEvent},
+    dex::pokemove::{gimmick_moves, move_dex},
+    event::{event_bus::EventBus, event_type::Event},
+    format::{
+        clause::Clause,
+        clause_handlers::SleepClauseHandler,
+        format::Format,
+        team_validator::{self, TeamValidationError},
     },
     query::{payload::PayloadMoveQuery, query::Query, query_bus::QueryBus},
 };
 
+// One way an input can play out: its probability, the battle after it and the next request
+pub type Outcome = (f64, Battle, BattleRequest);
+
+// Runs get_outcomes plays before giving up on listing a turn and sampling it instead
+pub const MAX_LISTED_RUNS: usize = 2048;
+pub const NUM_SAMPLED_RUNS: usize = 256;
+
+#[derive(Clone)]
 pub struct Battle {
     battle_state: BattleState,
     event_bus: EventBus,
     query_bus: QueryBus,
+    team_preview_size: Option<usize>, // set while waiting on team preview input
+    turn_limit: Option<u32>,
 }
 
 impl Battle {
     pub fn new(trainer_1_mons: Vec<Pokemon>, trainer_2_mons: Vec<Pokemon>) -> Self {
-        let mut event_bus = EventBus::new();
+        Self::new_with_game_type(trainer_1_mons, trainer_2_mons, GameType::Singles)
+    }
+
+    pub fn new_with_game_type(
+        trainer_1_mons: Vec<Pokemon>,
+        trainer_2_mons: Vec<Pokemon>,
+        game_type: GameType,
+    ) -> Self {
+        let event_bus = EventBus::new();
         let mut query_bus = QueryBus::new();
 
         // register defaults
@@ -45,50 +72,485 @@ impl Battle {
             .add_handler(Arc::new(StaticBattleHandler));
 
         Self {
-            battle_state: BattleState::new(trainer_1_mons, trainer_2_mons),
+            battle_state: BattleState::new(trainer_1_mons, trainer_2_mons, game_type),
             event_bus,
             query_bus,
+            team_preview_size: None,
+            turn_limit: None,
         }
     }
 
+    // Checks both teams against the format before building the battle, and returns
+    //  the first request, a team preview if the format has one.
+    //  On failure returns the errors for each side
+    pub fn init_with_format(
+        trainer_1_mons: Vec<Pokemon>,
+        trainer_2_mons: Vec<Pokemon>,
+        format: &Format,
+    ) -> Result<(Self, BattleRequest), (Vec<TeamValidationError>, Vec<TeamValidationError>)> {
+        let errors = (
+            team_validator::validate_team(format, &trainer_1_mons),
+            team_validator::validate_team(format, &trainer_2_mons),
+        );
+        if !errors.0.is_empty() || !errors.1.is_empty() {
+            return Err(errors);
+        }
+
+        let mut battle = Self::new_with_game_type(trainer_1_mons, trainer_2_mons, format.game_type);
+        battle.turn_limit = format.turn_limit;
+        battle.set_generation(format.generation);
+        if format.has_clause(Clause::Sleep) {
+            battle
+                .query_bus
+                .registry
+                .add_handler(Arc::new(SleepClauseHandler));
+        }
+
+        let request = match format.team_preview {
+            // the format's team size checks cover the team preview size
+            Some(team_size) => battle
+                .start_team_preview(team_size)
+                .expect("Team sizes should have been validated against the format"),
+            None => battle.start(),
+        };
+
+        Ok((battle, request))
+    }
+
     pub fn init_and_start(trainer_1_mons: Vec<Pokemon>, trainer_2_mons: Vec<Pokemon>) -> Self {
-        let mut battle = Self::new(trainer_1_mons, trainer_2_mons);
+        Self::init_and_start_with_game_type(trainer_1_mons, trainer_2_mons, GameType::Singles)
+    }
+
+    pub fn init_and_start_with_game_type(
+        trainer_1_mons: Vec<Pokemon>,
+        trainer_2_mons: Vec<Pokemon>,
+        game_type: GameType,
+    ) -> Self {
+        let mut battle = Self::new_with_game_type(trainer_1_mons, trainer_2_mons, game_type);
 
         battle.start_battle();
 
         battle
     }
 
-    pub fn start_battle(&mut self) {
-        let first_trainer = self.resolve_action_order(
-            &Action::Switch(SwitchSlot::Slot0),
-            &Action::Switch(SwitchSlot::Slot0),
+    // Switches the type chart, status numbers and move data to those of the generation
+    pub fn set_generation(&mut self, generation: Generation) {
+        self.battle_state.mechanics = MechanicsProfile::for_generation(generation);
+    }
+
+    pub fn set_turn_limit(&mut self, turn_limit: Option<u32>) {
+        self.turn_limit = turn_limit;
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.battle_state.set_seed(seed);
+    }
+
+    pub fn battle_state(&self) -> &BattleState {
+        &self.battle_state
+    }
+
+    // Starts the battle and resolves switch-in effects, returning the first request
+    pub fn start(&mut self) -> BattleRequest {
+        self.start_battle();
+        let mut turn_state = TurnState::new();
+        self.event_bus.drain_event_queue(
+            &mut self.battle_state,
+            &mut self.query_bus,
+            &mut turn_state,
         );
-        BattleEngine::switch_in_pokemon(&mut self.battle_context(), first_trainer, 0);
-        BattleEngine::switch_in_pokemon(&mut self.battle_context(), !first_trainer, 0);
+        self.generate_battle_request_from_turn_state(&turn_state)
+    }
+
+    // The request the battle is waiting on, rebuilt from the state so a copy can be resumed
+    pub fn get_request(&self) -> BattleRequest {
+        if let Some(team_size) = self.team_preview_size {
+            return BattleRequest::TeamPreview(
+                self.generate_team_preview_request(true, team_size),
+                self.generate_team_preview_request(false, team_size),
+            );
+        }
+
+        // a fainted active pokemon means the battle is waiting on switch-ins
+        let mut turn_state = TurnState::new();
+        for (trainer, slot) in self.battle_state.get_active_positions() {
+            if self
+                .battle_state
+                .get_active_pokemon(trainer, slot)
+                .is_fainted()
+            {
+                turn_state.record_faint(trainer, slot);
+            }
+        }
+        self.generate_battle_request_from_turn_state(&turn_state)
+    }
+
+    // Every way the input can play out with its probability, played on copies of the battle.
+    //  Outcomes that leave the battle in the same state are merged into one. Turns with more
+    //  roll combinations than can be listed, e.g. several multi-hit moves, are sampled instead
+    pub fn get_outcomes(&self, input: &BattleInput) -> Vec<Outcome> {
+        let mut outcomes: Vec<Outcome> = vec![];
+        let mut outcome_idxs: HashMap<String, usize> = HashMap::new();
+        let mut scripts = vec![vec![]];
+        let mut num_runs = 0;
+        while let Some(script) = scripts.pop() {
+            num_runs += 1;
+            if num_runs > MAX_LISTED_RUNS {
+                return self.sample_outcomes(input);
+            }
+
+            let mut battle = self.clone();
+            battle
+                .battle_state
+                .set_scripted_rolls(Some(ScriptedRolls::new(script)));
+            let request = battle.process_input(input.clone());
+            let scripted_rolls = battle
+                .battle_state
+                .take_scripted_rolls()
+                .expect("Scripted rolls removed during the turn");
+            scripts.extend(scripted_rolls.get_unexplored_scripts());
+
+            Battle::merge_outcome(
+                &mut outcomes,
+                &mut outcome_idxs,
+                (scripted_rolls.probability(), battle, request),
+            );
+        }
+        outcomes
+    }
+
+    // Plays the input NUM_SAMPLED_RUNS times, each copy seeded by its run so the result is
+    //  reproducible, and weighs every run the same
+    fn sample_outcomes(&self, input: &BattleInput) -> Vec<Outcome> {
+        let mut outcomes: Vec<Outcome> = vec![];
+        let mut outcome_idxs: HashMap<String, usize> = HashMap::new();
+        for run in 0..NUM_SAMPLED_RUNS {
+            let mut battle = self.clone();
+            battle.set_seed(run as u64);
+            let request = battle.process_input(input.clone());
+            Battle::merge_outcome(
+                &mut outcomes,
+                &mut outcome_idxs,
+                (1.0 / NUM_SAMPLED_RUNS as f64, battle, request),
+            );
+        }
+        outcomes
+    }
+
+    fn merge_outcome(
+        outcomes: &mut Vec<Outcome>,
+        outcome_idxs: &mut HashMap<String, usize>,
+        outcome: Outcome,
+    ) {
+        let state_key = outcome.1.battle_state.get_state_key();
+        match outcome_idxs.get(&state_key) {
+            Some(idx) => outcomes[*idx].0 += outcome.0,
+            None => {
+                outcome_idxs.insert(state_key, outcomes.len());
+                outcomes.push(outcome);
+            }
+        }
+    }
+
+    // Damage the action's move would deal right now, worked out on a copy of the battle
+    //  so the real one is untouched. Switches, status moves and immune targets take none
+    pub fn estimate_damage(&self, is_trainer_1: bool, slot: usize, action: &Action) -> u32 {
+        let Some(move_name) = self
+            .battle_state
+            .get_move_for_action(is_trainer_1, slot, action)
+        else {
+            return 0;
+        };
+
+        let mut battle = self.clone();
+        let move_context = Battle::create_move_context(
+            &battle.battle_state,
+            move_name,
+            is_trainer_1,
+            slot,
+            action.get_target_loc(),
+            action.get_move_modifier(),
+        );
+        BattleEngine::estimate_move_damage(&mut battle.battle_context(), &move_context)
+    }
+
+    // Every damage roll the action's move could deal right now, worked out on a copy of
+    //  the battle. None for switches and status moves
+    pub fn get_damage_rolls(
+        &self,
+        is_trainer_1: bool,
+        slot: usize,
+        action: &Action,
+    ) -> Option<DamageRolls> {
+        let move_name = self
+            .battle_state
+            .get_move_for_action(is_trainer_1, slot, action)?;
+
+        let mut battle = self.clone();
+        let move_context = Battle::create_move_context(
+            &battle.battle_state,
+            move_name,
+            is_trainer_1,
+            slot,
+            action.get_target_loc(),
+            action.get_move_modifier(),
+        );
+        BattleEngine::get_damage_rolls(&mut battle.battle_context(), &move_context)
+    }
+
+    // A copy of the battle as one trainer knows it, the opposing side keeping only what has
+    //  been revealed. See PokemonBattleInstance::hide_unrevealed_set
+    pub fn with_hidden_opponent_sets(&self, is_trainer_1: bool) -> Battle {
+        let mut battle = self.clone();
+        for idx in 0..battle.battle_state.get_side(!is_trainer_1).num_pokemon() {
+            BattleEngine::hide_unrevealed_set(&mut battle.battle_context(), !is_trainer_1, idx);
+        }
+        battle
+    }
+
+    // Plays only the end of turn effects, as if nobody had moved, and returns the hp each
+    //  active pokemon lost, negative for pokemon that recovered
+    pub fn process_end_of_turn(&mut self) -> Vec<((bool, usize), i32)> {
+        let positions = self.battle_state.get_active_positions();
+        let get_hp = |battle_state: &BattleState, (trainer, slot): (bool, usize)| {
+            battle_state.get_active_pokemon(trainer, slot).pokemon.hp as i32
+        };
+        let prev_hp: Vec<i32> = positions
+            .iter()
+            .map(|position| get_hp(&self.battle_state, *position))
+            .collect();
+
+        let mut turn_state = TurnState::new();
+        BattleEngine::queue_after_turn_effects(&mut self.battle_context());
+        self.event_bus.drain_event_queue(
+            &mut self.battle_state,
+            &mut self.query_bus,
+            &mut turn_state,
+        );
+        self.battle_state.turn += 1;
+
+        positions
+            .into_iter()
+            .zip(prev_hp)
+            .map(|(position, prev_hp)| (position, prev_hp - get_hp(&self.battle_state, position)))
+            .collect()
+    }
+
+    // Sends out the first pokemon in the party to each active slot
+    pub fn start_battle(&mut self) {
+        let first_trainer =
+            BattleEngine::resolve_speed_order(&mut self.battle_context(), (true, 0), (false, 0));
+        for trainer in [first_trainer, !first_trainer] {
+            for slot in 0..self.battle_state.get_side(trainer).num_active_slots() {
+                BattleEngine::switch_in_pokemon(&mut self.battle_context(), trainer, slot, slot);
+            }
+        }
+    }
+
+    // Shows each side the opposing species and waits for both lead orders
+    //  before sending anything out, in place of start_battle.
+    //  Fails if either party can't bring team_size pokemon
+    pub fn start_team_preview(
+        &mut self,
+        team_size: usize,
+    ) -> Result<BattleRequest, Vec<TeamPreviewError>> {
+        let active_slots = self.battle_state.game_type.active_slots();
+        let errors: Vec<TeamPreviewError> = [true, false]
+            .into_iter()
+            .filter_map(|trainer| {
+                let party_size = self.battle_state.get_side(trainer).num_pokemon();
+                (team_size < active_slots || team_size > party_size).then_some(TeamPreviewError {
+                    trainer_1: trainer,
+                    kind: TeamPreviewErrorKind::InvalidTeamSize {
+                        team_size,
+                        party_size,
+                    },
+                })
+            })
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        self.team_preview_size = Some(team_size);
+        for trainer in [true, false] {
+            let side = self.battle_state.get_side_mut(trainer);
+            side.team_preview = (0..side.num_pokemon())
+                .map(|idx| side.get_pokemon(idx).pokemon.species)
+                .collect();
+        }
+        Ok(BattleRequest::TeamPreview(
+            self.generate_team_preview_request(true, team_size),
+            self.generate_team_preview_request(false, team_size),
+        ))
+    }
+
+    fn generate_team_preview_request(&self, trainer: bool, team_size: usize) -> TeamPreviewRequest {
+        let opponent_side = self.battle_state.get_side(!trainer);
+        TeamPreviewRequest {
+            opponent_species: (0..opponent_side.num_pokemon())
+                .map(|idx| opponent_side.get_pokemon(idx).pokemon.species)
+                .collect(),
+            team_size,
+        }
     }
 
     pub fn process_input(&mut self, input: BattleInput) -> BattleRequest {
-        match input {
-            (None, None) => panic!("Illegal input: both trainers provided no action"),
-            (Some(SingleInput::SwitchInInput(slot1)), Some(SingleInput::SwitchInInput(slot2))) => {
-                todo!("Handle double switch-in")
+        // an invalid team order leaves the battle waiting on team preview, so the same
+        //  request is sent again. process_team_preview returns the reasons
+        if self.team_preview_size.is_some() {
+            return self
+                .process_team_preview(input)
+                .unwrap_or_else(|_| self.get_request());
+        }
+
+        let (side1_input, side2_input) = input;
+        let inputs: Vec<(bool, usize, SingleInput)> = [(true, side1_input), (false, side2_input)]
+            .into_iter()
+            .flat_map(|(trainer, side_input)| {
+                side_input
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(move |(slot, input)| input.map(|input| (trainer, slot, input)))
+            })
+            .collect();
+
+        if inputs.is_empty() {
+            panic!("Illegal input: both trainers provided no action");
+        }
+
+        if inputs
+            .iter()
+            .any(|(_, _, input)| matches!(input, SingleInput::StandardInput(_)))
+        {
+            let actions = inputs
+                .into_iter()
+                .map(|(trainer, slot, input)| match input {
+                    SingleInput::StandardInput(action) => (trainer, slot, action),
+                    _ => panic!("Illegal input combination: expected a move or switch"),
+                })
+                .collect();
+            self.process_actions(actions)
+        } else {
+            let switch_ins = inputs
+                .into_iter()
+                .map(|(trainer, slot, input)| match input {
+                    SingleInput::SwitchInInput(switch_slot) => (
+                        trainer,
+                        slot,
+                        Action::Switch(switch_slot).get_switch_index(),
+                    ),
+                    SingleInput::StandardInput(_) => unreachable!(),
+                    SingleInput::TeamOrderInput(_) => {
+                        panic!("Illegal input: team order outside of team preview")
+                    }
+                })
+                .collect();
+            self.process_switch_ins(switch_ins)
+        }
+    }
+
+    // Brings each side's chosen pokemon, leads first, and starts the battle. Checks both
+    //  orders before changing anything, so the battle is still waiting on them on failure
+    pub fn process_team_preview(
+        &mut self,
+        input: BattleInput,
+    ) -> Result<BattleRequest, Vec<TeamPreviewError>> {
+        let team_size = self
+            .team_preview_size
+            .expect("Illegal input: no team preview in progress");
+
+        let (side1_input, side2_input) = input;
+        let mut orders = vec![];
+        let mut errors = vec![];
+        for (trainer, side_input) in [(true, side1_input), (false, side2_input)] {
+            let party_size = self.battle_state.get_side(trainer).num_pokemon();
+            let result = match side_input.into_iter().next() {
+                Some(Some(SingleInput::TeamOrderInput(order))) => {
+                    Self::check_team_order(&order, team_size, party_size).map(|_| order)
+                }
+                _ => Err(TeamPreviewErrorKind::MissingTeamOrder),
+            };
+            match result {
+                Ok(order) => orders.push((trainer, order)),
+                Err(kind) => errors.push(TeamPreviewError {
+                    trainer_1: trainer,
+                    kind,
+                }),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        for (trainer, order) in orders {
+            self.battle_state.get_side_mut(trainer).select_team(&order);
+        }
+        self.team_preview_size = None;
+        self.start_battle();
+
+        let mut turn_state = TurnState::new();
+        self.event_bus.drain_event_queue(
+            &mut self.battle_state,
+            &mut self.query_bus,
+            &mut turn_state,
+        );
+        Ok(self.generate_battle_request_from_turn_state(&turn_state))
+    }
+
+    fn check_team_order(
+        order: &[usize],
+        team_size: usize,
+        party_size: usize,
+    ) -> Result<(), TeamPreviewErrorKind> {
+        if order.len() != team_size {
+            return Err(TeamPreviewErrorKind::WrongOrderLength {
+                len: order.len(),
+                team_size,
+            });
+        }
+        for (position, idx) in order.iter().enumerate() {
+            if *idx >= party_size {
+                return Err(TeamPreviewErrorKind::IndexOutOfRange {
+                    idx: *idx,
+                    party_size,
+                });
+            }
+            if order[..position].contains(idx) {
+                return Err(TeamPreviewErrorKind::DuplicateIndex { idx: *idx });
             }
-            (Some(SingleInput::SwitchInInput(slot1)), None) => {
-                todo!("Handle single switch-in for trainer 1")
+        }
+        Ok(())
+    }
+
+    // Replaces fainted pokemon at the end of a turn
+    fn process_switch_ins(&mut self, switch_ins: Vec<(bool, usize, usize)>) -> BattleRequest {
+        let mut turn_state = TurnState::new();
+        for (trainer, slot, switch_idx) in switch_ins {
+            if !self
+                .battle_state
+                .get_active_pokemon(trainer, slot)
+                .is_fainted()
+            {
+                panic!("Illegal input: switch-in for a slot that has not fainted");
             }
-            (None, Some(SingleInput::SwitchInInput(slot2))) => {
-                todo!("Handle single switch-in for trainer 2")
+            // two slots asking for the same pokemon only send it out once
+            if self.battle_state.get_side(trainer).is_active(switch_idx) {
+                continue;
             }
-            (
-                Some(SingleInput::StandardInput(action1)),
-                Some(SingleInput::StandardInput(action2)),
-            ) => self.process_actions(action1, action2),
-            _ => panic!("Illegal input combination"),
+
+            BattleEngine::switch_pokemon(&mut self.battle_context(), trainer, slot, switch_idx);
+            self.event_bus.drain_event_queue(
+                &mut self.battle_state,
+                &mut self.query_bus,
+                &mut turn_state,
+            );
         }
+
+        self.generate_battle_request_from_turn_state(&turn_state)
     }
 
-    fn process_actions(&mut self, action1: Action, action2: Action) -> BattleRequest {
+    fn process_actions(&mut self, actions: Vec<(bool, usize, Action)>) -> BattleRequest {
         let mut turn_state = TurnState::new();
         self.event_bus.publish(
             &Event::BeginTurn,
@@ -96,54 +558,202 @@ impl Battle {
             &mut self.query_bus,
             &mut turn_state,
         );
-        let action1_first = self.resolve_action_order(&action1, &action2);
 
-        if action1_first {
-            self.process_action(true, &action1, &mut turn_state);
-            // TODO: Handle mid-turn switch e.g. U-Turn
-            if !self.battle_state.get_active_pokemon(false).is_fainted() {
-                self.process_action(false, &action2, &mut turn_state);
+        // fainted slots that can't be refilled have nothing to do
+        let actions: Vec<(bool, usize, Action)> = actions
+            .into_iter()
+            .filter(|(trainer, slot, _)| {
+                !self
+                    .battle_state
+                    .get_active_pokemon(*trainer, *slot)
+                    .is_fainted()
+            })
+            .collect();
+
+        for (trainer, slot, action) in actions.iter() {
+            self.validate_action(*trainer, *slot, action);
+            self.validate_move_modifier(*trainer, *slot, action);
+        }
+
+        // mega evolution happens before turn order is decided, so the new speed counts
+        for (trainer, slot, action) in actions.iter() {
+            if action.get_move_modifier() == MoveModifier::MegaEvolve {
+                BattleEngine::mega_evolve(&mut self.battle_context(), *trainer, *slot);
             }
-        } else {
-            self.process_action(false, &action2, &mut turn_state);
+        }
+
+        self.queue_moves(&actions);
+        let actions = self.resolve_action_order(actions);
+
+        // every pokemon dynamaxes or terastallizes before anyone moves
+        for (trainer, slot, action) in actions.iter() {
+            match action.get_move_modifier() {
+                MoveModifier::Dynamax => {
+                    BattleEngine::dynamax(&mut self.battle_context(), *trainer, *slot)
+                }
+                MoveModifier::Terastallize => {
+                    BattleEngine::terastallize(&mut self.battle_context(), *trainer, *slot)
+                }
+                _ => {}
+            }
+        }
+
+        for (trainer, slot, action) in actions.iter() {
             // TODO: Handle mid-turn switch e.g. U-Turn
-            if !self.battle_state.get_active_pokemon(true).is_fainted() {
-                self.process_action(true, &action1, &mut turn_state);
+            if self
+                .battle_state
+                .get_active_pokemon(*trainer, *slot)
+                .is_fainted()
+            {
+                continue;
             }
+            self.process_action(*trainer, *slot, action, &mut turn_state);
         }
 
-        BattleEngine::queue_after_turn_effects(&mut self.battle_context(), &mut turn_state);
+        BattleEngine::queue_after_turn_effects(&mut self.battle_context());
         self.event_bus.drain_event_queue(
             &mut self.battle_state,
             &mut self.query_bus,
             &mut turn_state,
         );
+        self.battle_state.turn += 1;
 
-        self.generate_battle_request_from_turn_state(&turn_state)
+        match self.generate_battle_request_from_turn_state(&turn_state) {
+            BattleRequest::Request(_, _)
+                if self
+                    .turn_limit
+                    .is_some_and(|limit| self.battle_state.turn >= limit) =>
+            {
+                BattleRequest::BattleEnded(self.get_turn_limit_winner())
+            }
+            request => request,
+        }
+    }
+
+    // Move locks, pp and switches are only offered through the valid actions, so a
+    //  chosen move or switch outside them is rejected here
+    fn validate_action(&self, is_trainer_1: bool, slot: usize, action: &Action) {
+        let valid_actions = self
+            .battle_state
+            .get_side(is_trainer_1)
+            .get_valid_actions(slot, &self.battle_state.mechanics);
+        let is_valid = match (action, &valid_actions.valid_moves) {
+            (Action::Switch(_), _) => valid_actions.valid_switches.is_some_and(|valid_switches| {
+                valid_switches.switches.contains(&action.get_switch_index())
+            }),
+            (Action::Struggle, ValidMoves::Struggle) | (Action::Locked, ValidMoves::Locked) => true,
+            (Action::Move(_, _) | Action::TargetedMove(_, _, _), ValidMoves::Moves(move_idxs)) => {
+                action
+                    .get_move_index()
+                    .is_some_and(|idx| move_idxs.contains(&idx))
+            }
+            _ => false,
+        };
+        if !is_valid {
+            panic!(
+                "Illegal input: {:?} is not available for this pokemon",
+                action
+            );
+        }
+    }
+
+    fn validate_move_modifier(&self, is_trainer_1: bool, slot: usize, action: &Action) {
+        let modifier = action.get_move_modifier();
+        if modifier == MoveModifier::None {
+            return;
+        }
+
+        let valid_actions = self
+            .battle_state
+            .get_side(is_trainer_1)
+            .get_valid_actions(slot, &self.battle_state.mechanics);
+        let is_valid = match modifier {
+            MoveModifier::None => true,
+            MoveModifier::MegaEvolve => valid_actions.can_mega_evolve,
+            MoveModifier::ZMove => action
+                .get_move_index()
+                .is_some_and(|idx| valid_actions.z_moves.contains(&idx)),
+            MoveModifier::Dynamax => valid_actions.can_dynamax,
+            MoveModifier::Terastallize => valid_actions.can_terastallize,
+        };
+        if !is_valid {
+            panic!(
+                "Illegal input: {:?} is not available for this pokemon",
+                modifier
+            );
+        }
+    }
+
+    // Decided by pokemon remaining, then by the fraction of team hp remaining
+    fn get_turn_limit_winner(&self) -> Winner {
+        let side1 = self.battle_state.get_side(true);
+        let side2 = self.battle_state.get_side(false);
+        let remaining = (side1.num_usable_pokemon(), side2.num_usable_pokemon());
+        let hp_fraction = (side1.remaining_hp_fraction(), side2.remaining_hp_fraction());
+
+        if remaining.0 != remaining.1 {
+            if remaining.0 > remaining.1 {
+                Winner::Trainer1
+            } else {
+                Winner::Trainer2
+            }
+        } else if hp_fraction.0 > hp_fraction.1 {
+            Winner::Trainer1
+        } else if hp_fraction.1 > hp_fraction.0 {
+            Winner::Trainer2
+        } else {
+            Winner::Draw
+        }
     }
 
     fn process_action(
         &mut self,
         is_trainer_1: bool,
+        slot: usize,
         action: &Action,
         turn_state: &mut TurnState,
     ) -> ActionResponse {
+        self.battle_state
+            .get_active_pokemon_mut(is_trainer_1, slot)
+            .queued_move = None;
+
         if action.is_switch() {
-            BattleEngine::switch_pokemon(
-                &mut self.battle_context(),
+            let switch_idx = action.get_switch_index();
+            // an ally may already have switched this pokemon in
+            if !self
+                .battle_state
+                .get_side(is_trainer_1)
+                .is_active(switch_idx)
+            {
+                BattleEngine::switch_pokemon(
+                    &mut self.battle_context(),
+                    is_trainer_1,
+                    slot,
+                    switch_idx,
+                );
+            }
+        } else if let Some(move_name) =
+            self.battle_state
+                .get_move_for_action(is_trainer_1, slot, action)
+        {
+            let move_context = Battle::create_move_context(
+                &self.battle_state,
+                move_name,
                 is_trainer_1,
-                action.get_switch_index(),
+                slot,
+                action.get_target_loc(),
+                action.get_move_modifier(),
             );
-        } else {
-            let move_name = if *action == Action::Struggle {
-                MoveName::Struggle
-            } else {
-                self.battle_state
-                    .get_move_for_move_action(is_trainer_1, action)
-            };
-            let move_context = Battle::create_move_context(move_name, is_trainer_1);
 
+            if action.get_move_modifier() == MoveModifier::ZMove {
+                let side = self.battle_state.get_side_mut(is_trainer_1);
+                side.z_move_used = true;
+                side.get_active_pokemon_mut(slot).item_revealed = true;
+            }
             BattleEngine::try_use_move(&mut self.battle_context(), &move_context, turn_state);
+        } else {
+            // only a recharging pokemon has no move to use for a non-switch action
+            BattleEngine::recharge(&mut self.battle_context(), is_trainer_1, slot);
         }
 
         self.event_bus
@@ -151,31 +761,69 @@ impl Battle {
         ActionResponse::Continue
     }
 
-    // true is action1 goes first, false is action2 goes first
-    fn resolve_action_order(&mut self, action1: &Action, action2: &Action) -> bool {
+    // Records the moves each pokemon is about to use, and clears last turn's damage
+    fn queue_moves(&mut self, actions: &[(bool, usize, Action)]) {
+        for (is_trainer_1, slot, action) in actions {
+            let queued_move = self
+                .battle_state
+                .get_move_for_action(*is_trainer_1, *slot, action);
+            let pokemon_battle_instance = self
+                .battle_state
+                .get_active_pokemon_mut(*is_trainer_1, *slot);
+            pokemon_battle_instance.queued_move = queued_move;
+            pokemon_battle_instance.damage_taken = None;
+        }
+    }
+
+    // Sorts actions by priority, then by speed, with speed ties broken randomly
+    fn resolve_action_order(
+        &mut self,
+        actions: Vec<(bool, usize, Action)>,
+    ) -> Vec<(bool, usize, Action)> {
         let mut battle_context = self.battle_context();
-        let priority1 = Self::get_action_priority(&mut battle_context, true, action1);
-        let priority2 = Self::get_action_priority(&mut battle_context, false, action2);
+        let mut keyed_actions: Vec<_> = actions
+            .into_iter()
+            .map(|(trainer, slot, action)| {
+                let priority =
+                    Self::get_action_priority(&mut battle_context, trainer, slot, &action);
+                let speed = BattleEngine::get_effective_stat_value(
+                    &mut battle_context,
+                    trainer,
+                    slot,
+                    StatEnum::Speed,
+                );
+                ((priority, speed), (trainer, slot, action))
+            })
+            .collect();
+        keyed_actions.sort_by(|(key1, _), (key2, _)| key2.cmp(key1));
 
-        if priority1 > priority2 {
-            true
-        } else if priority2 > priority1 {
-            false
-        } else {
-            BattleEngine::resolve_speed_order(&mut battle_context)
+        // each run of tied actions is shuffled with small rolls, so searches can list
+        //  every order
+        for tied_actions in keyed_actions.chunk_by_mut(|(key1, _), (key2, _)| key1 == key2) {
+            for idx in (1..tied_actions.len()).rev() {
+                let other = battle_context.battle_state.get_rand_num(idx as u32 + 1);
+                tied_actions.swap(idx, other as usize);
+            }
         }
+
+        keyed_actions
+            .into_iter()
+            .map(|(_, action)| action)
+            .collect()
     }
 
     fn get_action_priority(
         battle_context: &mut BattleContext,
         is_trainer_1: bool,
+        slot: usize,
         action: &Action,
     ) -> i8 {
-        let move1_option = battle_context
-            .battle_state
-            .get_move_for_action(is_trainer_1, action);
+        let move1_option =
+            battle_context
+                .battle_state
+                .get_move_for_action(is_trainer_1, slot, action);
 
-        Self::get_move_priority(battle_context, move1_option, action, is_trainer_1)
+        Self::get_move_priority(battle_context, move1_option, action, is_trainer_1, slot)
     }
 
     fn get_move_priority(
@@ -183,105 +831,152 @@ impl Battle {
         optional_move: Option<MoveName>,
         action: &Action,
         is_trainer_1: bool,
+        slot: usize,
     ) -> i8 {
         if action.is_switch() {
             6
-        } else {
-            let move_name = optional_move.expect("Expected a move for non-switch action");
-            let context = Battle::create_move_context(move_name, is_trainer_1);
+        } else if let Some(move_name) = optional_move {
+            let context = Battle::create_move_context(
+                battle_context.battle_state,
+                move_name,
+                is_trainer_1,
+                slot,
+                action.get_target_loc(),
+                action.get_move_modifier(),
+            );
             let mut priority_query = Query::OnPriority(PayloadMoveQuery::i8(context));
             battle_context
                 .query_bus
                 .query(&mut priority_query, battle_context.battle_state);
             priority_query.into_payload_move_query().get_i8()
+        } else {
+            // recharging
+            0
         }
     }
 
-    fn create_move_context(move_name: MoveName, is_trainer_1: bool) -> MoveContext {
+    // Spread moves start with a single target here, the engine resolves the rest
+    fn create_move_context(
+        battle_state: &BattleState,
+        move_name: MoveName,
+        is_trainer_1: bool,
+        slot: usize,
+        target_loc: Option<TargetLoc>,
+        move_modifier: MoveModifier,
+    ) -> MoveContext {
+        let selected_move = move_dex::get_move_data(&move_name);
+        let is_dynamaxed = move_modifier == MoveModifier::Dynamax
+            || battle_state
+                .get_active_pokemon(is_trainer_1, slot)
+                .is_dynamaxed();
+
+        // Z-Moves and Max Moves replace the chosen move but remember it
+        let (move_name, base_move) = match move_modifier {
+            MoveModifier::ZMove => (
+                gimmick_moves::get_z_move(selected_move.move_type),
+                Some(move_name),
+            ),
+            _ if is_dynamaxed && move_name != MoveName::Struggle => {
+                (gimmick_moves::get_max_move(selected_move), Some(move_name))
+            }
+            _ => (move_name, None),
+        };
         let pokemove = move_dex::get_move_data(&move_name);
+        let num_slots = battle_state.get_side(is_trainer_1).num_active_slots();
+
+        let (target_trainer, target_slot) = match (pokemove.target, target_loc) {
+            (MoveTarget::User, _) => (is_trainer_1, slot),
+            (MoveTarget::UserSide | MoveTarget::Field, _) => (is_trainer_1, 0),
+            (MoveTarget::OpponentSide, _) => (!is_trainer_1, 0),
+            (_, Some(TargetLoc::Ally(ally_slot))) => (is_trainer_1, ally_slot),
+            (_, Some(TargetLoc::Opponent(foe_slot))) => (!is_trainer_1, foe_slot),
+            (MoveTarget::Ally, None) => (is_trainer_1, (slot + 1) % num_slots),
+            // default to the opponent facing the user
+            (_, None) => (!is_trainer_1, slot.min(num_slots - 1)),
+        };
+
         MoveContext {
             src_trainer: is_trainer_1,
-            target_trainer: match pokemove.target {
-                MoveTarget::Opponent => !is_trainer_1,
-                MoveTarget::User => is_trainer_1,
-            },
+            src_slot: slot,
+            target_trainer,
+            target_slot,
             move_name,
             pokemove,
+            is_spread: false,
+            base_move,
         }
     }
 
     fn generate_battle_request_from_turn_state(&self, turn_state: &TurnState) -> BattleRequest {
-        match turn_state.fainted_sides.as_slice() {
-            [] => BattleRequest::Request(
-                Some(SingleBattleRequest::ActionRequest(
-                    self.battle_state.get_side(true).get_valid_actions(),
-                )),
-                Some(SingleBattleRequest::ActionRequest(
-                    self.battle_state.get_side(false).get_valid_actions(),
-                )),
-            ),
-            [true] => {
-                if self.battle_state.get_side(true).out_of_usable_pokemon() {
-                    BattleRequest::BattleEnded(Winner::Trainer2)
-                } else {
-                    BattleRequest::Request(
-                        Some(SingleBattleRequest::SwitchInRequest(
-                            self.battle_state
-                                .get_side(true)
-                                .get_valid_switches()
-                                .expect("Switch In request should have valid switches"),
-                        )),
-                        None,
-                    )
-                }
+        let side1_lost = self.battle_state.get_side(true).out_of_usable_pokemon();
+        let side2_lost = self.battle_state.get_side(false).out_of_usable_pokemon();
+        match (side1_lost, side2_lost) {
+            (true, true) => return BattleRequest::BattleEnded(Winner::Draw),
+            (true, false) => return BattleRequest::BattleEnded(Winner::Trainer2),
+            (false, true) => return BattleRequest::BattleEnded(Winner::Trainer1),
+            (false, false) => {}
+        }
+
+        if !turn_state.fainted_sides.is_empty() {
+            let switch_requests = (
+                self.generate_switch_in_requests(true),
+                self.generate_switch_in_requests(false),
+            );
+            if switch_requests
+                .0
+                .iter()
+                .chain(switch_requests.1.iter())
+                .any(Option::is_some)
+            {
+                return BattleRequest::Request(switch_requests.0, switch_requests.1);
             }
-            [false] => {
-                if self.battle_state.get_side(false).out_of_usable_pokemon() {
-                    BattleRequest::BattleEnded(Winner::Trainer1)
+        }
+
+        BattleRequest::Request(
+            self.generate_action_requests(true),
+            self.generate_action_requests(false),
+        )
+    }
+
+    // Asks for a replacement for each fainted slot, as long as there is a pokemon to send out
+    fn generate_switch_in_requests(&self, trainer: bool) -> SideRequest {
+        let side = self.battle_state.get_side(trainer);
+        let Some(valid_switches) = side.get_valid_switches() else {
+            return (0..side.num_active_slots()).map(|_| None).collect();
+        };
+
+        let mut remaining_switches = valid_switches.switches.len();
+        (0..side.num_active_slots())
+            .map(|slot| {
+                if side.get_active_pokemon(slot).is_fainted() && remaining_switches > 0 {
+                    remaining_switches -= 1;
+                    Some(SingleBattleRequest::SwitchInRequest(ValidSwitches {
+                        switches: valid_switches.switches.clone(),
+                    }))
                 } else {
-                    BattleRequest::Request(
-                        None,
-                        Some(SingleBattleRequest::SwitchInRequest(
-                            self.battle_state
-                                .get_side(false)
-                                .get_valid_switches()
-                                .expect("Switch In request should have valid switches"),
-                        )),
-                    )
+                    None
                 }
-            }
-            [true, false] | [false, true] => {
-                let side1_lost = self.battle_state.get_side(true).out_of_usable_pokemon();
-                let side2_lost = self.battle_state.get_side(false).out_of_usable_pokemon();
-
-                if side1_lost && side2_lost {
-                    BattleRequest::BattleEnded(Winner::Draw)
-                } else if side1_lost {
-                    BattleRequest::BattleEnded(Winner::Trainer2)
-                } else if side2_lost {
-                    BattleRequest::BattleEnded(Winner::Trainer1)
+            })
+            .collect()
+    }
+
+    fn generate_action_requests(&self, trainer: bool) -> SideRequest {
+        let side = self.battle_state.get_side(trainer);
+        (0..side.num_active_slots())
+            .map(|slot| {
+                if side.get_active_pokemon(slot).is_fainted() {
+                    None
                 } else {
-                    BattleRequest::Request(
-                        Some(SingleBattleRequest::SwitchInRequest(
-                            self.battle_state
-                                .get_side(true)
-                                .get_valid_switches()
-                                .expect("Switch In request should have valid switches"),
-                        )),
-                        Some(SingleBattleRequest::SwitchInRequest(
-                            self.battle_state
-                                .get_side(false)
-                                .get_valid_switches()
-                                .expect("Switch In request should have valid switches"),
-                        )),
-                    )
+                    Some(SingleBattleRequest::ActionRequest(
+                        side.get_valid_actions(slot, &self.battle_state.mechanics),
+                    ))
                 }
-            }
-            _ => panic!("Unexpected turn state fainted sides"),
-        }
+            })
+            .collect()
     }
 
-    fn battle_context(&mut self) -> BattleContext {
+    // Direct access to the engine, e.g. to set up a position before calcing on it
+    pub fn battle_context(&mut self) -> BattleContext<'_> {
         BattleContext {
             battle_state: &mut self.battle_state,
             query_bus: &mut self.query_bus,
@@ -290,3 +985,221 @@ impl Battle {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        battle::{
+            actions::{MoveModifier, MoveSlot},
+            battle_request::Winner,
+            test_utils,
+        },
+        core::pokemon::{boostable_stat::BoostableStat, species::Species},
+        showdown::text,
+    };
+
+    const TEAM: &str = "Pikachu\nAbility: Technician\n- Tackle\n\n\
+        Snorlax\nAbility: Thick Fat\n- Tackle\n\n\
+        Gengar\nAbility: Technician\n- Tackle";
+
+    fn get_team_order_input(order_1: Vec<usize>, order_2: Vec<usize>) -> BattleInput {
+        (
+            vec![Some(SingleInput::TeamOrderInput(order_1))],
+            vec![Some(SingleInput::TeamOrderInput(order_2))],
+        )
+    }
+
+    fn get_preview_battle() -> Battle {
+        let team = text::parse_team(TEAM).unwrap();
+        Battle::new(team.clone(), team)
+    }
+
+    #[test]
+    fn team_preview_rejects_sizes_outside_the_party() {
+        let mut battle = get_preview_battle();
+        let errors = battle
+            .start_team_preview(4)
+            .err()
+            .expect("Team preview should fail");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].kind,
+            TeamPreviewErrorKind::InvalidTeamSize {
+                team_size: 4,
+                party_size: 3
+            }
+        );
+        assert!(battle.start_team_preview(0).is_err());
+    }
+
+    #[test]
+    fn team_preview_rejects_bad_orders_without_changing_the_battle() {
+        let mut battle = get_preview_battle();
+        battle.start_team_preview(2).unwrap();
+
+        let errors = battle
+            .process_team_preview(get_team_order_input(vec![1, 1], vec![0, 5]))
+            .err()
+            .expect("Team preview should fail");
+        assert_eq!(
+            errors,
+            vec![
+                TeamPreviewError {
+                    trainer_1: true,
+                    kind: TeamPreviewErrorKind::DuplicateIndex { idx: 1 },
+                },
+                TeamPreviewError {
+                    trainer_1: false,
+                    kind: TeamPreviewErrorKind::IndexOutOfRange {
+                        idx: 5,
+                        party_size: 3
+                    },
+                },
+            ]
+        );
+
+        let errors = battle
+            .process_team_preview(get_team_order_input(vec![0], vec![0, 1]))
+            .err()
+            .expect("Team preview should fail");
+        assert_eq!(
+            errors[0].kind,
+            TeamPreviewErrorKind::WrongOrderLength {
+                len: 1,
+                team_size: 2
+            }
+        );
+        assert!(matches!(
+            battle.get_request(),
+            BattleRequest::TeamPreview(_, _)
+        ));
+    }
+
+    #[test]
+    fn process_input_resends_team_preview_for_a_bad_order() {
+        let mut battle = get_preview_battle();
+        battle.start_team_preview(2).unwrap();
+
+        let request = battle.process_input(get_team_order_input(vec![0, 0], vec![0, 1]));
+        assert!(matches!(request, BattleRequest::TeamPreview(_, _)));
+        assert_eq!(battle.battle_state().get_side(true).num_pokemon(), 3);
+
+        let request = battle.process_input(get_team_order_input(vec![0, 1], vec![0, 1]));
+        assert!(matches!(request, BattleRequest::Request(_, _)));
+    }
+
+    #[test]
+    fn team_preview_brings_the_chosen_pokemon_leads_first() {
+        let mut battle = get_preview_battle();
+        battle.start_team_preview(2).unwrap();
+        let request = battle
+            .process_team_preview(get_team_order_input(vec![2, 0], vec![1, 2]))
+            .unwrap();
+        assert!(matches!(request, BattleRequest::Request(_, _)));
+
+        let get_species = |trainer: bool| {
+            let side = battle.battle_state().get_side(trainer);
+            (0..side.num_pokemon())
+                .map(|idx| side.get_pokemon(idx).pokemon.species)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(get_species(true), vec![Species::Gengar, Species::Pikachu]);
+        assert_eq!(get_species(false), vec![Species::Snorlax, Species::Gengar]);
+    }
+
+    fn get_move_input() -> BattleInput {
+        let get_side_input = || {
+            vec![Some(SingleInput::StandardInput(Action::Move(
+                MoveSlot::Slot0,
+                MoveModifier::None,
+            )))]
+        };
+        (get_side_input(), get_side_input())
+    }
+
+    #[test]
+    fn outcomes_enumerate_speed_ties() {
+        let mut battle = test_utils::get_battle(
+            "Snorlax\nAbility: Thick Fat\n- Earthquake",
+            "Snorlax\nAbility: Thick Fat\n- Earthquake",
+            0,
+        );
+        for trainer in [true, false] {
+            battle
+                .battle_context()
+                .battle_state
+                .get_active_pokemon_mut(trainer, 0)
+                .pokemon
+                .hp = 1;
+        }
+
+        let outcomes = battle.get_outcomes(&get_move_input());
+        assert_eq!(outcomes.len(), 2);
+        let mut winners = vec![];
+        for (probability, _, request) in outcomes {
+            assert!((probability - 0.5).abs() < 1e-9);
+            let BattleRequest::BattleEnded(winner) = request else {
+                panic!("The faster pokemon should win");
+            };
+            winners.push(winner);
+        }
+        assert!(winners.contains(&Winner::Trainer1) && winners.contains(&Winner::Trainer2));
+    }
+
+    #[test]
+    fn outcomes_that_only_differ_in_boosts_are_kept_apart() {
+        let mut battle = test_utils::get_battle(
+            "Snorlax\nAbility: Thick Fat\n- Growl",
+            "Snorlax\nAbility: Thick Fat\n- Swords Dance",
+            0,
+        );
+        battle
+            .battle_context()
+            .battle_state
+            .get_active_pokemon_mut(true, 0)
+            .modify_boost(BoostableStat::Accuracy, -1);
+
+        let outcomes = battle.get_outcomes(&get_move_input());
+        assert_eq!(outcomes.len(), 2);
+        let total: f64 = outcomes.iter().map(|(probability, _, _)| probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        let mut attack_boosts: Vec<i8> = outcomes
+            .iter()
+            .map(|(_, battle, _)| {
+                battle.battle_state().get_active_pokemon(false, 0).boosts
+                    [BoostableStat::Stat(StatEnum::Attack)]
+            })
+            .collect();
+        attack_boosts.sort();
+        assert_eq!(attack_boosts, vec![1, 2]);
+    }
+
+    #[test]
+    fn outcome_probabilities_sum_to_one() {
+        let battle = test_utils::get_battle(
+            "Garchomp\nAbility: Sand Force\n- Tackle",
+            "Snorlax\nAbility: Thick Fat\n- Earthquake",
+            0,
+        );
+        let outcomes = battle.get_outcomes(&get_move_input());
+        assert!(outcomes.len() > 1);
+        let total: f64 = outcomes.iter().map(|(probability, _, _)| probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn outcomes_of_multi_hit_moves_stay_bounded() {
+        let battle = test_utils::get_battle(
+            "Venusaur\nAbility: Overgrow\n- Bullet Seed",
+            "Snorlax\nAbility: Thick Fat\n- Bullet Seed",
+            0,
+        );
+        let outcomes = battle.get_outcomes(&get_move_input());
+        assert!(outcomes.len() > 1);
+        assert!(outcomes.len() <= MAX_LISTED_RUNS);
+        let total: f64 = outcomes.iter().map(|(probability, _, _)| probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+}
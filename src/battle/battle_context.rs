@@ -2,7 +2,7 @@ use crate::{
     battle::state::BattleState,
     common::registry::Registry,
     event::{event_handler::EventHandler, event_queue::EventQueue, event_type::Event},
-    query::{query::Query, query_bus::QueryBus, query_handler::QueryHandler},
+    query::query_bus::QueryBus,
 };
 
 pub struct BattleContext<'a> {
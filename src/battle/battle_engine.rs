@@ -3,57 +3,177 @@ use std::{cmp, sync::Arc};
 use crate::{
     battle::{
         battle_context::BattleContext,
-        pokemon_battle_instance::{self, PokemonBattleInstance},
+        damage_calculator::{DamageRolls, NUM_DAMAGE_ROLLS},
+        move_lock::MoveLock,
+        pokemon_battle_instance::PokemonBattleInstance,
+        scripted_rolls::ScriptedRolls,
         state::BattleState,
         turn_state::TurnState,
     },
-    common::{context::MoveContext, registry::Registry},
+    common::{context::MoveContext, has_kind::HasKind, registry::Registry},
     core::{
-        pokemon::{self, boostable_stat::BoostableStat, stat_enum::StatEnum},
+        field::weather::Weather,
+        pokemon::{boostable_stat::BoostableStat, stat_enum::StatEnum},
         pokemove::{
-            move_category::MoveCategory, move_name::MoveName, secondary_effect::SecondaryEffect,
+            fixed_damage::FixedDamage, move_category::MoveCategory, move_heal::MoveHeal,
+            move_name::MoveName, move_target::MoveTarget, multi_turn::MultiTurn,
+            secondary_effect::SecondaryEffect,
         },
-        poketype::{effectiveness, pokemon_typing::PokemonTyping, poketype::PokeType},
+        poketype::{effectiveness, pokemon_typing::PokemonTyping},
+        side_condition::side_condition::SideCondition,
         status::{status::Status, volatile_status::VolatileStatus},
-        util::damage_utils,
+        util::{
+            damage_utils::{self, DamageParams},
+            stat_utils,
+        },
+    },
+    dex::{
+        combined_handler::CombinedHandler,
+        pokemon::{mega_evolutions, pokedex},
+        pokemove::move_handlers,
     },
-    dex::{combined_handler::CombinedHandler, pokemove::move_dex},
     event::{
-        self,
         event_handler::EventHandler,
-        event_queue::{self, EventQueue},
-        event_type::{Event, FaintEvent},
+        event_handler_effect::EventHandlerEffect,
+        event_type::{Event, FaintEvent, MoveHitEvent, SwitchEvent},
     },
     query::{
         payload::PayloadMoveQuery,
         query::{
             CanApplyStatusQuery, CanApplyVolatileStatusQuery, FinalDamageQuery, MultiHitHitsQuery,
-            MultiHitRangeQuery, OnStatQuery, Query, TryUseMoveQuery,
+            MultiHitRangeQuery, OnStatQuery, Query, RedirectTargetQuery, TryUseMoveQuery,
         },
-        query_bus::QueryBus,
         query_handler::QueryHandler,
     },
 };
 
+// the counter wakes the pokemon when it reaches 1, leaving two turns asleep
+const REST_SLEEP_TURNS: u8 = 3;
+
 pub struct BattleEngine;
 
 impl BattleEngine {
-    pub fn switch_pokemon(battle_context: &mut BattleContext, trainer: bool, switch_idx: usize) {
-        BattleEngine::switch_out_pokemon(battle_context, trainer, switch_idx);
-        BattleEngine::switch_in_pokemon(battle_context, trainer, switch_idx);
+    pub fn switch_pokemon(
+        battle_context: &mut BattleContext,
+        trainer: bool,
+        slot: usize,
+        switch_idx: usize,
+    ) {
+        BattleEngine::switch_out_pokemon(battle_context, trainer, slot);
+        BattleEngine::switch_in_pokemon(battle_context, trainer, slot, switch_idx);
     }
 
-    pub fn switch_in_pokemon(battle_context: &mut BattleContext, trainer: bool, switch_idx: usize) {
+    pub fn switch_in_pokemon(
+        battle_context: &mut BattleContext,
+        trainer: bool,
+        slot: usize,
+        switch_idx: usize,
+    ) {
+        let orig_pokemon_idx = battle_context
+            .battle_state
+            .get_side(trainer)
+            .get_active_pokemon_idx(slot);
         let pokemon_battle_instance = battle_context
             .battle_state
             .get_side_mut(trainer)
-            .set_active_pokemon(switch_idx);
+            .set_active_pokemon(slot, switch_idx);
 
         BattleEngine::register_handlers_for_pokemon(
-            &mut battle_context.event_registry,
+            battle_context.event_registry,
             &mut battle_context.query_bus.registry,
             pokemon_battle_instance,
         );
+
+        battle_context
+            .event_queue
+            .add_event(Event::Switch(SwitchEvent {
+                trainer_side: trainer,
+                slot,
+                orig_pokemon_idx,
+                new_pokemon_idx: switch_idx,
+            }));
+    }
+
+    // Only the first request each battle goes through, e.g. when both slots ask in doubles
+    pub fn terastallize(battle_context: &mut BattleContext, trainer: bool, slot: usize) {
+        let side = battle_context.battle_state.get_side_mut(trainer);
+        if side.tera_used {
+            return;
+        }
+
+        side.tera_used = true;
+        side.get_active_pokemon_mut(slot).terastallized = true;
+    }
+
+    pub fn mega_evolve(battle_context: &mut BattleContext, trainer: bool, slot: usize) {
+        if battle_context.battle_state.get_side(trainer).mega_used {
+            return;
+        }
+
+        let pokemon = &battle_context
+            .battle_state
+            .get_active_pokemon(trainer, slot)
+            .pokemon;
+        let mega_evolution = pokemon
+            .item
+            .and_then(|item| mega_evolutions::get_mega_evolution(&pokemon.species, &item))
+            .expect("Mega evolution without a matching mega stone");
+
+        // the new ability replaces the old one's handler
+        let old_handler = battle_context
+            .battle_state
+            .get_active_pokemon(trainer, slot)
+            .ability_handler
+            .clone();
+        BattleEngine::unregister_handler(
+            &old_handler,
+            battle_context.event_registry,
+            &mut battle_context.query_bus.registry,
+        );
+
+        let side = battle_context.battle_state.get_side_mut(trainer);
+        side.mega_used = true;
+        let pokemon_battle_instance = side.get_active_pokemon_mut(slot);
+        pokemon_battle_instance.pokemon.mega_evolve(
+            pokedex::get_base_pokemon(&mega_evolution.mega_species),
+            mega_evolution.ability,
+        );
+        pokemon_battle_instance.set_ability(mega_evolution.ability);
+        pokemon_battle_instance.item_revealed = true;
+        pokemon_battle_instance.ability_revealed = true;
+
+        let new_handler = pokemon_battle_instance.ability_handler.clone();
+        BattleEngine::register_handler(
+            &new_handler,
+            battle_context.event_registry,
+            &mut battle_context.query_bus.registry,
+        );
+    }
+
+    pub fn dynamax(battle_context: &mut BattleContext, trainer: bool, slot: usize) {
+        let side = battle_context.battle_state.get_side_mut(trainer);
+        if side.dynamax_used {
+            return;
+        }
+
+        side.dynamax_used = true;
+        side.get_active_pokemon_mut(slot).start_dynamax();
+    }
+
+    fn tick_dynamax(battle_context: &mut BattleContext) {
+        for (trainer, slot) in battle_context.battle_state.get_active_positions() {
+            let pokemon_battle_instance = battle_context
+                .battle_state
+                .get_active_pokemon_mut(trainer, slot);
+            if !pokemon_battle_instance.is_dynamaxed() {
+                continue;
+            }
+
+            pokemon_battle_instance.dynamax_turns -= 1;
+            if pokemon_battle_instance.dynamax_turns == 0 {
+                pokemon_battle_instance.end_dynamax();
+            }
+        }
     }
 
     pub fn try_use_move(
@@ -61,16 +181,115 @@ impl BattleEngine {
         move_context: &MoveContext,
         turn_state: &mut TurnState,
     ) {
-        let can_execute = BattleEngine::check_move_execution(battle_context, move_context);
+        // a move's own handler is only registered for the duration of its execution
+        let move_handler = move_handlers::get_move_handler(
+            &move_context.move_name,
+            move_context.src_trainer,
+            move_context.src_slot,
+        );
+        if let Some(handler) = &move_handler {
+            BattleEngine::register_handler(
+                handler,
+                battle_context.event_registry,
+                &mut battle_context.query_bus.registry,
+            );
+        }
+
+        Self::execute_move(battle_context, move_context, turn_state);
+
+        if let Some(handler) = &move_handler {
+            BattleEngine::unregister_handler(
+                handler,
+                battle_context.event_registry,
+                &mut battle_context.query_bus.registry,
+            );
+        }
+    }
+
+    fn execute_move(
+        battle_context: &mut BattleContext,
+        move_context: &MoveContext,
+        turn_state: &mut TurnState,
+    ) {
+        let src_trainer = move_context.src_trainer;
+        let src_slot = move_context.src_slot;
+        let move_lock = battle_context
+            .battle_state
+            .get_active_pokemon(src_trainer, src_slot)
+            .move_lock;
+        let continuing_lock =
+            move_lock.is_some_and(|lock| lock.locked_move() == Some(move_context.move_name));
+
+        let can_execute =
+            BattleEngine::check_move_execution(battle_context, move_context, turn_state);
 
         if !can_execute {
+            Self::set_move_lock(battle_context, src_trainer, src_slot, None);
             return;
         }
 
-        if !BattleEngine::check_move_hit(battle_context, move_context) {
+        // locked moves only deduct pp on their first turn
+        if !continuing_lock {
+            Self::deduct_pp(battle_context, move_context);
+        }
+        Self::record_move_used(battle_context, move_context);
+
+        if move_context.pokemove.multi_turn == Some(MultiTurn::Charge) {
+            if !continuing_lock {
+                Self::set_move_lock(
+                    battle_context,
+                    src_trainer,
+                    src_slot,
+                    Some(MoveLock::Charging(move_context.move_name)),
+                );
+                return;
+            }
+            Self::set_move_lock(battle_context, src_trainer, src_slot, None);
+        }
+
+        let target_contexts = Self::resolve_targets(battle_context, move_context);
+        let mut hit_any_target = false;
+        for target_context in &target_contexts {
+            if Self::check_move_target(battle_context, target_context)
+                && Self::check_move_hit(battle_context, target_context)
+            {
+                Self::execute_move_on_target(battle_context, target_context, turn_state);
+                hit_any_target = true;
+            }
+
+            if battle_context
+                .battle_state
+                .get_active_pokemon(src_trainer, src_slot)
+                .is_fainted()
+            {
+                break;
+            }
+        }
+
+        if !hit_any_target {
+            // a rampage disrupted on its last turn still confuses the user
+            if continuing_lock && move_lock == Some(MoveLock::Rampage(move_context.move_name, 1)) {
+                Self::end_rampage(battle_context, move_context);
+            }
+            Self::set_move_lock(battle_context, src_trainer, src_slot, None);
             return;
         }
 
+        Self::apply_self_boosts(battle_context, move_context);
+        // damaging moves that set weather, e.g. Max Moves, do so after hitting
+        if let Some(weather) = move_context.pokemove.weather
+            && move_context.pokemove.category != MoveCategory::Status
+        {
+            Self::set_weather(battle_context, weather);
+        }
+        Self::update_move_lock(battle_context, move_context, move_lock);
+    }
+
+    fn execute_move_on_target(
+        battle_context: &mut BattleContext,
+        move_context: &MoveContext,
+        turn_state: &mut TurnState,
+    ) {
         let num_hits = if move_context.pokemove.is_multi_hit {
             BattleEngine::get_multi_hit_hits(battle_context, move_context)
         } else {
@@ -78,9 +297,16 @@ impl BattleEngine {
         };
 
         for _ in 0..num_hits {
-            match move_context.pokemove.category {
+            Self::publish_event(
+                battle_context,
+                &Event::BeforeMoveHit(*move_context),
+                turn_state,
+            );
+
+            let damage_dealt = match move_context.pokemove.category {
                 MoveCategory::Status => {
                     Self::apply_status_move(battle_context, move_context);
+                    0
                 }
                 MoveCategory::Physical | MoveCategory::Special => {
                     let damage_dealt = BattleEngine::single_hit_execution(
@@ -89,39 +315,466 @@ impl BattleEngine {
                         turn_state,
                     );
                     Self::apply_recoil(battle_context, move_context, damage_dealt, turn_state);
+                    Self::apply_drain(battle_context, move_context, damage_dealt);
                     Self::apply_secondary_effect(battle_context, move_context);
+                    damage_dealt
                 }
             };
 
+            let hit_event = Event::AfterMoveHit(MoveHitEvent {
+                move_context: *move_context,
+                damage_dealt,
+            });
+            Self::publish_event(battle_context, &hit_event, turn_state);
+
             // cancel if either pokemon faints
             if battle_context
                 .battle_state
-                .get_active_pokemon(move_context.target_trainer)
+                .get_active_pokemon(move_context.target_trainer, move_context.target_slot)
                 .is_fainted()
                 || battle_context
                     .battle_state
-                    .get_active_pokemon(move_context.src_trainer)
+                    .get_active_pokemon(move_context.src_trainer, move_context.src_slot)
                     .is_fainted()
             {
                 break;
             }
         }
+    }
 
-        if move_context.move_name != MoveName::Struggle {
-            Self::deduct_pp(battle_context, move_context);
+    // Expands a move into one context per pokemon it hits
+    fn resolve_targets(
+        battle_context: &mut BattleContext,
+        move_context: &MoveContext,
+    ) -> Vec<MoveContext> {
+        let src_trainer = move_context.src_trainer;
+        let src_slot = move_context.src_slot;
+        let is_alive = |battle_state: &BattleState, trainer: bool, slot: usize| {
+            !battle_state.get_active_pokemon(trainer, slot).is_fainted()
+        };
+
+        let targets: Vec<(bool, usize)> = match move_context.pokemove.target {
+            MoveTarget::User
+            | MoveTarget::UserSide
+            | MoveTarget::OpponentSide
+            | MoveTarget::Field => {
+                return vec![*move_context];
+            }
+            MoveTarget::Opponent | MoveTarget::Ally => {
+                let target_trainer = move_context.target_trainer;
+                let mut target_slot = move_context.target_slot;
+
+                if target_trainer != src_trainer {
+                    let mut redirect_query =
+                        Query::RedirectTarget(RedirectTargetQuery::new(*move_context));
+                    battle_context
+                        .query_bus
+                        .query(&mut redirect_query, battle_context.battle_state);
+                    target_slot = redirect_query.into_redirect_target_query().target_slot;
+                }
+
+                if matches!(move_context.pokemove.target, MoveTarget::Ally)
+                    && (target_trainer, target_slot) == (src_trainer, src_slot)
+                {
+                    // there is no ally to target in singles
+                    vec![]
+                } else if is_alive(battle_context.battle_state, target_trainer, target_slot) {
+                    vec![(target_trainer, target_slot)]
+                } else if target_trainer != src_trainer {
+                    // retarget to another opponent if the chosen one has fainted
+                    let num_slots = battle_context
+                        .battle_state
+                        .get_side(target_trainer)
+                        .num_active_slots();
+                    (0..num_slots)
+                        .filter(|slot| is_alive(battle_context.battle_state, target_trainer, *slot))
+                        .take(1)
+                        .map(|slot| (target_trainer, slot))
+                        .collect()
+                } else {
+                    vec![]
+                }
+            }
+            MoveTarget::AllAdjacent => battle_context
+                .battle_state
+                .get_active_positions()
+                .into_iter()
+                .filter(|position| *position != (src_trainer, src_slot))
+                .filter(|(trainer, slot)| is_alive(battle_context.battle_state, *trainer, *slot))
+                .collect(),
+            MoveTarget::AllAdjacentFoes => battle_context
+                .battle_state
+                .get_active_positions()
+                .into_iter()
+                .filter(|(trainer, slot)| {
+                    *trainer != src_trainer
+                        && is_alive(battle_context.battle_state, *trainer, *slot)
+                })
+                .collect(),
+        };
+
+        let is_spread = targets.len() > 1;
+        targets
+            .into_iter()
+            .map(|(target_trainer, target_slot)| MoveContext {
+                target_trainer,
+                target_slot,
+                is_spread,
+                ..*move_context
+            })
+            .collect()
+    }
+
+    pub fn recharge(battle_context: &mut BattleContext, trainer: bool, slot: usize) {
+        Self::set_move_lock(battle_context, trainer, slot, None);
+    }
+
+    fn record_move_used(battle_context: &mut BattleContext, move_context: &MoveContext) {
+        let pokemon_battle_instance = battle_context
+            .battle_state
+            .get_active_pokemon_mut(move_context.src_trainer, move_context.src_slot);
+
+        let selected_move = move_context.get_selected_move();
+        pokemon_battle_instance.last_move_used = Some(selected_move);
+        if let Some(idx) = pokemon_battle_instance
+            .pokemon
+            .moves
+            .iter()
+            .position(|move_name| *move_name == selected_move)
+        {
+            pokemon_battle_instance.revealed_moves[idx] = true;
+        }
+        if pokemon_battle_instance.has_choice_item()
+            && pokemon_battle_instance.choice_lock.is_none()
+            && selected_move != MoveName::Struggle
+        {
+            pokemon_battle_instance.choice_lock = Some(selected_move);
         }
     }
 
+    fn set_move_lock(
+        battle_context: &mut BattleContext,
+        trainer: bool,
+        slot: usize,
+        move_lock: Option<MoveLock>,
+    ) {
+        battle_context
+            .battle_state
+            .get_active_pokemon_mut(trainer, slot)
+            .move_lock = move_lock;
+    }
+
+    // Sets up the lock for the next turn after a multi-turn move has executed
+    fn update_move_lock(
+        battle_context: &mut BattleContext,
+        move_context: &MoveContext,
+        prev_move_lock: Option<MoveLock>,
+    ) {
+        let src_trainer = move_context.src_trainer;
+        let src_slot = move_context.src_slot;
+        if battle_context
+            .battle_state
+            .get_active_pokemon(src_trainer, src_slot)
+            .is_fainted()
+        {
+            return;
+        }
+
+        let move_name = move_context.move_name;
+        let next_move_lock = match move_context.pokemove.multi_turn {
+            Some(MultiTurn::Recharge) => Some(MoveLock::Recharging),
+            Some(MultiTurn::Rampage) => match prev_move_lock {
+                Some(MoveLock::Rampage(_, turns_left)) if turns_left > 1 => {
+                    Some(MoveLock::Rampage(move_name, turns_left - 1))
+                }
+                Some(MoveLock::Rampage(_, _)) => {
+                    Self::end_rampage(battle_context, move_context);
+                    None
+                }
+                _ => {
+                    let turns_left = battle_context.battle_state.get_rand_num_inclusive(1, 2);
+                    Some(MoveLock::Rampage(move_name, turns_left))
+                }
+            },
+            Some(MultiTurn::Rollout) => {
+                let hits = match prev_move_lock {
+                    Some(MoveLock::Rollout(_, hits)) => hits + 1,
+                    _ => 1,
+                };
+                if hits >= 5 {
+                    None
+                } else {
+                    Some(MoveLock::Rollout(move_name, hits))
+                }
+            }
+            Some(MultiTurn::Charge) | None => None,
+        };
+
+        Self::set_move_lock(battle_context, src_trainer, src_slot, next_move_lock);
+    }
+
+    // A rampage ends with the user confused from fatigue
+    fn end_rampage(battle_context: &mut BattleContext, move_context: &MoveContext) {
+        Self::set_volatile_status(
+            battle_context,
+            move_context,
+            move_context.src_trainer,
+            move_context.src_slot,
+            VolatileStatus::Confusion,
+        );
+    }
+
     // returns damage dealt
     fn single_hit_execution(
         battle_context: &mut BattleContext,
         move_context: &MoveContext,
         turn_state: &mut TurnState,
     ) -> u32 {
-        let damage = BattleEngine::calculate_damage(battle_context, move_context);
+        let damage = BattleEngine::calculate_move_damage(battle_context, move_context);
         BattleEngine::deal_damage(battle_context, Some(move_context), None, turn_state, damage)
     }
 
+    // Damage a hit would deal without dealing it, 0 for status moves and immune targets.
+    //  Still rolls the battle's rng, so callers should use a copy of the battle
+    pub fn estimate_move_damage(
+        battle_context: &mut BattleContext,
+        move_context: &MoveContext,
+    ) -> u32 {
+        let category = battle_context
+            .battle_state
+            .mechanics
+            .get_move_category(move_context.get_category_move());
+        if category == MoveCategory::Status {
+            return 0;
+        }
+
+        let move_handler = move_handlers::get_move_handler(
+            &move_context.move_name,
+            move_context.src_trainer,
+            move_context.src_slot,
+        );
+        if let Some(handler) = &move_handler {
+            BattleEngine::register_handler(
+                handler,
+                battle_context.event_registry,
+                &mut battle_context.query_bus.registry,
+            );
+        }
+
+        let damage = if Self::check_move_target(battle_context, move_context) {
+            Self::calculate_move_damage(battle_context, move_context)
+        } else {
+            0
+        };
+
+        if let Some(handler) = &move_handler {
+            BattleEngine::unregister_handler(
+                handler,
+                battle_context.event_registry,
+                &mut battle_context.query_bus.registry,
+            );
+        }
+        damage
+    }
+
+    // Damage a hit would deal for every crit and damage roll, None for status moves.
+    //  Leaves the battle's rng untouched, but handlers are registered on the way, so
+    //  callers should still use a copy of the battle
+    pub fn get_damage_rolls(
+        battle_context: &mut BattleContext,
+        move_context: &MoveContext,
+    ) -> Option<DamageRolls> {
+        let category = battle_context
+            .battle_state
+            .mechanics
+            .get_move_category(move_context.get_category_move());
+        if category == MoveCategory::Status {
+            return None;
+        }
+
+        let move_handler = move_handlers::get_move_handler(
+            &move_context.move_name,
+            move_context.src_trainer,
+            move_context.src_slot,
+        );
+        if let Some(handler) = &move_handler {
+            BattleEngine::register_handler(
+                handler,
+                battle_context.event_registry,
+                &mut battle_context.query_bus.registry,
+            );
+        }
+
+        let mut damage_rolls = DamageRolls {
+            rolls: [0; NUM_DAMAGE_ROLLS],
+            crit_rolls: [0; NUM_DAMAGE_ROLLS],
+            crit_chance: 0.0,
+            hit_chances: vec![(1, 1.0)],
+        };
+        if Self::check_move_target(battle_context, move_context) {
+            damage_rolls.crit_chance = Self::get_crit_chance(battle_context, move_context);
+            if move_context.pokemove.is_multi_hit {
+                damage_rolls.hit_chances =
+                    Self::get_multi_hit_chances(battle_context, move_context);
+            }
+            for (idx, r) in (85..=100).enumerate() {
+                for (is_crit, rolls) in [
+                    (false, &mut damage_rolls.rolls),
+                    (true, &mut damage_rolls.crit_rolls),
+                ] {
+                    let damage = match move_context.pokemove.fixed_damage {
+                        Some(fixed_damage) => BattleEngine::calculate_fixed_damage(
+                            battle_context,
+                            move_context,
+                            fixed_damage,
+                        ),
+                        None => Self::calculate_damage_with_roll(
+                            battle_context,
+                            move_context,
+                            is_crit,
+                            r,
+                        ),
+                    };
+                    rolls[idx] = Self::get_final_damage(battle_context, move_context, damage);
+                }
+            }
+        }
+
+        if let Some(handler) = &move_handler {
+            BattleEngine::unregister_handler(
+                handler,
+                battle_context.event_registry,
+                &mut battle_context.query_bus.registry,
+            );
+        }
+        Some(damage_rolls)
+    }
+
+    // Walks through every outcome of the crit roll with scripted rolls, so handlers that
+    //  change the odds or force a crit are counted
+    fn get_crit_chance(battle_context: &mut BattleContext, move_context: &MoveContext) -> f64 {
+        let prev_scripted_rolls = battle_context.battle_state.take_scripted_rolls();
+        let mut crit_chance = 0.0;
+        let mut scripts = vec![vec![]];
+        while let Some(script) = scripts.pop() {
+            battle_context
+                .battle_state
+                .set_scripted_rolls(Some(ScriptedRolls::new(script)));
+            let is_crit = Self::roll_crit(battle_context, move_context);
+            let scripted_rolls = battle_context
+                .battle_state
+                .take_scripted_rolls()
+                .expect("Scripted rolls removed during the crit roll");
+            scripts.extend(scripted_rolls.get_unexplored_scripts());
+            if is_crit {
+                crit_chance += scripted_rolls.probability();
+            }
+        }
+        battle_context
+            .battle_state
+            .set_scripted_rolls(prev_scripted_rolls);
+        crit_chance
+    }
+
+    // Walks through every outcome of the multi-hit roll the same way as the crit roll.
+    //  Returns each number of hits with its chance
+    fn get_multi_hit_chances(
+        battle_context: &mut BattleContext,
+        move_context: &MoveContext,
+    ) -> Vec<(u8, f64)> {
+        let prev_scripted_rolls = battle_context.battle_state.take_scripted_rolls();
+        let mut hit_chances: Vec<(u8, f64)> = vec![];
+        let mut scripts = vec![vec![]];
+        while let Some(script) = scripts.pop() {
+            battle_context
+                .battle_state
+                .set_scripted_rolls(Some(ScriptedRolls::new(script)));
+            let num_hits = Self::get_multi_hit_hits(battle_context, move_context);
+            let scripted_rolls = battle_context
+                .battle_state
+                .take_scripted_rolls()
+                .expect("Scripted rolls removed during the multi-hit roll");
+            scripts.extend(scripted_rolls.get_unexplored_scripts());
+            match hit_chances.iter_mut().find(|(hits, _)| *hits == num_hits) {
+                Some((_, chance)) => *chance += scripted_rolls.probability(),
+                None => hit_chances.push((num_hits, scripted_rolls.probability())),
+            }
+        }
+        battle_context
+            .battle_state
+            .set_scripted_rolls(prev_scripted_rolls);
+        hit_chances.sort_by_key(|(hits, _)| *hits);
+        hit_chances
+    }
+
+    // Damage after the modifiers applied as it is dealt, e.g. screens
+    fn get_final_damage(
+        battle_context: &mut BattleContext,
+        move_context: &MoveContext,
+        damage: u32,
+    ) -> u32 {
+        let mut final_damage_query =
+            Query::FinalDamage(FinalDamageQuery::from_move(damage, *move_context));
+        battle_context
+            .query_bus
+            .query(&mut final_damage_query, battle_context.battle_state);
+        final_damage_query.into_final_damage_query().damage
+    }
+
+    fn calculate_move_damage(
+        battle_context: &mut BattleContext,
+        move_context: &MoveContext,
+    ) -> u32 {
+        match move_context.pokemove.fixed_damage {
+            Some(fixed_damage) => {
+                BattleEngine::calculate_fixed_damage(battle_context, move_context, fixed_damage)
+            }
+            None => BattleEngine::calculate_damage(battle_context, move_context),
+        }
+    }
+
+    fn calculate_fixed_damage(
+        battle_context: &mut BattleContext,
+        move_context: &MoveContext,
+        fixed_damage: FixedDamage,
+    ) -> u32 {
+        let default_damage = match fixed_damage {
+            FixedDamage::Amount(amount) => amount,
+            FixedDamage::Level => {
+                battle_context
+                    .battle_state
+                    .get_active_pokemon(move_context.src_trainer, move_context.src_slot)
+                    .pokemon
+                    .level as u32
+            }
+            FixedDamage::TargetHpPercent(percent) => {
+                let target_hp = battle_context
+                    .battle_state
+                    .get_active_pokemon(move_context.target_trainer, move_context.target_slot)
+                    .pokemon
+                    .hp as u32;
+                cmp::max(target_hp * percent as u32 / 100, 1)
+            }
+            FixedDamage::Retaliation => 0,
+            FixedDamage::OneHitKo => {
+                battle_context
+                    .battle_state
+                    .get_active_pokemon(move_context.target_trainer, move_context.target_slot)
+                    .pokemon
+                    .hp as u32
+            }
+        };
+
+        let mut fixed_damage_query = Query::OnFixedDamage(PayloadMoveQuery::u32_with_default(
+            *move_context,
+            default_damage,
+        ));
+        battle_context
+            .query_bus
+            .query(&mut fixed_damage_query, battle_context.battle_state);
+        fixed_damage_query.into_payload_move_query().get_u32()
+    }
+
     fn deduct_pp(battle_context: &mut BattleContext, move_context: &MoveContext) {
         let mut deduct_pp_query =
             Query::GetDeductPP(PayloadMoveQuery::u8_with_default(*move_context, 1));
@@ -129,111 +782,335 @@ impl BattleEngine {
             .query_bus
             .query(&mut deduct_pp_query, battle_context.battle_state);
         let pp_to_deduct = deduct_pp_query.into_payload_move_query().get_u8();
+        if pp_to_deduct == 0 {
+            return;
+        }
 
         battle_context
             .battle_state
-            .get_active_pokemon_mut(move_context.src_trainer)
-            .deduct_pp(&move_context.move_name, pp_to_deduct);
+            .get_active_pokemon_mut(move_context.src_trainer, move_context.src_slot)
+            .deduct_pp(&move_context.get_selected_move(), pp_to_deduct);
     }
 
     pub fn deal_damage_and_heal(
         battle_context: &mut BattleContext,
-        target_damage_trainer: bool,
-        target_heal_trainer: bool,
+        damage_target: (bool, usize),
+        heal_target: (bool, usize),
         damage: u32,
         turn_state: &mut TurnState,
     ) {
         let damage_dealt = Self::deal_damage(
             battle_context,
             None,
-            Some(target_damage_trainer),
+            Some(damage_target),
             turn_state,
             damage,
         );
-        Self::heal(
-            battle_context,
-            target_heal_trainer,
-            damage_dealt,
-            turn_state,
-        );
+        let (heal_trainer, heal_slot) = heal_target;
+        Self::heal(battle_context, heal_trainer, heal_slot, damage_dealt);
     }
 
+    // returns amount healed
     pub fn heal(
         battle_context: &mut BattleContext,
         target_trainer: bool,
+        target_slot: usize,
         heal_amt: u32,
-        turn_state: &mut TurnState,
-    ) {
+    ) -> u32 {
         battle_context
             .battle_state
             .get_side_mut(target_trainer)
-            .heal(heal_amt);
+            .heal(target_slot, heal_amt)
     }
 
     // returns damage dealt
     pub fn deal_damage(
         battle_context: &mut BattleContext,
         move_context: Option<&MoveContext>,
-        target_trainer: Option<bool>,
+        target: Option<(bool, usize)>,
         turn_state: &mut TurnState,
         damage: u32,
     ) -> u32 {
-        if move_context.is_none() || target_trainer.is_none() {
-            panic!("Move context and target trainer must be provided to deal damage");
+        let (target_trainer, target_slot) = match (move_context, target) {
+            (Some(context), _) => (context.target_trainer, context.target_slot),
+            (None, Some(target)) => target,
+            (None, None) => {
+                panic!("Move context and target trainer must be provided to deal damage")
+            }
+        };
+
+        // residual effects can still fire for a pokemon that fainted without a replacement
+        if battle_context
+            .battle_state
+            .get_active_pokemon(target_trainer, target_slot)
+            .is_fainted()
+        {
+            return 0;
         }
 
-        let mut final_damage_query = if move_context.is_some() {
-            Query::FinalDamage(FinalDamageQuery::from_move(damage, *move_context.unwrap()))
-        } else {
-            Query::FinalDamage(FinalDamageQuery::from_non_move(
+        let mut final_damage_query = match move_context {
+            Some(context) => Query::FinalDamage(FinalDamageQuery::from_move(damage, *context)),
+            None => Query::FinalDamage(FinalDamageQuery::from_non_move(
                 damage,
-                target_trainer.unwrap(),
-            ))
+                target_trainer,
+                target_slot,
+            )),
         };
         battle_context
             .query_bus
             .query(&mut final_damage_query, battle_context.battle_state);
 
-        let target = if move_context.is_some() {
-            move_context.unwrap().target_trainer
-        } else {
-            target_trainer.unwrap()
-        };
-
         let final_damage = final_damage_query.into_final_damage_query().damage;
         let (caused_faint, damage_dealt) = battle_context
             .battle_state
-            .get_side_mut(target)
-            .take_damage(final_damage);
+            .get_side_mut(target_trainer)
+            .take_damage(target_slot, final_damage);
+
+        if let Some(context) = move_context {
+            battle_context
+                .battle_state
+                .get_active_pokemon_mut(target_trainer, target_slot)
+                .damage_taken = Some((damage_dealt, context.get_category_move().category));
+        }
 
         if caused_faint {
             let faint_event = Event::Faint(FaintEvent {
                 move_context: move_context.copied(),
-                trainer_side: target,
+                trainer_side: target_trainer,
+                slot: target_slot,
             });
             battle_context.event_queue.add_event(faint_event);
             battle_context
                 .battle_state
-                .get_active_pokemon_mut(target)
+                .get_active_pokemon_mut(target_trainer, target_slot)
                 .set_fainted();
-            turn_state.record_faint(target);
+            turn_state.record_faint(target_trainer, target_slot);
+
+            // a fainted pokemon may stay in its slot in doubles, so its effects end here
+            let pokemon_battle_instance = battle_context
+                .battle_state
+                .get_active_pokemon(target_trainer, target_slot);
+            BattleEngine::unregister_handlers_for_pokemon(
+                battle_context.event_registry,
+                &mut battle_context.query_bus.registry,
+                pokemon_battle_instance,
+            );
         }
 
         damage_dealt
     }
 
+    // Publishes an event immediately, rather than queueing it, so that handlers
+    // registered only for the current move still receive it
+    pub fn publish_event(
+        battle_context: &mut BattleContext,
+        event: &Event,
+        turn_state: &mut TurnState,
+    ) {
+        if !battle_context.event_registry.contains(&event.kind()) {
+            return;
+        }
+
+        // copy the handlers, since effects may unregister handlers for this event
+        let handlers = battle_context.event_registry.get(&event.kind()).clone();
+        for handler in handlers {
+            let effects = handler.handle(event, battle_context.battle_state);
+            for effect in effects {
+                Self::apply_event_handler_effect(battle_context, effect, turn_state);
+            }
+        }
+    }
+
+    pub fn apply_event_handler_effect(
+        battle_context: &mut BattleContext,
+        effect: EventHandlerEffect,
+        turn_state: &mut TurnState,
+    ) {
+        match effect {
+            EventHandlerEffect::Damage(damage, target_trainer, target_slot) => {
+                Self::deal_damage(
+                    battle_context,
+                    None,
+                    Some((target_trainer, target_slot)),
+                    turn_state,
+                    damage,
+                );
+            }
+            EventHandlerEffect::DamageAndHeal(
+                damage,
+                damage_trainer,
+                damage_slot,
+                heal_trainer,
+                heal_slot,
+            ) => {
+                Self::deal_damage_and_heal(
+                    battle_context,
+                    (damage_trainer, damage_slot),
+                    (heal_trainer, heal_slot),
+                    damage,
+                    turn_state,
+                );
+            }
+            EventHandlerEffect::RemoveVolatileStatus(
+                volatile_status,
+                target_trainer,
+                target_slot,
+            ) => {
+                Self::remove_volatile_status(
+                    battle_context,
+                    target_trainer,
+                    target_slot,
+                    volatile_status,
+                );
+            }
+            EventHandlerEffect::RemoveItem(target_trainer, target_slot) => {
+                Self::remove_item(battle_context, target_trainer, target_slot);
+            }
+            EventHandlerEffect::RemoveSideCondition(side_condition, target_trainer) => {
+                Self::remove_side_condition(battle_context, target_trainer, side_condition);
+            }
+        }
+    }
+
+    pub fn remove_item(battle_context: &mut BattleContext, trainer: bool, slot: usize) {
+        let pokemon_battle_instance = battle_context
+            .battle_state
+            .get_active_pokemon_mut(trainer, slot);
+        let item_handler = pokemon_battle_instance.item_handler.take();
+        pokemon_battle_instance.pokemon.item = None;
+        pokemon_battle_instance.item_revealed = true;
+        pokemon_battle_instance.choice_lock = None;
+
+        if let Some(handler) = &item_handler {
+            BattleEngine::unregister_handler(
+                handler,
+                battle_context.event_registry,
+                &mut battle_context.query_bus.registry,
+            );
+        }
+    }
+
+    // Used to calc from one trainer's point of view, see Battle::with_hidden_opponent_sets
+    pub fn hide_unrevealed_set(battle_context: &mut BattleContext, trainer: bool, idx: usize) {
+        let side = battle_context.battle_state.get_side_mut(trainer);
+        let is_registered = side.is_active(idx) && !side.get_pokemon(idx).is_fainted();
+        let pokemon_battle_instance = side.get_pokemon_mut(idx);
+        if is_registered {
+            BattleEngine::unregister_handlers_for_pokemon(
+                battle_context.event_registry,
+                &mut battle_context.query_bus.registry,
+                pokemon_battle_instance,
+            );
+        }
+
+        pokemon_battle_instance.hide_unrevealed_set();
+
+        if is_registered {
+            BattleEngine::register_handlers_for_pokemon(
+                battle_context.event_registry,
+                &mut battle_context.query_bus.registry,
+                pokemon_battle_instance,
+            );
+        }
+    }
+
+    pub fn set_side_condition(
+        battle_context: &mut BattleContext,
+        trainer: bool,
+        side_condition: SideCondition,
+    ) {
+        let side_condition_handler = battle_context
+            .battle_state
+            .get_side_mut(trainer)
+            .add_side_condition(side_condition);
+
+        if let Some(handler) = side_condition_handler {
+            BattleEngine::register_handler(
+                handler,
+                battle_context.event_registry,
+                &mut battle_context.query_bus.registry,
+            );
+        }
+    }
+
+    pub fn remove_side_condition(
+        battle_context: &mut BattleContext,
+        trainer: bool,
+        side_condition: SideCondition,
+    ) {
+        let side_condition_handler = battle_context
+            .battle_state
+            .get_side_mut(trainer)
+            .remove_side_condition(&side_condition);
+
+        if let Some(handler) = &side_condition_handler {
+            BattleEngine::unregister_handler(
+                handler,
+                battle_context.event_registry,
+                &mut battle_context.query_bus.registry,
+            );
+        }
+    }
+
+    fn set_weather(battle_context: &mut BattleContext, weather: Weather) {
+        if battle_context.battle_state.weather == Some(weather) {
+            return;
+        }
+
+        battle_context.battle_state.weather = Some(weather);
+        battle_context.battle_state.weather_turns = 5;
+    }
+
+    fn tick_weather(battle_context: &mut BattleContext) {
+        if battle_context.battle_state.weather.is_none() {
+            return;
+        }
+
+        battle_context.battle_state.weather_turns -= 1;
+        if battle_context.battle_state.weather_turns == 0 {
+            battle_context.battle_state.weather = None;
+        }
+    }
+
     fn apply_status_move(battle_context: &mut BattleContext, move_context: &MoveContext) {
-        if let Some(status) = &move_context.pokemove.status {
-            BattleEngine::set_status(battle_context, move_context, *status);
+        let target = move_context.target_trainer;
+        let target_slot = move_context.target_slot;
+
+        let status_applied = match &move_context.pokemove.status {
+            Some(status) => {
+                BattleEngine::set_status(battle_context, move_context, target, target_slot, *status)
+            }
+            None => true,
+        };
+
+        // a move that also sets a status, e.g. Rest, only heals if the status lands
+        if let Some(move_heal) = move_context.pokemove.heal
+            && status_applied
+        {
+            Self::apply_move_heal(battle_context, move_context, move_heal);
         }
 
         if let Some(volatile_status) = &move_context.pokemove.volatile_status {
-            Self::set_volatile_status(battle_context, move_context, *volatile_status);
+            Self::set_volatile_status(
+                battle_context,
+                move_context,
+                target,
+                target_slot,
+                *volatile_status,
+            );
+        }
+
+        if let Some(side_condition) = move_context.pokemove.side_condition {
+            Self::set_side_condition(battle_context, target, side_condition);
+        }
+
+        if let Some(weather) = move_context.pokemove.weather {
+            Self::set_weather(battle_context, weather);
         }
 
         if let Some(boosts) = &move_context.pokemove.boosts {
             for (stat, amount) in boosts {
-                Self::apply_boost(battle_context, move_context.target_trainer, *stat, *amount);
+                Self::apply_boost(battle_context, target, target_slot, *stat, *amount);
             }
         }
     }
@@ -245,11 +1122,11 @@ impl BattleEngine {
 
         let target_fainted = battle_context
             .battle_state
-            .get_active_pokemon(move_context.target_trainer)
+            .get_active_pokemon(move_context.target_trainer, move_context.target_slot)
             .is_fainted();
         let source_fainted = battle_context
             .battle_state
-            .get_active_pokemon(move_context.src_trainer)
+            .get_active_pokemon(move_context.src_trainer, move_context.src_slot)
             .is_fainted();
 
         let secondary_effects = move_context.pokemove.secondary_effects.as_ref().unwrap();
@@ -281,18 +1158,55 @@ impl BattleEngine {
 
                 match effect {
                     SecondaryEffect::Status(status) => {
-                        Self::set_status(battle_context, move_context, *status);
+                        Self::set_status(
+                            battle_context,
+                            move_context,
+                            move_context.target_trainer,
+                            move_context.target_slot,
+                            *status,
+                        );
                     }
                     SecondaryEffect::VolatileStatus(volatile_status) => {
-                        Self::set_volatile_status(battle_context, move_context, *volatile_status);
+                        Self::set_volatile_status(
+                            battle_context,
+                            move_context,
+                            move_context.target_trainer,
+                            move_context.target_slot,
+                            *volatile_status,
+                        );
+                    }
+                    SecondaryEffect::UserStatus(status) => {
+                        Self::set_status(
+                            battle_context,
+                            move_context,
+                            move_context.src_trainer,
+                            move_context.src_slot,
+                            *status,
+                        );
+                    }
+                    SecondaryEffect::UserVolatileStatus(volatile_status) => {
+                        Self::set_volatile_status(
+                            battle_context,
+                            move_context,
+                            move_context.src_trainer,
+                            move_context.src_slot,
+                            *volatile_status,
+                        );
                     }
                     SecondaryEffect::UserBoost(stat, amount) => {
-                        Self::apply_boost(battle_context, move_context.src_trainer, *stat, *amount);
+                        Self::apply_boost(
+                            battle_context,
+                            move_context.src_trainer,
+                            move_context.src_slot,
+                            *stat,
+                            *amount,
+                        );
                     }
                     SecondaryEffect::TargetBoost(stat, amount) => {
                         Self::apply_boost(
                             battle_context,
                             move_context.target_trainer,
+                            move_context.target_slot,
                             *stat,
                             *amount,
                         );
@@ -308,58 +1222,109 @@ impl BattleEngine {
         damage_dealt: u32,
         turn_state: &mut TurnState,
     ) {
-        if move_context.move_name == MoveName::Struggle {
-            let recoil_damage = battle_context
-                .battle_state
-                .get_active_pokemon(move_context.src_trainer)
-                .pokemon
-                .max_hp
-                / 4;
-
-            Self::deal_damage(
-                battle_context,
-                None,
-                Some(move_context.src_trainer),
-                turn_state,
-                recoil_damage as u32,
-            );
-        } else if move_context.pokemove.recoil.is_some() {
-            let percent = move_context.pokemove.recoil.unwrap();
+        if let Some(percent) = move_context.pokemove.recoil {
             let base_recoil_damage = (damage_dealt * percent as u32) / 100;
 
             // TODO: account for abilities that affect recoil damage
             Self::deal_damage(
                 battle_context,
                 None,
-                Some(move_context.src_trainer),
+                Some((move_context.src_trainer, move_context.src_slot)),
                 turn_state,
                 base_recoil_damage,
             );
         }
     }
 
+    fn apply_drain(
+        battle_context: &mut BattleContext,
+        move_context: &MoveContext,
+        damage_dealt: u32,
+    ) {
+        if let Some(percent) = move_context.pokemove.drain {
+            let drain_amt = cmp::max((damage_dealt * percent as u32) / 100, 1);
+            Self::heal(
+                battle_context,
+                move_context.src_trainer,
+                move_context.src_slot,
+                drain_amt,
+            );
+        }
+    }
+
+    fn apply_move_heal(
+        battle_context: &mut BattleContext,
+        move_context: &MoveContext,
+        move_heal: MoveHeal,
+    ) {
+        let max_hp = battle_context
+            .battle_state
+            .get_active_pokemon(move_context.target_trainer, move_context.target_slot)
+            .pokemon
+            .max_hp as u32;
+
+        let heal_amt = match move_heal {
+            MoveHeal::Percent(percent) => max_hp * percent as u32 / 100,
+            MoveHeal::Weather => match battle_context.battle_state.weather {
+                None => max_hp / 2,
+                Some(Weather::Sun) => max_hp * 2 / 3,
+                Some(_) => max_hp / 4,
+            },
+        };
+
+        Self::heal(
+            battle_context,
+            move_context.target_trainer,
+            move_context.target_slot,
+            heal_amt,
+        );
+    }
+
+    fn apply_self_boosts(battle_context: &mut BattleContext, move_context: &MoveContext) {
+        let Some(self_boosts) = &move_context.pokemove.self_boosts else {
+            return;
+        };
+        if battle_context
+            .battle_state
+            .get_active_pokemon(move_context.src_trainer, move_context.src_slot)
+            .is_fainted()
+        {
+            return;
+        }
+
+        for (stat, amount) in self_boosts {
+            Self::apply_boost(
+                battle_context,
+                move_context.src_trainer,
+                move_context.src_slot,
+                *stat,
+                *amount,
+            );
+        }
+    }
+
     fn apply_boost(
         battle_context: &mut BattleContext,
         target_trainer: bool,
+        target_slot: usize,
         stat: BoostableStat,
         amount: i8,
     ) {
         battle_context
             .battle_state
-            .get_active_pokemon_mut(target_trainer)
+            .get_active_pokemon_mut(target_trainer, target_slot)
             .modify_boost(stat, amount);
     }
 
     fn set_volatile_status(
         battle_context: &mut BattleContext,
         move_context: &MoveContext,
+        target: bool,
+        target_slot: usize,
         volatile_status: VolatileStatus,
     ) {
-        // FIXME: Always assumes target trainer is target of status currently
-        let target = move_context.target_trainer;
-
         let mut can_apply_volatile_status_query = Query::CanApplyVolatileStatus(
-            CanApplyVolatileStatusQuery::new(*move_context, target, volatile_status),
+            CanApplyVolatileStatusQuery::new(*move_context, target, target_slot, volatile_status),
         );
         battle_context.query_bus.query(
             &mut can_apply_volatile_status_query,
@@ -372,43 +1337,113 @@ impl BattleEngine {
             return;
         }
 
+        Self::init_volatile_status_counters(
+            battle_context,
+            move_context,
+            target,
+            target_slot,
+            volatile_status,
+        );
+
         let volatile_status_handler = battle_context
             .battle_state
-            .get_active_pokemon_mut(target)
+            .get_active_pokemon_mut(target, target_slot)
             .add_volatile_status(volatile_status);
 
         BattleEngine::register_handler(
             volatile_status_handler,
-            &mut battle_context.event_registry,
+            battle_context.event_registry,
             &mut battle_context.query_bus.registry,
         );
     }
 
-    pub fn queue_after_turn_effects(
+    fn init_volatile_status_counters(
         battle_context: &mut BattleContext,
-        turn_state: &mut TurnState,
+        move_context: &MoveContext,
+        target: bool,
+        target_slot: usize,
+        volatile_status: VolatileStatus,
     ) {
-        let trainer_1_first = Self::resolve_speed_order(battle_context);
+        match volatile_status {
+            VolatileStatus::Confusion => {
+                let confusion_turns = battle_context.battle_state.get_rand_num_inclusive(2, 5);
+                battle_context
+                    .battle_state
+                    .get_active_pokemon_mut(target, target_slot)
+                    .confusion_turns = confusion_turns;
+            }
+            VolatileStatus::Encore => {
+                let pokemon_battle_instance = battle_context
+                    .battle_state
+                    .get_active_pokemon_mut(target, target_slot);
+                pokemon_battle_instance.encore_move = pokemon_battle_instance.last_move_used;
+                pokemon_battle_instance.encore_turns = 3;
+            }
+            VolatileStatus::LeechSeed => {
+                battle_context
+                    .battle_state
+                    .get_active_pokemon_mut(target, target_slot)
+                    .leech_seed_slot = move_context.src_slot;
+            }
+            _ => {}
+        }
+    }
 
-        battle_context
-            .event_queue
-            .add_event(Event::OnTurnEnd(trainer_1_first));
-        battle_context
-            .event_queue
-            .add_event(Event::OnTurnEnd(!trainer_1_first));
+    pub fn queue_after_turn_effects(battle_context: &mut BattleContext) {
+        Self::tick_weather(battle_context);
+        Self::tick_dynamax(battle_context);
+
+        // every active pokemon's effects go in speed order, ties shuffled like actions
+        let standing: Vec<(bool, usize)> = battle_context
+            .battle_state
+            .get_active_positions()
+            .into_iter()
+            .filter(|(trainer, slot)| {
+                !battle_context
+                    .battle_state
+                    .get_active_pokemon(*trainer, *slot)
+                    .is_fainted()
+            })
+            .collect();
+        let mut positions: Vec<(u32, (bool, usize))> = standing
+            .into_iter()
+            .map(|(trainer, slot)| {
+                let speed =
+                    Self::get_effective_stat_value(battle_context, trainer, slot, StatEnum::Speed);
+                (speed, (trainer, slot))
+            })
+            .collect();
+        positions.sort_by(|(speed1, _), (speed2, _)| speed2.cmp(speed1));
+        for tied_positions in positions.chunk_by_mut(|(speed1, _), (speed2, _)| speed1 == speed2) {
+            for idx in (1..tied_positions.len()).rev() {
+                let other = battle_context.battle_state.get_rand_num(idx as u32 + 1);
+                tied_positions.swap(idx, other as usize);
+            }
+        }
+
+        for (_, (trainer, slot)) in positions {
+            battle_context
+                .event_queue
+                .add_event(Event::OnTurnEnd(trainer, slot));
+        }
+        for trainer in [true, false] {
+            battle_context
+                .event_queue
+                .add_event(Event::OnSideTurnEnd(trainer));
+        }
     }
 
     pub fn set_status(
         battle_context: &mut BattleContext,
         move_context: &MoveContext,
+        status_target: bool,
+        status_target_slot: usize,
         status: Status,
-    ) {
-        // FIXME: Always assumes that the target trainer is target of status.
-        let status_target = move_context.target_trainer;
-
+    ) -> bool {
         let mut can_apply_status_query = Query::CanApplyStatus(CanApplyStatusQuery::new(
             *move_context,
             status_target,
+            status_target_slot,
             status,
         ));
         battle_context
@@ -418,47 +1453,71 @@ impl BattleEngine {
             .into_can_apply_status_query()
             .can_apply
         {
-            return;
+            return false;
+        }
+
+        // a status can only be overwritten when a move explicitly allows it, e.g. Rest
+        if battle_context
+            .battle_state
+            .get_active_pokemon(status_target, status_target_slot)
+            .status
+            .is_some()
+        {
+            Self::remove_status(battle_context, status_target, status_target_slot);
+        }
+
+        if status == Status::Sleep {
+            // Rest always sleeps for exactly two turns, so it doesn't roll a counter
+            let sleep_turns = if move_context.move_name == MoveName::Rest {
+                REST_SLEEP_TURNS
+            } else {
+                let (min_turns, max_turns) = battle_context.battle_state.mechanics.sleep_counter;
+                battle_context
+                    .battle_state
+                    .get_rand_num_inclusive(min_turns, max_turns)
+            };
+            let pokemon_battle_instance = battle_context
+                .battle_state
+                .get_active_pokemon_mut(status_target, status_target_slot);
+            pokemon_battle_instance.sleep_turns = sleep_turns;
+            pokemon_battle_instance.self_inflicted_sleep = (status_target, status_target_slot)
+                == (move_context.src_trainer, move_context.src_slot);
         }
 
+        Self::apply_status(battle_context, status_target, status_target_slot, status);
+        true
+    }
+
+    // Gives the pokemon the status without any checks, replacing the one it had
+    pub fn apply_status(
+        battle_context: &mut BattleContext,
+        status_target: bool,
+        status_target_slot: usize,
+        status: Status,
+    ) {
         if battle_context
             .battle_state
-            .get_active_pokemon(status_target)
+            .get_active_pokemon(status_target, status_target_slot)
             .status
-            != None
+            .is_some()
         {
-            panic!("Trying to apply status to pokemon that already has a status");
-        }
-
-        // let prev_status_handler = &battle_context
-        //     .battle_state
-        //     .get_active_pokemon(move_context.target_trainer)
-        //     .status_handler;
-        // match prev_status_handler {
-        //     Some(handler) => {
-        //         BattleEngine::unregister_handler(
-        //             &handler,
-        //             &mut battle_context.event_registry,
-        //             &mut battle_context.query_bus.registry,
-        //         );
-        //     }
-        //     None => {}
-        // }
+            Self::remove_status(battle_context, status_target, status_target_slot);
+        }
 
         battle_context
             .battle_state
-            .get_active_pokemon_mut(status_target)
+            .get_active_pokemon_mut(status_target, status_target_slot)
             .set_status(status);
 
         let new_status_handler = &battle_context
             .battle_state
-            .get_active_pokemon(status_target)
+            .get_active_pokemon(status_target, status_target_slot)
             .status_handler;
         match new_status_handler {
             Some(handler) => {
                 BattleEngine::register_handler(
-                    &handler,
-                    &mut battle_context.event_registry,
+                    handler,
+                    battle_context.event_registry,
                     &mut battle_context.query_bus.registry,
                 );
             }
@@ -466,16 +1525,16 @@ impl BattleEngine {
         }
     }
 
-    pub fn remove_status(battle_context: &mut BattleContext, trainer: bool) {
+    pub fn remove_status(battle_context: &mut BattleContext, trainer: bool, slot: usize) {
         let prev_status_handler = &battle_context
             .battle_state
-            .get_active_pokemon(trainer)
+            .get_active_pokemon(trainer, slot)
             .status_handler;
         match prev_status_handler {
             Some(handler) => {
                 BattleEngine::unregister_handler(
-                    &handler,
-                    &mut battle_context.event_registry,
+                    handler,
+                    battle_context.event_registry,
                     &mut battle_context.query_bus.registry,
                 );
             }
@@ -484,33 +1543,90 @@ impl BattleEngine {
 
         battle_context
             .battle_state
-            .get_active_pokemon_mut(trainer)
+            .get_active_pokemon_mut(trainer, slot)
             .clear_status();
     }
 
     pub fn remove_volatile_status(
         battle_context: &mut BattleContext,
         trainer: bool,
+        slot: usize,
         volatile_status: VolatileStatus,
     ) {
-        let pokemon_battle_instance = battle_context.battle_state.get_active_pokemon(trainer);
+        let pokemon_battle_instance = battle_context
+            .battle_state
+            .get_active_pokemon(trainer, slot);
 
         let volatile_status_handler =
             pokemon_battle_instance.get_volatile_status_handler(&volatile_status);
 
         BattleEngine::unregister_handler(
-            &volatile_status_handler,
-            &mut battle_context.event_registry,
+            volatile_status_handler,
+            battle_context.event_registry,
             &mut battle_context.query_bus.registry,
         );
 
         battle_context
             .battle_state
-            .get_active_pokemon_mut(trainer)
+            .get_active_pokemon_mut(trainer, slot)
             .remove_volatile_status(&volatile_status);
     }
 
     fn calculate_damage(battle_context: &mut BattleContext, move_context: &MoveContext) -> u32 {
+        let is_crit = Self::roll_crit(battle_context, move_context);
+        if battle_context.battle_state.has_scripted_rolls() {
+            return Self::script_damage_roll(battle_context, move_context, is_crit);
+        }
+
+        let r = 100 - battle_context.battle_state.get_rand_num(16);
+        Self::calculate_damage_with_roll(battle_context, move_context, is_crit, r)
+    }
+
+    // While outcomes are being listed, rolls that deal the same damage are one outcome,
+    //  which keeps multi-hit moves from branching 16 ways on every hit
+    fn script_damage_roll(
+        battle_context: &mut BattleContext,
+        move_context: &MoveContext,
+        is_crit: bool,
+    ) -> u32 {
+        let mut damages: Vec<(u32, f64)> = vec![];
+        for r in 85..=100 {
+            let damage = Self::calculate_damage_with_roll(battle_context, move_context, is_crit, r);
+            match damages.iter_mut().find(|(other, _)| *other == damage) {
+                Some((_, probability)) => *probability += 1.0 / 16.0,
+                None => damages.push((damage, 1.0 / 16.0)),
+            }
+        }
+        if damages.len() == 1 {
+            return damages[0].0;
+        }
+
+        let probabilities = damages
+            .iter()
+            .map(|(_, probability)| *probability)
+            .collect();
+        let outcome = battle_context
+            .battle_state
+            .get_scripted_outcome(probabilities)
+            .expect("Damage roll scripted without scripted rolls");
+        damages[outcome].0
+    }
+
+    fn roll_crit(battle_context: &mut BattleContext, move_context: &MoveContext) -> bool {
+        let mut is_crit_query = Query::IsCrit(PayloadMoveQuery::bool(*move_context));
+        battle_context
+            .query_bus
+            .query(&mut is_crit_query, battle_context.battle_state);
+        is_crit_query.into_payload_move_query().get_bool()
+    }
+
+    // Damage for a given crit and random roll, where r goes from 85 to 100
+    fn calculate_damage_with_roll(
+        battle_context: &mut BattleContext,
+        move_context: &MoveContext,
+        is_crit: bool,
+        r: u32,
+    ) -> u32 {
         let mut base_power_query = Query::OnBasePower(PayloadMoveQuery::vec_f32(*move_context));
         battle_context
             .query_bus
@@ -519,16 +1635,22 @@ impl BattleEngine {
             .into_payload_move_query()
             .as_combined_modifier();
 
-        let (modified_atk, modified_def) = match move_context.pokemove.category {
+        let category = battle_context
+            .battle_state
+            .mechanics
+            .get_move_category(move_context.get_category_move());
+        let (modified_atk, modified_def) = match category {
             MoveCategory::Physical => (
                 BattleEngine::get_effective_stat_value(
                     battle_context,
                     move_context.src_trainer,
+                    move_context.src_slot,
                     StatEnum::Attack,
                 ),
                 BattleEngine::get_effective_stat_value(
                     battle_context,
                     move_context.target_trainer,
+                    move_context.target_slot,
                     StatEnum::Defense,
                 ),
             ),
@@ -536,11 +1658,13 @@ impl BattleEngine {
                 BattleEngine::get_effective_stat_value(
                     battle_context,
                     move_context.src_trainer,
+                    move_context.src_slot,
                     StatEnum::SpecialAttack,
                 ),
                 BattleEngine::get_effective_stat_value(
                     battle_context,
                     move_context.target_trainer,
+                    move_context.target_slot,
                     StatEnum::SpecialDefense,
                 ),
             ),
@@ -551,81 +1675,78 @@ impl BattleEngine {
         battle_context
             .query_bus
             .query(&mut mod1_query, battle_context.battle_state);
-        let mod1 = mod1_query
-            .into_payload_move_query()
-            .as_combined_modifier_default(1);
+        let mod1 = mod1_query.into_payload_move_query().as_modifier_product();
 
         let mut mod2_query = Query::OnMod2(PayloadMoveQuery::vec_f32(*move_context));
         battle_context
             .query_bus
-            .query(&mut mod2_query, battle_context.battle_state);
-        let mod2 = mod2_query
-            .into_payload_move_query()
-            .as_combined_modifier_default(1);
-
-        let mut mod3_query = Query::OnMod3(PayloadMoveQuery::vec_f32(*move_context));
-        battle_context
-            .query_bus
-            .query(&mut mod3_query, battle_context.battle_state);
-        let mod3 = mod3_query
-            .into_payload_move_query()
-            .as_combined_modifier_default(1);
+            .query(&mut mod2_query, battle_context.battle_state);
+        let mod2 = mod2_query.into_payload_move_query().as_modifier_product();
 
-        let mut is_crit_query = Query::IsCrit(PayloadMoveQuery::bool(*move_context));
+        let mut mod3_query = Query::OnMod3(PayloadMoveQuery::vec_f32(*move_context));
         battle_context
             .query_bus
-            .query(&mut is_crit_query, battle_context.battle_state);
-        let is_crit = is_crit_query.into_payload_move_query().get_bool();
+            .query(&mut mod3_query, battle_context.battle_state);
+        let mod3 = mod3_query.into_payload_move_query().as_modifier_product();
 
         let crit_mult: f32 = if !is_crit {
             1.0
         } else {
-            let mut crit_mult_query =
-                Query::CritMult(PayloadMoveQuery::f32_with_default(*move_context, 1.5));
+            let mut crit_mult_query = Query::CritMult(PayloadMoveQuery::f32_with_default(
+                *move_context,
+                battle_context.battle_state.mechanics.crit_multiplier,
+            ));
             battle_context
                 .query_bus
                 .query(&mut crit_mult_query, battle_context.battle_state);
             crit_mult_query.into_payload_move_query().get_f32()
         };
 
-        let r = (100 - battle_context.battle_state.get_rand_num(16));
-
-        let stab_mult: f32 = if !Self::is_stab(battle_context, move_context) {
-            1.0
-        } else {
-            let mut stab_mult_query =
-                Query::StabMult(PayloadMoveQuery::f32_with_default(*move_context, 1.5));
-            battle_context
-                .query_bus
-                .query(&mut stab_mult_query, battle_context.battle_state);
-            stab_mult_query.into_payload_move_query().get_f32()
+        let default_stab_mult = battle_context
+            .battle_state
+            .get_active_pokemon(move_context.src_trainer, move_context.src_slot)
+            .get_stab_mult(move_context.pokemove.move_type);
+        let stab_mult: f32 = match default_stab_mult {
+            None => 1.0,
+            Some(default_stab_mult) => {
+                let mut stab_mult_query = Query::StabMult(PayloadMoveQuery::f32_with_default(
+                    *move_context,
+                    default_stab_mult,
+                ));
+                battle_context
+                    .query_bus
+                    .query(&mut stab_mult_query, battle_context.battle_state);
+                stab_mult_query.into_payload_move_query().get_f32()
+            }
         };
 
         let move_type = move_context.pokemove.move_type;
+        let generation = battle_context.battle_state.mechanics.generation;
         let (type1_mult, type2_mult) = match battle_context
             .battle_state
-            .get_active_pokemon(move_context.target_trainer)
-            .pokemon
-            .typing
+            .get_active_pokemon(move_context.target_trainer, move_context.target_slot)
+            .get_typing()
         {
-            PokemonTyping::MonoType(t) => {
-                (effectiveness::type_effectiveness(move_type, t), 1.0 as f32)
-            }
+            PokemonTyping::MonoType(t) => (
+                effectiveness::type_effectiveness(move_type, t, generation),
+                1.0,
+            ),
             PokemonTyping::DualType(t1, t2) => (
-                effectiveness::type_effectiveness(move_type, t1),
-                effectiveness::type_effectiveness(move_type, t2),
+                effectiveness::type_effectiveness(move_type, t1, generation),
+                effectiveness::type_effectiveness(move_type, t2, generation),
             ),
         };
 
-        damage_utils::get_damage_for_move(
-            battle_context
-                .battle_state
-                .get_active_pokemon(move_context.src_trainer)
-                .pokemon
-                .level,
-            modified_base_power,
-            modified_atk,
-            modified_def,
+        let level = battle_context
+            .battle_state
+            .get_active_pokemon(move_context.src_trainer, move_context.src_slot)
+            .pokemon
+            .level;
+        damage_utils::get_damage_for_move(&DamageParams {
+            level,
+            bp: modified_base_power,
+            atk: modified_atk,
+            def: modified_def,
             mod1,
             mod2,
             mod3,
@@ -634,44 +1755,54 @@ impl BattleEngine {
             stab_mult,
             type1_mult,
             type2_mult,
-        )
-    }
-
-    fn is_stab(battle_context: &mut BattleContext, move_context: &MoveContext) -> bool {
-        let pokemon = &battle_context
-            .battle_state
-            .get_active_pokemon(move_context.src_trainer)
-            .pokemon;
-        let move_type = move_context.pokemove.move_type;
-
-        match pokemon.typing {
-            PokemonTyping::DualType(a, b) => a == move_type || b == move_type,
-            PokemonTyping::MonoType(a) => a == move_type,
-        }
+        })
     }
 
     fn get_default_immunity(
         battle_context: &mut BattleContext,
         move_context: &MoveContext,
     ) -> bool {
-        let pokemon = &battle_context
+        // moves used on the user ignore type immunities
+        if (move_context.target_trainer, move_context.target_slot)
+            == (move_context.src_trainer, move_context.src_slot)
+        {
+            return false;
+        }
+
+        let typing = battle_context
             .battle_state
-            .get_active_pokemon(move_context.target_trainer)
-            .pokemon;
+            .get_active_pokemon(move_context.target_trainer, move_context.target_slot)
+            .get_typing();
         let move_type = move_context.pokemove.move_type;
+        let generation = battle_context.battle_state.mechanics.generation;
 
-        match pokemon.typing {
+        match typing {
             PokemonTyping::DualType(a, b) => {
-                effectiveness::type_immunity(move_type, a)
-                    || effectiveness::type_immunity(move_type, b)
+                effectiveness::type_immunity(move_type, a, generation)
+                    || effectiveness::type_immunity(move_type, b, generation)
             }
-            PokemonTyping::MonoType(a) => effectiveness::type_immunity(move_type, a),
+            PokemonTyping::MonoType(a) => effectiveness::type_immunity(move_type, a, generation),
         }
     }
 
-    pub fn resolve_speed_order(battle_context: &mut BattleContext) -> bool {
-        let speed1 = BattleEngine::get_effective_stat_value(battle_context, true, StatEnum::Speed);
-        let speed2 = BattleEngine::get_effective_stat_value(battle_context, false, StatEnum::Speed);
+    // returns true if the pokemon at `first` moves before the pokemon at `second`
+    pub fn resolve_speed_order(
+        battle_context: &mut BattleContext,
+        first: (bool, usize),
+        second: (bool, usize),
+    ) -> bool {
+        let speed1 = BattleEngine::get_effective_stat_value(
+            battle_context,
+            first.0,
+            first.1,
+            StatEnum::Speed,
+        );
+        let speed2 = BattleEngine::get_effective_stat_value(
+            battle_context,
+            second.0,
+            second.1,
+            StatEnum::Speed,
+        );
         if speed1 > speed2 {
             true
         } else if speed2 > speed1 {
@@ -684,6 +1815,7 @@ impl BattleEngine {
     fn check_move_execution(
         battle_context: &mut BattleContext,
         move_context: &MoveContext,
+        turn_state: &mut TurnState,
     ) -> bool {
         let mut try_use_move_query = Query::TryUseMove(TryUseMoveQuery::new(*move_context));
         battle_context
@@ -694,9 +1826,15 @@ impl BattleEngine {
             battle_context,
             move_context,
             &try_use_move_payload,
+            turn_state,
         );
-        if try_use_move_payload.should_cancel {
-            return false;
+        !try_use_move_payload.should_cancel
+    }
+
+    fn check_move_target(battle_context: &mut BattleContext, move_context: &MoveContext) -> bool {
+        // moves aimed at a side or the field can't be dodged or resisted
+        if !move_context.pokemove.target.targets_pokemon() {
+            return true;
         }
 
         let mut invuln_query =
@@ -723,15 +1861,19 @@ impl BattleEngine {
     }
 
     fn check_move_hit(battle_context: &mut BattleContext, move_context: &MoveContext) -> bool {
-        if move_context.pokemove.accuracy.is_none() {
+        let Some(accuracy) = battle_context
+            .battle_state
+            .mechanics
+            .get_move_accuracy(move_context.pokemove)
+        else {
             return true;
-        }
+        };
 
         let mut get_move_hit_chance_query =
             Query::GetMoveHitChance(PayloadMoveQuery::vec_f32_with_default(
                 *move_context,
                 vec![
-                    move_context.pokemove.accuracy.unwrap() as f32,
+                    accuracy as f32,
                     Self::get_accuracy_mutliplier(battle_context, move_context),
                 ],
             ));
@@ -742,42 +1884,98 @@ impl BattleEngine {
             get_move_hit_chance_query
                 .into_payload_move_query()
                 .as_combined_modifier(),
-            100 as u32,
+            100,
         ) as u8;
 
-        let accuracy_roll = battle_context.battle_state.get_rand_num_inclusive(1, 100);
-        accuracy_roll <= modified_accuracy
+        battle_context
+            .battle_state
+            .get_random_check(modified_accuracy as u32, 100)
     }
 
     fn process_try_use_move_secondary_effects(
         battle_context: &mut BattleContext,
         move_context: &MoveContext,
         try_use_move_payload: &TryUseMoveQuery,
+        turn_state: &mut TurnState,
     ) {
         if try_use_move_payload.unfreeze || try_use_move_payload.wake_sleep {
-            BattleEngine::remove_status(battle_context, move_context.target_trainer);
+            BattleEngine::remove_status(
+                battle_context,
+                move_context.src_trainer,
+                move_context.src_slot,
+            );
         } else if try_use_move_payload.confuse_self {
-            todo!("Implement deal damage from confusion");
+            BattleEngine::deal_confusion_damage(
+                battle_context,
+                move_context.src_trainer,
+                move_context.src_slot,
+                turn_state,
+            );
         } else if try_use_move_payload.unconfuse {
             BattleEngine::remove_volatile_status(
                 battle_context,
-                move_context.target_trainer,
+                move_context.src_trainer,
+                move_context.src_slot,
                 VolatileStatus::Confusion,
             );
         }
     }
 
-    fn switch_out_pokemon(battle_context: &mut BattleContext, trainer: bool, switch_idx: usize) {
+    // The user hits itself with a typeless 40 power physical attack that can't crit,
+    //  using its own attack and defense with boosts but without items or abilities
+    fn deal_confusion_damage(
+        battle_context: &mut BattleContext,
+        trainer: bool,
+        slot: usize,
+        turn_state: &mut TurnState,
+    ) {
+        let pokemon_battle_instance = battle_context
+            .battle_state
+            .get_active_pokemon(trainer, slot);
+        let [atk, def] = [StatEnum::Attack, StatEnum::Defense].map(|stat| {
+            damage_utils::rounded_damage_from_modifiers(&[
+                pokemon_battle_instance.pokemon.get_stat_value(stat) as f32,
+                stat_utils::get_stat_boost_to_multiplier(
+                    pokemon_battle_instance.boosts[BoostableStat::Stat(stat)],
+                ),
+            ])
+        });
+        let level = pokemon_battle_instance.pokemon.level;
+
+        let r = 100 - battle_context.battle_state.get_rand_num(16);
+        let damage = damage_utils::get_damage_for_move(
+            &DamageParams::builder()
+                .level(level)
+                .bp(40)
+                .atk(atk)
+                .def(def)
+                .r(r)
+                .build(),
+        );
+        Self::deal_damage(
+            battle_context,
+            None,
+            Some((trainer, slot)),
+            turn_state,
+            damage,
+        );
+    }
+
+    fn switch_out_pokemon(battle_context: &mut BattleContext, trainer: bool, slot: usize) {
         let pokemon_battle_instance = battle_context
             .battle_state
-            .get_side_mut(trainer)
-            .get_active_pokemon();
+            .get_active_pokemon(trainer, slot);
 
         BattleEngine::unregister_handlers_for_pokemon(
-            &mut battle_context.event_registry,
+            battle_context.event_registry,
             &mut battle_context.query_bus.registry,
             pokemon_battle_instance,
         );
+
+        battle_context
+            .battle_state
+            .get_active_pokemon_mut(trainer, slot)
+            .reset();
     }
 
     fn get_multi_hit_hits(battle_context: &mut BattleContext, move_context: &MoveContext) -> u8 {
@@ -807,24 +2005,26 @@ impl BattleEngine {
     pub fn get_effective_stat_value(
         battle_context: &mut BattleContext,
         trainer: bool,
+        slot: usize,
         stat_enum: StatEnum,
     ) -> u32 {
         let base_stat_value = battle_context
             .battle_state
-            .get_active_pokemon(trainer)
+            .get_active_pokemon(trainer, slot)
             .pokemon
             .get_stat_value(stat_enum);
         let boost_value = battle_context
             .battle_state
-            .get_active_pokemon(trainer)
+            .get_active_pokemon(trainer, slot)
             .boosts[BoostableStat::Stat(stat_enum)];
 
         let mut stat_query = Query::OnStat(OnStatQuery {
             trainer,
+            slot,
             stat: stat_enum,
             mults: vec![
                 base_stat_value as f32,
-                BattleEngine::get_stat_boost_to_multiplier(boost_value),
+                stat_utils::get_stat_boost_to_multiplier(boost_value),
             ],
         });
         battle_context
@@ -840,11 +2040,11 @@ impl BattleEngine {
     ) -> f32 {
         let src_accuracy_stage = battle_context
             .battle_state
-            .get_active_pokemon(move_context.src_trainer)
+            .get_active_pokemon(move_context.src_trainer, move_context.src_slot)
             .boosts[BoostableStat::Accuracy];
         let target_evasion_stage = battle_context
             .battle_state
-            .get_active_pokemon(move_context.target_trainer)
+            .get_active_pokemon(move_context.target_trainer, move_context.target_slot)
             .boosts[BoostableStat::Evasion];
 
         let overall_accuracy_stage = (src_accuracy_stage - target_evasion_stage).clamp(-6, 6);
@@ -869,14 +2069,6 @@ impl BattleEngine {
         percent as f32 / 100.0
     }
 
-    fn get_stat_boost_to_multiplier(boost: i8) -> f32 {
-        if boost >= 0 {
-            (2.0 + boost as f32) / 2.0
-        } else {
-            2.0 / (2 - boost) as f32
-        }
-    }
-
     fn register_handlers_for_pokemon(
         event_registry: &mut Registry<Event, dyn EventHandler>,
         query_registry: &mut Registry<Query, dyn QueryHandler>,
@@ -913,3 +2105,676 @@ impl BattleEngine {
         query_registry.remove_handler(handler.clone());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        battle::{
+            actions::{Action, MoveModifier, MoveSlot, TargetLoc},
+            battle::Battle,
+            battle_engine::BattleEngine,
+            battle_input::SingleInput,
+            move_lock::MoveLock,
+            test_utils,
+        },
+        common::context::MoveContext,
+        core::{
+            ability::ability::Ability,
+            field::weather::Weather,
+            mechanics::generation::Generation,
+            pokemon::{boostable_stat::BoostableStat, species::Species, stat_enum::StatEnum},
+            pokemove::move_name::MoveName,
+            poketype::poketype::PokeType,
+            side_condition::side_condition::SideCondition,
+            status::{status::Status, volatile_status::VolatileStatus},
+        },
+        dex::pokemove::move_dex,
+        event::event_type::Event,
+    };
+
+    const DRAGONITE: &str = "Dragonite\nAbility: Thick Fat\n- Outrage";
+    const FERROTHORN: &str = "Ferrothorn\nAbility: Thick Fat\n- Swords Dance";
+    const TERA_FERROTHORN: &str =
+        "Ferrothorn\nAbility: Thick Fat\nTera Type: Fairy\n- Swords Dance";
+
+    fn use_move(modifier: MoveModifier) -> Action {
+        Action::Move(MoveSlot::Slot0, modifier)
+    }
+
+    #[test]
+    fn rampage_lasts_two_to_three_turns_then_confuses() {
+        let mut lengths = vec![];
+        for seed in 0..20 {
+            let mut battle = test_utils::get_battle(DRAGONITE, FERROTHORN, seed);
+            let mut turns = 0;
+            loop {
+                let input = test_utils::get_input(
+                    &battle,
+                    use_move(MoveModifier::None),
+                    use_move(MoveModifier::None),
+                );
+                battle.process_input(input);
+                turns += 1;
+                if battle
+                    .battle_state()
+                    .get_active_pokemon(true, 0)
+                    .move_lock
+                    .is_none()
+                {
+                    break;
+                }
+            }
+
+            assert!(
+                battle
+                    .battle_state()
+                    .get_active_pokemon(true, 0)
+                    .volatile_statuses
+                    .contains_key(&VolatileStatus::Confusion)
+            );
+            lengths.push(turns);
+        }
+
+        assert!(lengths.iter().all(|turns| *turns == 2 || *turns == 3));
+        assert!(lengths.contains(&2) && lengths.contains(&3));
+    }
+
+    #[test]
+    fn rampage_disrupted_on_last_turn_confuses() {
+        for seed in 0..20 {
+            let mut battle = test_utils::get_battle(DRAGONITE, TERA_FERROTHORN, seed);
+            let input = test_utils::get_input(
+                &battle,
+                use_move(MoveModifier::None),
+                use_move(MoveModifier::None),
+            );
+            battle.process_input(input);
+            let turns_left = match battle.battle_state().get_active_pokemon(true, 0).move_lock {
+                Some(MoveLock::Rampage(MoveName::Outrage, turns_left)) => turns_left,
+                move_lock => panic!("Expected a rampage, got {move_lock:?}"),
+            };
+
+            // the target becoming a fairy type makes the next outrage fail
+            let input = test_utils::get_input(
+                &battle,
+                use_move(MoveModifier::None),
+                use_move(MoveModifier::Terastallize),
+            );
+            battle.process_input(input);
+
+            let dragonite = battle.battle_state().get_active_pokemon(true, 0);
+            assert_eq!(dragonite.move_lock, None);
+            assert_eq!(
+                dragonite
+                    .volatile_statuses
+                    .contains_key(&VolatileStatus::Confusion),
+                turns_left == 1
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "is not available for this pokemon")]
+    fn choosing_another_move_while_locked_panics() {
+        let mut battle = test_utils::get_battle(
+            "Garchomp\nAbility: Sand Force\n- Outrage\n- Swords Dance",
+            FERROTHORN,
+            0,
+        );
+        for move_slot in [MoveSlot::Slot0, MoveSlot::Slot1] {
+            battle.process_input((
+                vec![Some(SingleInput::StandardInput(Action::Move(
+                    move_slot,
+                    MoveModifier::None,
+                )))],
+                vec![Some(SingleInput::StandardInput(use_move(
+                    MoveModifier::None,
+                )))],
+            ));
+        }
+    }
+
+    #[test]
+    fn confusion_from_rampage_can_hit_the_user() {
+        let mut hit_itself = false;
+        for seed in 0..20 {
+            let mut battle = test_utils::get_battle(DRAGONITE, FERROTHORN, seed);
+            // ferrothorn never attacks, so any damage dragonite takes is from confusion
+            for _ in 0..6 {
+                let input = test_utils::get_input(
+                    &battle,
+                    use_move(MoveModifier::None),
+                    use_move(MoveModifier::None),
+                );
+                battle.process_input(input);
+            }
+
+            let dragonite = &battle.battle_state().get_active_pokemon(true, 0).pokemon;
+            hit_itself |= dragonite.hp < dragonite.max_hp;
+        }
+        assert!(hit_itself);
+    }
+
+    #[test]
+    fn flinching_only_lasts_one_turn() {
+        let mut flinched = false;
+        for seed in 0..20 {
+            let mut battle = test_utils::get_battle(
+                "Tyranitar\nAbility: Thick Fat\n- Rock Slide\n- Swords Dance",
+                "Snorlax\nAbility: Thick Fat\n- Tackle",
+                seed,
+            );
+            let max_hp = get_hp(&battle, true);
+            play_turn(&mut battle);
+            // tyranitar moves first, so snorlax's tackle only misses the turn it flinched
+            if get_hp(&battle, true) != max_hp {
+                continue;
+            }
+            flinched = true;
+            assert!(
+                !battle
+                    .battle_state()
+                    .get_active_pokemon(false, 0)
+                    .volatile_statuses
+                    .contains_key(&VolatileStatus::Flinch)
+            );
+
+            let input = test_utils::get_input(
+                &battle,
+                Action::Move(MoveSlot::Slot1, MoveModifier::None),
+                use_move(MoveModifier::None),
+            );
+            battle.process_input(input);
+            assert!(get_hp(&battle, true) < max_hp);
+        }
+        assert!(flinched);
+    }
+
+    const SNORLAX: &str = "Snorlax\nAbility: Thick Fat\n- Swords Dance";
+
+    fn play_turn(battle: &mut Battle) {
+        let input = test_utils::get_input(
+            battle,
+            use_move(MoveModifier::None),
+            use_move(MoveModifier::None),
+        );
+        battle.process_input(input);
+    }
+
+    fn get_hp(battle: &Battle, trainer: bool) -> u16 {
+        battle
+            .battle_state()
+            .get_active_pokemon(trainer, 0)
+            .pokemon
+            .hp
+    }
+
+    fn set_hp(battle: &mut Battle, trainer: bool, hp: u16) {
+        battle
+            .battle_context()
+            .battle_state
+            .get_active_pokemon_mut(trainer, 0)
+            .pokemon
+            .hp = hp;
+    }
+
+    #[test]
+    fn drain_heals_half_the_damage_dealt() {
+        for seed in 0..10 {
+            let mut battle =
+                test_utils::get_battle("Venusaur\nAbility: Overgrow\n- Giga Drain", SNORLAX, seed);
+            set_hp(&mut battle, true, 1);
+            let max_hp = battle
+                .battle_state()
+                .get_active_pokemon(false, 0)
+                .pokemon
+                .max_hp;
+            play_turn(&mut battle);
+
+            let damage_dealt = max_hp - get_hp(&battle, false);
+            assert_eq!(get_hp(&battle, true), 1 + (damage_dealt / 2).max(1));
+        }
+    }
+
+    #[test]
+    fn recover_heals_half_max_hp() {
+        let mut battle = test_utils::get_battle("Starmie\nAbility: Torrent\n- Recover", SNORLAX, 0);
+        set_hp(&mut battle, true, 1);
+        play_turn(&mut battle);
+
+        let max_hp = battle
+            .battle_state()
+            .get_active_pokemon(true, 0)
+            .pokemon
+            .max_hp;
+        assert_eq!(get_hp(&battle, true), 1 + max_hp / 2);
+
+        // healing stops at max hp
+        play_turn(&mut battle);
+        play_turn(&mut battle);
+        assert_eq!(get_hp(&battle, true), max_hp);
+    }
+
+    #[test]
+    fn fixed_damage_ignores_stats() {
+        for (attacker, expected) in [
+            ("Alakazam\nAbility: Technician\n- Seismic Toss", 100),
+            (
+                "Alakazam\nAbility: Technician\nLevel: 50\n- Seismic Toss",
+                50,
+            ),
+            ("Alakazam\nAbility: Technician\n- Dragon Rage", 40),
+        ] {
+            let mut battle = test_utils::get_battle(attacker, SNORLAX, 0);
+            let max_hp = get_hp(&battle, false);
+            play_turn(&mut battle);
+            assert_eq!(max_hp - get_hp(&battle, false), expected);
+        }
+
+        let mut battle =
+            test_utils::get_battle("Alakazam\nAbility: Technician\n- Super Fang", SNORLAX, 0);
+        set_hp(&mut battle, false, 101);
+        play_turn(&mut battle);
+        assert_eq!(get_hp(&battle, false), 51);
+    }
+
+    #[test]
+    fn self_side_and_field_moves_hit_their_targets() {
+        let mut battle = test_utils::get_battle(
+            "Skarmory\nAbility: Technician\n- Swords Dance\n- Spikes\n- Reflect\n- Sunny Day",
+            SNORLAX,
+            0,
+        );
+        for slot in [
+            MoveSlot::Slot0,
+            MoveSlot::Slot1,
+            MoveSlot::Slot2,
+            MoveSlot::Slot3,
+        ] {
+            let input = test_utils::get_input(
+                &battle,
+                Action::Move(slot, MoveModifier::None),
+                use_move(MoveModifier::None),
+            );
+            battle.process_input(input);
+        }
+
+        let battle_state = battle.battle_state();
+        assert_eq!(
+            battle_state.get_active_pokemon(true, 0).boosts[BoostableStat::Stat(StatEnum::Attack)],
+            2
+        );
+        assert_eq!(battle_state.get_side(false).spikes_layers, 1);
+        assert!(
+            battle_state
+                .get_side(true)
+                .has_side_condition(&SideCondition::Reflect)
+        );
+        assert!(
+            !battle_state
+                .get_side(false)
+                .has_side_condition(&SideCondition::Reflect)
+        );
+        assert_eq!(battle_state.weather, Some(Weather::Sun));
+    }
+
+    #[test]
+    fn terastallizing_changes_typing_once_per_battle() {
+        let mut battle = test_utils::get_battle(TERA_FERROTHORN, SNORLAX, 0);
+        let input = test_utils::get_input(
+            &battle,
+            use_move(MoveModifier::Terastallize),
+            use_move(MoveModifier::None),
+        );
+        battle.process_input(input);
+
+        let battle_state = battle.battle_state();
+        let ferrothorn = battle_state.get_active_pokemon(true, 0);
+        assert!(ferrothorn.terastallized);
+        let typing = ferrothorn.get_typing();
+        assert!(typing.has_type(PokeType::Fairy) && !typing.has_type(PokeType::Steel));
+        assert!(
+            !battle_state
+                .get_side(true)
+                .get_valid_actions(0, &battle_state.mechanics)
+                .can_terastallize
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "is not available for this pokemon")]
+    fn terastallizing_twice_panics() {
+        let mut battle = test_utils::get_battle(TERA_FERROTHORN, SNORLAX, 0);
+        for _ in 0..2 {
+            let input = test_utils::get_input(
+                &battle,
+                use_move(MoveModifier::Terastallize),
+                use_move(MoveModifier::None),
+            );
+            battle.process_input(input);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "is not available for this pokemon")]
+    fn terastallizing_before_gen_9_panics() {
+        let mut battle = test_utils::get_battle(TERA_FERROTHORN, SNORLAX, 0);
+        battle.set_generation(Generation::Gen8);
+        let input = test_utils::get_input(
+            &battle,
+            use_move(MoveModifier::Terastallize),
+            use_move(MoveModifier::None),
+        );
+        battle.process_input(input);
+    }
+
+    #[test]
+    fn tera_stab_stacks_with_original_types() {
+        let get_stab_mults = |tera_type: &str, move_types: &[PokeType]| {
+            let mut battle = test_utils::get_battle(
+                &format!("Ferrothorn\nAbility: Thick Fat\nTera Type: {tera_type}\n- Swords Dance"),
+                SNORLAX,
+                0,
+            );
+            BattleEngine::terastallize(&mut battle.battle_context(), true, 0);
+            let ferrothorn = battle.battle_state().get_active_pokemon(true, 0);
+            move_types
+                .iter()
+                .map(|move_type| ferrothorn.get_stab_mult(*move_type))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            get_stab_mults(
+                "Fairy",
+                &[PokeType::Fairy, PokeType::Grass, PokeType::Water]
+            ),
+            vec![Some(1.5), Some(1.5), None]
+        );
+        assert_eq!(
+            get_stab_mults("Grass", &[PokeType::Grass, PokeType::Steel]),
+            vec![Some(2.0), Some(1.5)]
+        );
+        assert_eq!(
+            get_stab_mults(
+                "Stellar",
+                &[PokeType::Grass, PokeType::Water, PokeType::Typeless]
+            ),
+            vec![Some(2.0), Some(1.2), None]
+        );
+    }
+
+    fn get_gimmick_battle(team_1: &str, generation: Generation) -> Battle {
+        let mut battle = test_utils::get_battle(team_1, SNORLAX, 0);
+        battle.set_generation(generation);
+        battle
+    }
+
+    fn play_modified_turn(battle: &mut Battle, modifier: MoveModifier) {
+        let input = test_utils::get_input(battle, use_move(modifier), use_move(MoveModifier::None));
+        battle.process_input(input);
+    }
+
+    #[test]
+    fn mega_evolution_changes_species_and_ability() {
+        let mut battle = get_gimmick_battle(
+            "Venusaur @ Venusaurite\nAbility: Overgrow\n- Tackle",
+            Generation::Gen7,
+        );
+        play_modified_turn(&mut battle, MoveModifier::MegaEvolve);
+
+        let battle_state = battle.battle_state();
+        let venusaur = &battle_state.get_active_pokemon(true, 0).pokemon;
+        assert_eq!(venusaur.species, Species::VenusaurMega);
+        assert_eq!(venusaur.ability, Ability::ThickFat);
+        assert!(
+            !battle_state
+                .get_side(true)
+                .get_valid_actions(0, &battle_state.mechanics)
+                .can_mega_evolve
+        );
+    }
+
+    #[test]
+    fn z_move_replaces_the_move_once_per_battle() {
+        let mut battle = get_gimmick_battle(
+            "Snorlax @ Normalium Z\nAbility: Thick Fat\n- Tackle",
+            Generation::Gen7,
+        );
+        let max_hp = get_hp(&battle, false);
+        play_modified_turn(&mut battle, MoveModifier::ZMove);
+        let z_move_damage = max_hp - get_hp(&battle, false);
+        set_hp(&mut battle, false, max_hp);
+        play_turn(&mut battle);
+        let tackle_damage = max_hp - get_hp(&battle, false);
+        assert!(z_move_damage > 2 * tackle_damage);
+
+        let battle_state = battle.battle_state();
+        assert_eq!(
+            battle_state.get_active_pokemon(true, 0).last_move_used,
+            Some(MoveName::Tackle)
+        );
+        assert!(
+            battle_state
+                .get_side(true)
+                .get_valid_actions(0, &battle_state.mechanics)
+                .z_moves
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn dynamax_doubles_hp_for_three_turns() {
+        let mut battle = get_gimmick_battle(SNORLAX, Generation::Gen8);
+        let max_hp = get_hp(&battle, true);
+        play_modified_turn(&mut battle, MoveModifier::Dynamax);
+        for _ in 0..2 {
+            let snorlax = battle.battle_state().get_active_pokemon(true, 0);
+            assert!(snorlax.is_dynamaxed());
+            assert_eq!(snorlax.pokemon.max_hp, 2 * max_hp);
+            assert_eq!(snorlax.pokemon.hp, 2 * max_hp);
+            play_turn(&mut battle);
+        }
+
+        let snorlax = battle.battle_state().get_active_pokemon(true, 0);
+        assert!(!snorlax.is_dynamaxed());
+        assert_eq!(snorlax.pokemon.max_hp, max_hp);
+        assert_eq!(snorlax.pokemon.hp, max_hp);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not available for this pokemon")]
+    fn gimmicks_outside_their_generation_panic() {
+        let mut battle = get_gimmick_battle(SNORLAX, Generation::Gen7);
+        play_modified_turn(&mut battle, MoveModifier::Dynamax);
+    }
+
+    const GARCHOMP: &str = "Garchomp\nAbility: Sand Force\n- Tackle\n- Earthquake";
+    const VENUSAUR: &str = "Venusaur\nAbility: Overgrow\n- Tackle";
+    const SNORLAXES: &str = "Snorlax\nAbility: Thick Fat\n- Swords Dance\n- Follow Me\n- Rage Powder\n\n\
+        Snorlax\nAbility: Thick Fat\n- Swords Dance\n- Follow Me\n- Rage Powder";
+
+    fn get_doubles_battle(team_1_lead: &str) -> Battle {
+        test_utils::get_doubles_battle(&format!("{team_1_lead}\n\n{SNORLAX}"), SNORLAXES, 0)
+    }
+
+    fn use_move_slot(move_idx: usize) -> Action {
+        Action::Move(MoveSlot::from_index(move_idx), MoveModifier::None)
+    }
+
+    fn tackle(foe_slot: usize) -> Action {
+        Action::TargetedMove(
+            MoveSlot::Slot0,
+            TargetLoc::Opponent(foe_slot),
+            MoveModifier::None,
+        )
+    }
+
+    fn get_hp_lost(battle: &Battle, trainer: bool, slot: usize) -> u16 {
+        let pokemon = &battle
+            .battle_state()
+            .get_active_pokemon(trainer, slot)
+            .pokemon;
+        pokemon.max_hp - pokemon.hp
+    }
+
+    #[test]
+    fn doubles_moves_hit_the_chosen_slot() {
+        for foe_slot in 0..2 {
+            let mut battle = get_doubles_battle(GARCHOMP);
+            let input = test_utils::get_doubles_input(
+                [tackle(foe_slot), use_move_slot(0)],
+                [use_move_slot(0), use_move_slot(0)],
+            );
+            battle.process_input(input);
+
+            assert!(get_hp_lost(&battle, false, foe_slot) > 0);
+            assert_eq!(get_hp_lost(&battle, false, 1 - foe_slot), 0);
+        }
+    }
+
+    #[test]
+    fn follow_me_and_rage_powder_redirect_single_target_moves() {
+        // Follow Me draws the attack to the pokemon that used it
+        let mut battle = get_doubles_battle(GARCHOMP);
+        let input = test_utils::get_doubles_input(
+            [tackle(0), use_move_slot(0)],
+            [use_move_slot(0), use_move_slot(1)],
+        );
+        battle.process_input(input);
+        assert_eq!(get_hp_lost(&battle, false, 0), 0);
+        assert!(get_hp_lost(&battle, false, 1) > 0);
+
+        // Rage Powder is a powder move, so it doesn't draw in grass types
+        for (attacker, is_redirected) in [(GARCHOMP, true), (VENUSAUR, false)] {
+            let mut battle = get_doubles_battle(attacker);
+            let input = test_utils::get_doubles_input(
+                [tackle(0), use_move_slot(0)],
+                [use_move_slot(0), use_move_slot(2)],
+            );
+            battle.process_input(input);
+            assert_eq!(get_hp_lost(&battle, false, 0) == 0, is_redirected);
+            assert_eq!(get_hp_lost(&battle, false, 1) > 0, is_redirected);
+        }
+    }
+
+    #[test]
+    fn spread_moves_hit_every_adjacent_pokemon() {
+        let mut battle = get_doubles_battle(GARCHOMP);
+        let input = test_utils::get_doubles_input(
+            [use_move_slot(1), use_move_slot(0)],
+            [use_move_slot(0), use_move_slot(0)],
+        );
+        battle.process_input(input);
+
+        // Earthquake hits the ally too, but not the user
+        assert_eq!(get_hp_lost(&battle, true, 0), 0);
+        assert!(get_hp_lost(&battle, true, 1) > 0);
+        assert!(get_hp_lost(&battle, false, 0) > 0);
+        assert!(get_hp_lost(&battle, false, 1) > 0);
+    }
+
+    #[test]
+    fn spread_flinches_only_last_one_turn() {
+        let get_attack_boost = |battle: &Battle, slot: usize| {
+            battle.battle_state().get_active_pokemon(false, slot).boosts
+                [BoostableStat::Stat(StatEnum::Attack)]
+        };
+        let mut flinched = false;
+        for seed in 0..20 {
+            let mut battle = test_utils::get_doubles_battle(
+                &format!(
+                    "Tyranitar\nAbility: Thick Fat\n- Rock Slide\n- Swords Dance\n\n{SNORLAX}"
+                ),
+                SNORLAXES,
+                seed,
+            );
+            let input = test_utils::get_doubles_input(
+                [use_move_slot(0), use_move_slot(0)],
+                [use_move_slot(0), use_move_slot(0)],
+            );
+            battle.process_input(input);
+            // a foe that flinched didn't get its swords dance boost
+            let boosts: Vec<i8> = (0..2).map(|slot| get_attack_boost(&battle, slot)).collect();
+            if !boosts.contains(&0) {
+                continue;
+            }
+            flinched = true;
+
+            let input = test_utils::get_doubles_input(
+                [use_move_slot(1), use_move_slot(0)],
+                [use_move_slot(0), use_move_slot(0)],
+            );
+            battle.process_input(input);
+            for (slot, boost) in boosts.into_iter().enumerate() {
+                assert_eq!(get_attack_boost(&battle, slot), boost + 2);
+            }
+        }
+        assert!(flinched);
+    }
+
+    #[test]
+    fn end_of_turn_effects_go_in_speed_order_of_every_active_pokemon() {
+        let mut battle = test_utils::get_doubles_battle(
+            "Snorlax\nAbility: Thick Fat\n- Tackle\n\nGarchomp\nAbility: Sand Force\n- Tackle",
+            "Pikachu\nAbility: Technician\n- Tackle\n\nTyranitar\nAbility: Sand Force\n- Tackle",
+            0,
+        );
+        let get_turn_end_order = |battle: &mut Battle| {
+            let mut battle_context = battle.battle_context();
+            BattleEngine::queue_after_turn_effects(&mut battle_context);
+            let mut order = vec![];
+            while let Some(event) = battle_context.event_queue.dequeue() {
+                if let Event::OnTurnEnd(trainer, slot) = event {
+                    order.push((trainer, slot));
+                }
+            }
+            order
+        };
+
+        // Garchomp in slot 1 is the fastest, Snorlax in slot 0 the slowest
+        assert_eq!(
+            get_turn_end_order(&mut battle),
+            vec![(true, 1), (false, 0), (false, 1), (true, 0)]
+        );
+
+        // fainted pokemon have no end of turn effects
+        battle
+            .battle_context()
+            .battle_state
+            .get_active_pokemon_mut(true, 0)
+            .status = Some(Status::Faint);
+        assert_eq!(
+            get_turn_end_order(&mut battle),
+            vec![(true, 1), (false, 0), (false, 1)]
+        );
+    }
+
+    #[test]
+    fn spread_moves_deal_three_quarters_damage() {
+        let mut battle = get_doubles_battle(GARCHOMP);
+        let mut get_rolls = |is_spread: bool| {
+            let move_context = MoveContext {
+                src_trainer: true,
+                src_slot: 0,
+                target_trainer: false,
+                target_slot: 0,
+                move_name: MoveName::Earthquake,
+                pokemove: move_dex::get_move_data(&MoveName::Earthquake),
+                is_spread,
+                base_move: None,
+            };
+            BattleEngine::get_damage_rolls(&mut battle.battle_context(), &move_context)
+                .expect("Earthquake should deal damage")
+                .rolls
+        };
+
+        let single_rolls = get_rolls(false);
+        let spread_rolls = get_rolls(true);
+        for (single, spread) in single_rolls.iter().zip(spread_rolls) {
+            assert!(spread < *single);
+            // the modifier is applied before the later rounding steps
+            assert!((spread as f64 / *single as f64 - 0.75).abs() < 0.01);
+        }
+    }
+}
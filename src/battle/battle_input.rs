@@ -1,8 +1,14 @@
 use crate::battle::actions::{Action, SwitchSlot};
 
+#[derive(Clone)]
 pub enum SingleInput {
     StandardInput(Action),
     SwitchInInput(SwitchSlot),
+    TeamOrderInput(Vec<usize>), // party indices to bring, leads first
 }
 
-pub type BattleInput = (Option<SingleInput>, Option<SingleInput>);
+// One entry per active slot, None for slots that were not asked for input
+//  team preview takes a single TeamOrderInput per side
+pub type SideInput = Vec<Option<SingleInput>>;
+
+pub type BattleInput = (SideInput, SideInput);
@@ -1,4 +1,9 @@
-use crate::battle::valid_actions::{ValidActions, ValidSwitches};
+use serde::Serialize;
+
+use crate::{
+    battle::valid_actions::{ValidActions, ValidSwitches},
+    core::pokemon::species::Species,
+};
 
 pub enum SingleBattleRequest {
     SwitchInRequest(ValidSwitches),
@@ -11,11 +16,22 @@ pub enum ActionResponse {
     Continue,
 }
 
+// One entry per active slot, None for slots that need no input
+pub type SideRequest = Vec<Option<SingleBattleRequest>>;
+
+// Sent once before the first turn, answered with a TeamOrderInput
+pub struct TeamPreviewRequest {
+    pub opponent_species: Vec<Species>,
+    pub team_size: usize, // number of pokemon to bring, e.g. 4 of 6
+}
+
 pub enum BattleRequest {
-    Request(Option<SingleBattleRequest>, Option<SingleBattleRequest>),
+    TeamPreview(TeamPreviewRequest, TeamPreviewRequest),
+    Request(SideRequest, SideRequest),
     BattleEnded(Winner), // winner is trainer_side
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum Winner {
     Trainer1,
     Trainer2,
@@ -0,0 +1,499 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use typed_builder::TypedBuilder;
+
+use crate::{
+    battle::{
+        actions::{Action, MoveModifier, MoveSlot},
+        battle::Battle,
+        battle_engine::BattleEngine,
+    },
+    core::{
+        field::weather::Weather,
+        mechanics::generation::Generation,
+        pokemon::{boostable_stat::BoostableStat, pokemon::Pokemon},
+        pokemove::move_name::MoveName,
+        side_condition::side_condition::SideCondition,
+        status::status::Status,
+    },
+    dex::pokemove::move_dex,
+};
+
+pub const NUM_DAMAGE_ROLLS: usize = 16;
+
+// Damage of a single hit for each random roll, from the lowest roll (85%) up
+#[derive(Clone, Debug, Serialize)]
+pub struct DamageRolls {
+    pub rolls: [u32; NUM_DAMAGE_ROLLS],
+    pub crit_rolls: [u32; NUM_DAMAGE_ROLLS],
+    pub crit_chance: f64,
+    pub hit_chances: Vec<(u8, f64)>, // hits per use of the move with their chance, one for most moves
+}
+
+impl DamageRolls {
+    // Average damage of a hit without a crit, every roll being equally likely
+    pub fn get_mean_roll(&self) -> f64 {
+        self.rolls.iter().map(|damage| *damage as f64).sum::<f64>() / NUM_DAMAGE_ROLLS as f64
+    }
+
+    // Every damage a hit can deal with its probability, crits included
+    pub fn get_distribution(&self) -> Vec<(u32, f64)> {
+        let mut distribution: Vec<(u32, f64)> = vec![];
+        let rolls = self
+            .rolls
+            .iter()
+            .map(|damage| (*damage, (1.0 - self.crit_chance) / NUM_DAMAGE_ROLLS as f64))
+            .chain(
+                self.crit_rolls
+                    .iter()
+                    .map(|damage| (*damage, self.crit_chance / NUM_DAMAGE_ROLLS as f64)),
+            );
+        for (damage, probability) in rolls {
+            if probability <= 0.0 {
+                continue;
+            }
+            match distribution.iter_mut().find(|(other, _)| *other == damage) {
+                Some((_, total)) => *total += probability,
+                None => distribution.push((damage, probability)),
+            }
+        }
+        distribution
+    }
+}
+
+// The state of one side going into the calc
+#[derive(Clone, Default, TypedBuilder)]
+pub struct CalcSide {
+    #[builder(default)]
+    pub hp: Option<u16>, // full hp when None
+    #[builder(default)]
+    pub status: Option<Status>,
+    #[builder(default)]
+    pub boosts: Vec<(BoostableStat, i8)>,
+    #[builder(default)]
+    pub terastallized: bool,
+    #[builder(default)]
+    pub reflect: bool,
+    #[builder(default)]
+    pub light_screen: bool,
+}
+
+#[derive(Clone, TypedBuilder)]
+pub struct CalcField {
+    #[builder(default)]
+    pub generation: Option<Generation>, // the battle default when None
+    #[builder(default)]
+    pub weather: Option<Weather>,
+    #[builder(default)]
+    pub attacker_side: CalcSide,
+    #[builder(default)]
+    pub defender_side: CalcSide,
+    #[builder(default = 4)]
+    pub max_uses: usize, // uses of the move to work out KO chances for
+}
+
+impl Default for CalcField {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DamageCalc {
+    pub damage_rolls: DamageRolls,
+    pub defender_hp: u16,
+    pub defender_max_hp: u16,
+    pub percent_range: (f64, f64), // of the defender's max hp, without a crit
+    pub crit_percent_range: (f64, f64),
+    pub recoil_range: Option<(u32, u32)>, // hp the attacker loses per hit, without a crit
+    pub drain_range: Option<(u32, u32)>,
+    pub attacker_residuals: Vec<i32>, // hp lost at the end of each turn, negative for recovery
+    pub defender_residuals: Vec<i32>,
+    pub ko_chances: Vec<f64>, // chance the defender has fainted after n + 1 uses of the move
+}
+
+// Calcs the attacker using the move on the defender, played through the battle's
+//  queries so abilities, items, statuses and the field all apply. The attacker is given
+//  the move if it doesn't know it. Rolls and ranges are per hit, while KO chances
+//  count every hit of a multi-hit move. None for status moves
+pub fn calculate(
+    attacker: &Pokemon,
+    defender: &Pokemon,
+    move_name: MoveName,
+    field: &CalcField,
+) -> Option<DamageCalc> {
+    let mut attacker = attacker.clone();
+    let move_idx = match attacker.moves.iter().position(|name| *name == move_name) {
+        Some(idx) => idx,
+        None => {
+            let idx = attacker
+                .moves
+                .iter()
+                .position(|name| *name == MoveName::Empty)
+                .unwrap_or(0);
+            attacker.moves[idx] = move_name;
+            idx
+        }
+    };
+
+    let mut battle = Battle::new(vec![attacker], vec![defender.clone()]);
+    battle.set_seed(0);
+    if let Some(generation) = field.generation {
+        battle.set_generation(generation);
+    }
+    battle.start();
+    set_up_field(&mut battle, field);
+
+    let damage_rolls = battle.get_damage_rolls(
+        true,
+        0,
+        &Action::Move(MoveSlot::from_index(move_idx), MoveModifier::None),
+    )?;
+    let (attacker_residuals, defender_residuals) = get_residuals(&battle, field.max_uses);
+
+    let attacker = &battle.battle_state().get_active_pokemon(true, 0).pokemon;
+    let defender = &battle.battle_state().get_active_pokemon(false, 0).pokemon;
+    let pokemove = move_dex::get_move_data(&move_name);
+    let get_percent = |damage: u32| damage as f64 * 100.0 / defender.max_hp as f64;
+    let min_damage = damage_rolls.rolls[0].min(defender.hp as u32);
+    let max_damage = damage_rolls.rolls[NUM_DAMAGE_ROLLS - 1].min(defender.hp as u32);
+
+    let ko_chances = get_ko_chances(
+        &damage_rolls,
+        (attacker.hp, attacker.max_hp),
+        (defender.hp, defender.max_hp),
+        pokemove.recoil,
+        pokemove.drain,
+        &attacker_residuals,
+        &defender_residuals,
+    );
+
+    Some(DamageCalc {
+        percent_range: (
+            get_percent(damage_rolls.rolls[0]),
+            get_percent(damage_rolls.rolls[NUM_DAMAGE_ROLLS - 1]),
+        ),
+        crit_percent_range: (
+            get_percent(damage_rolls.crit_rolls[0]),
+            get_percent(damage_rolls.crit_rolls[NUM_DAMAGE_ROLLS - 1]),
+        ),
+        recoil_range: pokemove.recoil.map(|percent| {
+            (
+                get_recoil(min_damage, percent),
+                get_recoil(max_damage, percent),
+            )
+        }),
+        drain_range: pokemove.drain.map(|percent| {
+            (
+                get_drain(min_damage, percent),
+                get_drain(max_damage, percent),
+            )
+        }),
+        defender_hp: defender.hp,
+        defender_max_hp: defender.max_hp,
+        damage_rolls,
+        attacker_residuals,
+        defender_residuals,
+        ko_chances,
+    })
+}
+
+fn set_up_field(battle: &mut Battle, field: &CalcField) {
+    let mut battle_context = battle.battle_context();
+    if let Some(weather) = field.weather {
+        battle_context.battle_state.weather = Some(weather);
+        battle_context.battle_state.weather_turns = 5;
+    }
+
+    for (trainer, side) in [(true, &field.attacker_side), (false, &field.defender_side)] {
+        if let Some(status) = side.status {
+            BattleEngine::apply_status(&mut battle_context, trainer, 0, status);
+        }
+        if side.terastallized {
+            BattleEngine::terastallize(&mut battle_context, trainer, 0);
+        }
+        if side.reflect {
+            BattleEngine::set_side_condition(&mut battle_context, trainer, SideCondition::Reflect);
+        }
+        if side.light_screen {
+            BattleEngine::set_side_condition(
+                &mut battle_context,
+                trainer,
+                SideCondition::LightScreen,
+            );
+        }
+
+        let pokemon = battle_context
+            .battle_state
+            .get_active_pokemon_mut(trainer, 0);
+        for (stat, amount) in side.boosts.iter() {
+            pokemon.modify_boost(*stat, *amount);
+        }
+        if let Some(hp) = side.hp {
+            pokemon.pokemon.hp = hp.clamp(1, pokemon.pokemon.max_hp);
+        }
+    }
+}
+
+// HP each side loses at the end of each turn, played on a copy of the battle. Both
+//  pokemon are put at half hp before each turn, so recovery isn't cut off at max hp and
+//  residuals rarely make them faint
+fn get_residuals(battle: &Battle, num_turns: usize) -> (Vec<i32>, Vec<i32>) {
+    let mut battle = battle.clone();
+    let mut residuals = (vec![], vec![]);
+    for _ in 0..num_turns {
+        for trainer in [true, false] {
+            let pokemon = &mut battle
+                .battle_context()
+                .battle_state
+                .get_active_pokemon_mut(trainer, 0)
+                .pokemon;
+            pokemon.hp = pokemon.max_hp.div_ceil(2);
+        }
+
+        let hp_lost = battle.process_end_of_turn();
+        for ((trainer, slot), lost) in hp_lost {
+            match (trainer, slot) {
+                (true, 0) => residuals.0.push(lost),
+                (false, 0) => residuals.1.push(lost),
+                _ => {}
+            }
+        }
+    }
+    residuals
+}
+
+// Chance the defender has fainted after each use of the move, from damage or residuals.
+//  Every hit of a use is played before the turn's residuals. Recoil and residuals can make
+//  the attacker faint first, ending the uses
+fn get_ko_chances(
+    damage_rolls: &DamageRolls,
+    attacker_hp: (u16, u16),
+    defender_hp: (u16, u16),
+    recoil: Option<u8>,
+    drain: Option<u8>,
+    attacker_residuals: &[i32],
+    defender_residuals: &[i32],
+) -> Vec<f64> {
+    let hit_distribution = damage_rolls.get_distribution();
+    let hit_chances = &damage_rolls.hit_chances;
+    let max_hits = hit_chances.iter().map(|(hits, _)| *hits).max().unwrap_or(1);
+
+    // (defender hp, attacker hp) of the battles still going, with their probability
+    let mut states: HashMap<(u16, u16), f64> =
+        HashMap::from([((defender_hp.0, attacker_hp.0), 1.0)]);
+    let mut ko_chance = 0.0;
+    let mut ko_chances = vec![];
+    for (attacker_residual, defender_residual) in attacker_residuals.iter().zip(defender_residuals)
+    {
+        // the hits of a multi-hit move follow on from each other, so the states after
+        //  n hits are kept for moves that hit more than n times
+        let mut hit_states = states;
+        let mut used_states: HashMap<(u16, u16), f64> = HashMap::new();
+        for hits in 1..=max_hits {
+            let mut next_states = HashMap::new();
+            for ((defender, attacker), probability) in hit_states {
+                for (damage, hit_probability) in hit_distribution.iter() {
+                    let probability = probability * hit_probability;
+                    let dealt = (*damage).min(defender as u32);
+                    let defender = defender - dealt as u16;
+                    if defender == 0 {
+                        ko_chance += probability;
+                        continue;
+                    }
+
+                    let mut attacker = attacker;
+                    if let Some(percent) = recoil {
+                        attacker = attacker.saturating_sub(get_recoil(dealt, percent) as u16);
+                    }
+                    if let Some(percent) = drain {
+                        attacker = apply_residual(
+                            attacker,
+                            attacker_hp.1,
+                            -(get_drain(dealt, percent) as i32),
+                        );
+                    }
+                    if attacker == 0 {
+                        continue;
+                    }
+                    *next_states.entry((defender, attacker)).or_insert(0.0) += probability;
+                }
+            }
+
+            // the share of uses that stop at this many hits, out of those that got this far
+            let remaining: f64 = hit_chances
+                .iter()
+                .filter(|(other, _)| *other >= hits)
+                .map(|(_, chance)| chance)
+                .sum();
+            let stopping = hit_chances
+                .iter()
+                .find(|(other, _)| *other == hits)
+                .map_or(0.0, |(_, chance)| *chance);
+            let stop_share = if remaining > 0.0 {
+                stopping / remaining
+            } else {
+                1.0
+            };
+            hit_states = HashMap::new();
+            for (state, probability) in next_states {
+                *used_states.entry(state).or_insert(0.0) += probability * stop_share;
+                if stop_share < 1.0 {
+                    hit_states.insert(state, probability * (1.0 - stop_share));
+                }
+            }
+        }
+
+        let mut next_states = HashMap::new();
+        for ((defender, attacker), probability) in used_states {
+            let defender = apply_residual(defender, defender_hp.1, *defender_residual);
+            if defender == 0 {
+                ko_chance += probability;
+                continue;
+            }
+            let attacker = apply_residual(attacker, attacker_hp.1, *attacker_residual);
+            if attacker == 0 {
+                continue;
+            }
+            *next_states.entry((defender, attacker)).or_insert(0.0) += probability;
+        }
+        states = next_states;
+        ko_chances.push(ko_chance.min(1.0));
+    }
+    ko_chances
+}
+
+fn apply_residual(hp: u16, max_hp: u16, lost: i32) -> u16 {
+    (hp as i32 - lost).clamp(0, max_hp as i32) as u16
+}
+
+// Same rounding as the engine's recoil and drain
+fn get_recoil(damage_dealt: u32, percent: u8) -> u32 {
+    damage_dealt * percent as u32 / 100
+}
+
+fn get_drain(damage_dealt: u32, percent: u8) -> u32 {
+    (damage_dealt * percent as u32 / 100).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        battle::test_utils, core::status::volatile_status::VolatileStatus, showdown::text,
+    };
+
+    fn get_pokemon(set: &str) -> Pokemon {
+        text::parse_team(set).unwrap().remove(0)
+    }
+
+    #[test]
+    fn residuals_include_recovery() {
+        let mut battle = test_utils::get_battle(
+            "Venusaur\nAbility: Overgrow\n- Giga Drain",
+            "Snorlax\nAbility: Thick Fat\n- Tackle",
+            0,
+        );
+        let battle_context = battle.battle_context();
+        let handler = battle_context
+            .battle_state
+            .get_active_pokemon_mut(false, 0)
+            .add_volatile_status(VolatileStatus::LeechSeed)
+            .clone();
+        battle_context.event_registry.add_handler(handler.clone());
+        battle_context.query_bus.registry.add_handler(handler);
+
+        let seed_damage = battle
+            .battle_state()
+            .get_active_pokemon(false, 0)
+            .pokemon
+            .max_hp as i32
+            / 8;
+        let (attacker_residuals, defender_residuals) = get_residuals(&battle, 2);
+        assert_eq!(attacker_residuals, vec![-seed_damage; 2]);
+        assert_eq!(defender_residuals, vec![seed_damage; 2]);
+    }
+
+    #[test]
+    fn ko_chances_start_from_the_given_hp() {
+        let attacker = get_pokemon("Garchomp\nAbility: Sand Force\n- Earthquake");
+        let defender = get_pokemon("Snorlax\nAbility: Thick Fat\n- Tackle");
+
+        let calc = calculate(
+            &attacker,
+            &defender,
+            MoveName::Earthquake,
+            &CalcField::default(),
+        )
+        .unwrap();
+        assert!(calc.ko_chances[0] < 1.0);
+        assert_eq!(calc.defender_hp, calc.defender_max_hp);
+
+        let field = CalcField::builder()
+            .defender_side(CalcSide::builder().hp(Some(1)).build())
+            .build();
+        let calc = calculate(&attacker, &defender, MoveName::Earthquake, &field).unwrap();
+        assert_eq!(calc.defender_hp, 1);
+        assert!(
+            calc.ko_chances
+                .iter()
+                .all(|chance| (chance - 1.0).abs() < 1e-9)
+        );
+    }
+
+    #[test]
+    fn burn_residuals_count_towards_ko_chances() {
+        let attacker = get_pokemon("Snorlax\nAbility: Thick Fat\n- Tackle");
+        let defender = get_pokemon("Snorlax\nAbility: Thick Fat\n- Tackle");
+        let field = CalcField::builder()
+            .defender_side(CalcSide::builder().status(Some(Status::Burn)).build())
+            .build();
+        let calc = calculate(&attacker, &defender, MoveName::Tackle, &field).unwrap();
+        assert_eq!(
+            calc.defender_residuals,
+            vec![calc.defender_max_hp as i32 / 16; 4]
+        );
+
+        let unburned = calculate(
+            &attacker,
+            &defender,
+            MoveName::Tackle,
+            &CalcField::default(),
+        )
+        .unwrap();
+        assert!(calc.ko_chances[3] >= unburned.ko_chances[3]);
+    }
+
+    #[test]
+    fn multi_hit_ko_chances_count_every_hit_of_a_use() {
+        let attacker = get_pokemon("Ferrothorn\nAbility: Technician\n- Bullet Seed");
+        let defender = get_pokemon("Snorlax\nAbility: Thick Fat\n- Tackle");
+        let calc = calculate(
+            &attacker,
+            &defender,
+            MoveName::BulletSeed,
+            &CalcField::default(),
+        )
+        .unwrap();
+        let hit_chances = &calc.damage_rolls.hit_chances;
+        assert_eq!(
+            hit_chances
+                .iter()
+                .map(|(hits, _)| *hits)
+                .collect::<Vec<u8>>(),
+            vec![2, 3, 4, 5]
+        );
+        assert!((hit_chances.iter().map(|(_, chance)| chance).sum::<f64>() - 1.0).abs() < 1e-9);
+
+        // no single hit can KO, but every use hits at least twice
+        let crit_rolls = calc.damage_rolls.crit_rolls;
+        let hp = crit_rolls[NUM_DAMAGE_ROLLS - 1] as u16 + 1;
+        assert!(2 * calc.damage_rolls.rolls[0] >= hp as u32);
+        let field = CalcField::builder()
+            .defender_side(CalcSide::builder().hp(Some(hp)).build())
+            .build();
+        let calc = calculate(&attacker, &defender, MoveName::BulletSeed, &field).unwrap();
+        assert!((calc.ko_chances[0] - 1.0).abs() < 1e-9);
+    }
+}
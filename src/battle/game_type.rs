@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameType {
+    Singles,
+    Doubles,
+}
+
+impl GameType {
+    // number of pokemon each side has on the field at once
+    pub fn active_slots(&self) -> usize {
+        match self {
+            GameType::Singles => 1,
+            GameType::Doubles => 2,
+        }
+    }
+}
@@ -4,8 +4,16 @@ pub mod battle_context;
 pub mod battle_engine;
 pub mod battle_input;
 pub mod battle_request;
+pub mod damage_calculator;
+pub mod game_type;
+pub mod move_lock;
 pub mod pokemon_battle_instance;
+pub mod scripted_rolls;
+pub mod side_observation;
 pub mod state;
 pub mod static_battle_handler;
+pub mod team_preview_error;
+#[cfg(test)]
+pub mod test_utils;
 pub mod turn_state;
 pub mod valid_actions;
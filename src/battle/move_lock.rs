@@ -0,0 +1,23 @@
+use serde::Serialize;
+
+use crate::core::pokemove::move_name::MoveName;
+
+// Locks that force a pokemon's next action, and prevent it from switching
+#[derive(Clone, Copy, Serialize, PartialEq, Eq, Debug)]
+pub enum MoveLock {
+    Charging(MoveName),
+    Recharging,
+    Rampage(MoveName, u8), // turns remaining after the current one
+    Rollout(MoveName, u8), // consecutive hits so far
+}
+
+impl MoveLock {
+    pub fn locked_move(&self) -> Option<MoveName> {
+        match self {
+            MoveLock::Charging(move_name) => Some(*move_name),
+            MoveLock::Recharging => None,
+            MoveLock::Rampage(move_name, _) => Some(*move_name),
+            MoveLock::Rollout(move_name, _) => Some(*move_name),
+        }
+    }
+}
@@ -1,17 +1,23 @@
 use std::{collections::HashMap, sync::Arc};
 
 use enum_map::EnumMap;
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 
 use crate::{
+    battle::move_lock::MoveLock,
     core::{
+        ability::ability::Ability,
         pokemon::{boostable_stat::BoostableStat, pokemon::Pokemon, stat_enum::StatEnum},
-        pokemove::move_name::MoveName,
+        pokemove::{move_category::MoveCategory, move_name::MoveName},
+        poketype::{pokemon_typing::PokemonTyping, poketype::PokeType},
         status::{status::Status, volatile_status::VolatileStatus},
+        util::stat_utils,
     },
     dex::{
         ability::ability_handlers,
         combined_handler::CombinedHandler,
+        item::item_handlers,
+        pokemon::pokedex,
         pokemove::move_dex,
         status::{status_handlers, volatile_status_handlers},
     },
@@ -26,15 +32,35 @@ pub struct PokemonBattleInstance {
     #[serde(skip)]
     pub volatile_statuses: HashMap<VolatileStatus, Arc<dyn CombinedHandler>>,
     pub trainer_side: bool,
+    pub slot: usize, // active slot, only meaningful while this pokemon is active
     pub sleep_turns: u8,
+    pub self_inflicted_sleep: bool, // e.g. Rest, ignored by Sleep Clause
     pub confusion_turns: u8,
     pub badly_poison_turns: u8,
+    pub encore_turns: u8,
+    pub leech_seed_slot: usize, // opposing slot healed by Leech Seed
     #[serde(skip)]
     pub boosts: EnumMap<BoostableStat, i8>,
 
+    pub move_lock: Option<MoveLock>,
+    pub choice_lock: Option<MoveName>,
+    pub encore_move: Option<MoveName>,
+    pub last_move_used: Option<MoveName>,
+    pub queued_move: Option<MoveName>, // move chosen this turn that has not been used yet
+    pub damage_taken: Option<(u32, MoveCategory)>, // last damage taken from a move this turn
+    pub dynamax_turns: u8,             // turns left, 0 when not dynamaxed
+    pub terastallized: bool,           // kept after switching out
+
+    // what the opposing trainer has seen of this pokemon
+    pub revealed_moves: [bool; 4],
+    pub item_revealed: bool,
+    pub ability_revealed: bool,
+
     #[serde(skip)]
     pub ability_handler: Arc<dyn CombinedHandler>,
     #[serde(skip)]
+    pub item_handler: Option<Arc<dyn CombinedHandler>>,
+    #[serde(skip)]
     pub status_handler: Option<Arc<dyn CombinedHandler>>,
 
     pub pp: [u8; 4],
@@ -43,29 +69,88 @@ pub struct PokemonBattleInstance {
 impl PokemonBattleInstance {
     pub fn new(pokemon: Pokemon, trainer_side: bool) -> Self {
         let ability = pokemon.ability;
+        let item = pokemon.item;
         let moves = pokemon.moves;
 
         Self {
             pokemon,
             trainer_side,
+            slot: 0,
             status: None,
             sleep_turns: 0,
+            self_inflicted_sleep: false,
             confusion_turns: 0,
             badly_poison_turns: 0,
+            encore_turns: 0,
+            leech_seed_slot: 0,
             volatile_statuses: HashMap::new(),
             boosts: EnumMap::default(),
 
-            ability_handler: ability_handlers::get_ability_handler(&ability, trainer_side),
+            move_lock: None,
+            choice_lock: None,
+            encore_move: None,
+            last_move_used: None,
+            queued_move: None,
+            damage_taken: None,
+            dynamax_turns: 0,
+            terastallized: false,
+
+            revealed_moves: [false; 4],
+            item_revealed: false,
+            ability_revealed: false,
+
+            ability_handler: ability_handlers::get_ability_handler(&ability, trainer_side, 0),
+            item_handler: item
+                .and_then(|item| item_handlers::get_item_handler(&item, trainer_side, 0)),
             status_handler: None,
             pp: moves.map(|move_name| move_dex::get_move_pp(&move_name)),
         }
     }
 
+    // Handlers are tied to an active slot, so they are rebuilt when switching in
+    pub fn set_slot(&mut self, slot: usize) {
+        self.slot = slot;
+        self.ability_handler =
+            ability_handlers::get_ability_handler(&self.pokemon.ability, self.trainer_side, slot);
+        self.item_handler = self
+            .pokemon
+            .item
+            .and_then(|item| item_handlers::get_item_handler(&item, self.trainer_side, slot));
+        self.status_handler = self
+            .status
+            .filter(|status| *status != Status::Faint)
+            .map(|status| status_handlers::get_status_handler(status, self.trainer_side, slot));
+    }
+
+    // Keeps only what the opposing trainer has seen: unrevealed items are dropped, an
+    //  unrevealed ability does nothing and stats assume a neutral spread. The caller
+    //  swaps the registered handlers
+    pub fn hide_unrevealed_set(&mut self) {
+        if !self.item_revealed {
+            self.pokemon.item = None;
+            self.item_handler = None;
+            self.choice_lock = None;
+        }
+        if !self.ability_revealed {
+            self.ability_handler = ability_handlers::get_unrevealed_ability_handler();
+        }
+        self.pokemon
+            .set_neutral_spread(pokedex::get_base_pokemon(&self.pokemon.species));
+    }
+
+    // Used by mega evolution, the caller swaps the registered handler
+    pub fn set_ability(&mut self, ability: Ability) {
+        self.pokemon.ability = ability;
+        self.ability_handler =
+            ability_handlers::get_ability_handler(&ability, self.trainer_side, self.slot);
+    }
+
     pub fn set_status(&mut self, status: Status) {
         self.status = Some(status);
         self.status_handler = Some(status_handlers::get_status_handler(
             status,
             self.trainer_side,
+            self.slot,
         ));
     }
 
@@ -80,6 +165,7 @@ impl PokemonBattleInstance {
 
     pub fn clear_status(&mut self) {
         self.status = None;
+        self.self_inflicted_sleep = false;
         self.status_handler = None;
     }
 
@@ -88,20 +174,24 @@ impl PokemonBattleInstance {
     }
 
     pub fn is_fainted(&self) -> bool {
-        match self.status {
-            Some(Status::Faint) => true,
-            _ => false,
-        }
+        matches!(self.status, Some(Status::Faint))
     }
 
     pub fn add_volatile_status(&mut self, status: VolatileStatus) -> &Arc<dyn CombinedHandler> {
-        let handler =
-            volatile_status_handlers::get_volatile_status_handler(status, self.trainer_side);
+        let handler = volatile_status_handlers::get_volatile_status_handler(
+            status,
+            self.trainer_side,
+            self.slot,
+        );
         self.volatile_statuses.insert(status, handler);
         self.volatile_statuses.get(&status).unwrap()
     }
 
     pub fn remove_volatile_status(&mut self, status: &VolatileStatus) -> Arc<dyn CombinedHandler> {
+        if *status == VolatileStatus::Encore {
+            self.encore_move = None;
+            self.encore_turns = 0;
+        }
         self.volatile_statuses.remove(status).unwrap()
     }
 
@@ -112,11 +202,100 @@ impl PokemonBattleInstance {
     }
 
     pub fn reset(&mut self) {
+        if self.is_dynamaxed() {
+            self.end_dynamax();
+        }
         self.volatile_statuses.clear();
         self.badly_poison_turns = 0;
         self.confusion_turns = 0;
+        self.encore_turns = 0;
 
         self.boosts.clear();
+
+        self.move_lock = None;
+        self.choice_lock = None;
+        self.encore_move = None;
+        self.last_move_used = None;
+        self.queued_move = None;
+        self.damage_taken = None;
+    }
+
+    // Typing used when this pokemon is hit, accounting for temporary type changes
+    pub fn get_typing(&self) -> PokemonTyping {
+        // a Stellar tera keeps the original types defensively
+        if self.terastallized && self.pokemon.tera_type != PokeType::Stellar {
+            return PokemonTyping::MonoType(self.pokemon.tera_type);
+        }
+
+        let typing = self.pokemon.typing.clone();
+        if !self.volatile_statuses.contains_key(&VolatileStatus::Roost) {
+            return typing;
+        }
+
+        // Roost removes the Flying type until the end of the turn
+        match typing {
+            PokemonTyping::DualType(PokeType::Flying, other)
+            | PokemonTyping::DualType(other, PokeType::Flying) => PokemonTyping::MonoType(other),
+            PokemonTyping::MonoType(PokeType::Flying) => PokemonTyping::MonoType(PokeType::Normal),
+            _ => typing,
+        }
+    }
+
+    // STAB multiplier before abilities, None if the move doesn't get STAB
+    pub fn get_stab_mult(&self, move_type: PokeType) -> Option<f32> {
+        if move_type == PokeType::Typeless {
+            return None;
+        }
+
+        let original_type = self.pokemon.typing.has_type(move_type);
+        if !self.terastallized {
+            return original_type.then_some(1.5);
+        }
+
+        match self.pokemon.tera_type {
+            // Stellar only boosts each type once per battle in game, which isn't tracked here
+            PokeType::Stellar if original_type => Some(2.0),
+            PokeType::Stellar => Some(1.2),
+            tera_type if tera_type == move_type && original_type => Some(2.0),
+            tera_type if tera_type == move_type => Some(1.5),
+            _ => original_type.then_some(1.5),
+        }
+    }
+
+    // Stat value with stat stages applied, but no other modifiers
+    pub fn get_boosted_stat(&self, stat: StatEnum) -> f32 {
+        self.pokemon.get_stat_value(stat) as f32
+            * stat_utils::get_stat_boost_to_multiplier(self.boosts[BoostableStat::Stat(stat)])
+    }
+
+    // The only move this pokemon may select, if Encore or a choice item restricts it
+    pub fn get_move_restriction(&self) -> Option<MoveName> {
+        // choice items don't restrict a dynamaxed pokemon
+        if self.is_dynamaxed() {
+            return self.encore_move;
+        }
+        self.encore_move.or(self.choice_lock)
+    }
+
+    pub fn is_dynamaxed(&self) -> bool {
+        self.dynamax_turns > 0
+    }
+
+    // Dynamax doubles current and max hp for three turns
+    pub fn start_dynamax(&mut self) {
+        self.dynamax_turns = 3;
+        self.pokemon.max_hp *= 2;
+        self.pokemon.hp *= 2;
+    }
+
+    pub fn end_dynamax(&mut self) {
+        self.dynamax_turns = 0;
+        self.pokemon.max_hp /= 2;
+        self.pokemon.hp = self.pokemon.hp.div_ceil(2);
+    }
+
+    pub fn has_choice_item(&self) -> bool {
+        self.pokemon.item.is_some_and(|item| item.is_choice())
     }
 
     pub fn get_all_event_handlers(&self) -> Vec<Arc<dyn EventHandler>> {
@@ -124,6 +303,10 @@ impl PokemonBattleInstance {
 
         handlers.push(self.ability_handler.clone());
 
+        if let Some(item_handler) = &self.item_handler {
+            handlers.push(item_handler.clone());
+        }
+
         if let Some(status_handler) = &self.status_handler {
             handlers.push(status_handler.clone());
         }
@@ -140,6 +323,10 @@ impl PokemonBattleInstance {
 
         handlers.push(self.ability_handler.clone());
 
+        if let Some(item_handler) = &self.item_handler {
+            handlers.push(item_handler.clone());
+        }
+
         if let Some(status_handler) = &self.status_handler {
             handlers.push(status_handler.clone());
         }
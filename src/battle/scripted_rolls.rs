@@ -0,0 +1,49 @@
+// Takes the place of the rng to walk through every outcome of a battle's random rolls.
+//  Each roll takes the outcome the script gives it, or the first outcome once the script
+//  runs out, and records the probability of every outcome it could have had
+#[derive(Clone)]
+pub struct ScriptedRolls {
+    script: Vec<usize>,
+    taken: Vec<(usize, Vec<f64>)>, // outcome taken and the probability of each outcome, per roll
+}
+
+impl ScriptedRolls {
+    pub fn new(script: Vec<usize>) -> Self {
+        Self {
+            script,
+            taken: vec![],
+        }
+    }
+
+    pub fn next_outcome(&mut self, probabilities: Vec<f64>) -> usize {
+        let outcome = self.script.get(self.taken.len()).copied().unwrap_or(0);
+        self.taken.push((outcome, probabilities));
+        outcome
+    }
+
+    // Chance of every roll so far coming out the way it did
+    pub fn probability(&self) -> f64 {
+        self.taken
+            .iter()
+            .map(|(outcome, probabilities)| probabilities[*outcome])
+            .product()
+    }
+
+    // Scripts for the outcomes this run passed over, each branching off at one of the
+    //  rolls made after the script ran out. Following them all visits every outcome once
+    pub fn get_unexplored_scripts(&self) -> Vec<Vec<usize>> {
+        let mut scripts = vec![];
+        for roll in self.script.len()..self.taken.len() {
+            let prefix: Vec<usize> = self.taken[..roll]
+                .iter()
+                .map(|(outcome, _)| *outcome)
+                .collect();
+            for outcome in 1..self.taken[roll].1.len() {
+                let mut script = prefix.clone();
+                script.push(outcome);
+                scripts.push(script);
+            }
+        }
+        scripts
+    }
+}
@@ -0,0 +1,251 @@
+use enum_map::Enum;
+use serde::Serialize;
+
+use crate::{
+    battle::{
+        pokemon_battle_instance::PokemonBattleInstance,
+        state::{BattleState, SingleSideState},
+    },
+    core::{
+        ability::ability::Ability,
+        field::weather::Weather,
+        item::item::Item,
+        pokemon::{boostable_stat::BoostableStat, pokemon::Pokemon, species::Species},
+        pokemove::move_name::MoveName,
+        poketype::{pokemon_typing::PokemonTyping, poketype::PokeType},
+        side_condition::side_condition::SideCondition,
+        status::{status::Status, volatile_status::VolatileStatus},
+    },
+};
+
+// One trainer's view of the battle, holding only what that trainer could know. Their
+//  own side is shown in full, the opposing side only as far as it has been revealed
+#[derive(Clone, Serialize)]
+pub struct SideObservation {
+    pub is_trainer_1: bool,
+    pub turn: u32,
+    pub weather: Option<Weather>,
+    pub weather_turns: u8,
+    pub own_side: SideView,
+    pub opponent_side: SideView,
+}
+
+#[derive(Clone, Serialize)]
+pub struct SideView {
+    pub party_size: usize,
+    pub team_preview: Vec<Species>, // empty without team preview
+    pub pokemon: Vec<PokemonView>,  // own side in party order, opposing side in the order seen
+    pub spikes_layers: u8,
+    pub stealth_rock: bool,
+    pub reflect_turns: u8,
+    pub light_screen_turns: u8,
+    pub mega_used: bool,
+    pub z_move_used: bool,
+    pub dynamax_used: bool,
+    pub tera_used: bool,
+}
+
+#[derive(Clone, Serialize)]
+pub struct PokemonView {
+    pub party_idx: Option<usize>, // own side only
+    pub species: Species,
+    pub level: u8,
+    pub typing: PokemonTyping, // current types, after tera and Roost
+    pub hp_percent: u8,        // only 0 once fainted
+    pub status: Option<Status>,
+    pub volatile_statuses: Vec<VolatileStatus>,
+    pub boosts: [i8; BoostableStat::LENGTH],
+    pub is_active: bool,
+    pub moves: [Option<MoveName>; 4], // None for moves that haven't been used yet
+    pub pp: [Option<u8>; 4],          // pp left, counted from the uses seen
+    pub item: Option<Option<Item>>,   // None until revealed, Some(None) once known to be gone
+    pub ability: Option<Ability>,     // None until revealed
+    pub tera_type: Option<PokeType>,  // None until it terastallizes
+    pub terastallized: bool,
+    pub dynamax_turns: u8,
+    pub set: Option<Pokemon>, // the full set with exact hp and stats, own side only
+}
+
+impl SideObservation {
+    pub fn new(battle_state: &BattleState, is_trainer_1: bool) -> Self {
+        Self {
+            is_trainer_1,
+            turn: battle_state.turn,
+            weather: battle_state.weather,
+            weather_turns: battle_state.weather_turns,
+            own_side: SideView::new(battle_state.get_side(is_trainer_1), true),
+            opponent_side: SideView::new(battle_state.get_side(!is_trainer_1), false),
+        }
+    }
+}
+
+impl SideView {
+    fn new(side: &SingleSideState, is_own_side: bool) -> Self {
+        let pokemon = if is_own_side {
+            (0..side.num_pokemon())
+                .map(|idx| PokemonView::new_own(side, idx))
+                .collect()
+        } else {
+            side.get_seen_pokemon()
+                .iter()
+                .map(|idx| PokemonView::new_opponent(side, *idx))
+                .collect()
+        };
+
+        Self {
+            party_size: side.num_pokemon(),
+            team_preview: side.team_preview.clone(),
+            pokemon,
+            spikes_layers: side.spikes_layers,
+            stealth_rock: side.has_side_condition(&SideCondition::StealthRock),
+            reflect_turns: side.reflect_turns,
+            light_screen_turns: side.light_screen_turns,
+            mega_used: side.mega_used,
+            z_move_used: side.z_move_used,
+            dynamax_used: side.dynamax_used,
+            tera_used: side.tera_used,
+        }
+    }
+}
+
+impl PokemonView {
+    fn new_own(side: &SingleSideState, idx: usize) -> Self {
+        let pokemon = side.get_pokemon(idx);
+        Self {
+            party_idx: Some(idx),
+            moves: pokemon.pokemon.moves.map(Some),
+            pp: pokemon.pp.map(Some),
+            item: Some(pokemon.pokemon.item),
+            ability: Some(pokemon.pokemon.ability),
+            tera_type: Some(pokemon.pokemon.tera_type),
+            set: Some(pokemon.pokemon.clone()),
+            ..Self::new_public(pokemon, side.is_active(idx))
+        }
+    }
+
+    fn new_opponent(side: &SingleSideState, idx: usize) -> Self {
+        let pokemon = side.get_pokemon(idx);
+        let revealed = pokemon.revealed_moves;
+        Self {
+            moves: std::array::from_fn(|move_idx| {
+                revealed[move_idx].then_some(pokemon.pokemon.moves[move_idx])
+            }),
+            pp: std::array::from_fn(|move_idx| revealed[move_idx].then_some(pokemon.pp[move_idx])),
+            item: pokemon.item_revealed.then_some(pokemon.pokemon.item),
+            ability: pokemon.ability_revealed.then_some(pokemon.pokemon.ability),
+            tera_type: pokemon.terastallized.then_some(pokemon.pokemon.tera_type),
+            ..Self::new_public(pokemon, side.is_active(idx))
+        }
+    }
+
+    // What either trainer can see of a pokemon that has been sent out
+    fn new_public(pokemon: &PokemonBattleInstance, is_active: bool) -> Self {
+        let mut boosts = [0; BoostableStat::LENGTH];
+        for (stat, boost) in pokemon.boosts.iter() {
+            boosts[stat.into_usize()] = *boost;
+        }
+
+        Self {
+            party_idx: None,
+            species: pokemon.pokemon.species,
+            level: pokemon.pokemon.level,
+            typing: pokemon.get_typing(),
+            hp_percent: get_hp_percent(pokemon.pokemon.hp, pokemon.pokemon.max_hp),
+            status: pokemon.status,
+            volatile_statuses: pokemon.volatile_statuses.keys().copied().collect(),
+            boosts,
+            is_active,
+            moves: [None; 4],
+            pp: [None; 4],
+            item: None,
+            ability: None,
+            tera_type: None,
+            terastallized: pokemon.terastallized,
+            dynamax_turns: pokemon.dynamax_turns,
+            set: None,
+        }
+    }
+}
+
+// Rounded up like the health bar, so only a full pokemon shows 100 and only a fainted
+//  one shows 0
+fn get_hp_percent(hp: u16, max_hp: u16) -> u8 {
+    if hp == max_hp {
+        return 100;
+    }
+    (hp as u32 * 100).div_ceil(max_hp as u32).min(99) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::{
+        actions::{Action, MoveModifier, MoveSlot},
+        battle::Battle,
+        test_utils,
+    };
+
+    fn get_opponent_view(battle: &Battle, is_trainer_1: bool) -> PokemonView {
+        SideObservation::new(battle.battle_state(), is_trainer_1)
+            .opponent_side
+            .pokemon[0]
+            .clone()
+    }
+
+    fn play_turn(battle: &mut Battle) {
+        let action = || Action::Move(MoveSlot::Slot0, MoveModifier::None);
+        let input = test_utils::get_input(battle, action(), action());
+        battle.process_input(input);
+    }
+
+    #[test]
+    fn opponent_sets_are_hidden_until_revealed() {
+        let battle = test_utils::get_battle(
+            "Alakazam @ Choice Band\nAbility: Technician\n- Tackle",
+            "Snorlax\nAbility: Thick Fat\n- Swords Dance",
+            0,
+        );
+        let view = get_opponent_view(&battle, false);
+        assert_eq!(view.species, Species::Alakazam);
+        assert_eq!(view.ability, None);
+        assert_eq!(view.item, None);
+        assert_eq!(view.moves, [None; 4]);
+        assert!(view.set.is_none());
+    }
+
+    // stat and damage modifiers are worked out through queries, which players never see
+    #[test]
+    fn modifiers_taking_effect_stay_hidden() {
+        let mut battle = test_utils::get_battle(
+            "Alakazam @ Choice Scarf\nAbility: Technician\n- Tackle",
+            "Snorlax\nAbility: Thick Fat\n- Swords Dance",
+            0,
+        );
+        play_turn(&mut battle);
+
+        let alakazam = get_opponent_view(&battle, false);
+        assert_eq!(alakazam.ability, None);
+        assert_eq!(alakazam.item, None);
+        assert_eq!(alakazam.moves[0], Some(MoveName::Tackle));
+
+        let mut battle = test_utils::get_battle(
+            "Charizard\nAbility: Blaze\n- Ember",
+            "Snorlax\nAbility: Thick Fat\n- Swords Dance",
+            0,
+        );
+        play_turn(&mut battle);
+        assert_eq!(get_opponent_view(&battle, true).ability, None);
+    }
+
+    #[test]
+    fn knocked_off_items_are_revealed() {
+        let mut battle = test_utils::get_battle(
+            "Alakazam\nAbility: Technician\n- Knock Off",
+            "Snorlax @ Choice Band\nAbility: Thick Fat\n- Swords Dance",
+            0,
+        );
+        assert_eq!(get_opponent_view(&battle, true).item, None);
+        play_turn(&mut battle);
+        assert_eq!(get_opponent_view(&battle, true).item, Some(None));
+    }
+}
@@ -1,29 +1,63 @@
+use std::{collections::HashMap, sync::Arc};
+
 use crate::{
     battle::{
         actions::Action,
+        game_type::GameType,
         pokemon_battle_instance::PokemonBattleInstance,
+        scripted_rolls::ScriptedRolls,
         valid_actions::{ValidActions, ValidMoves, ValidSwitches},
     },
-    core::{pokemon::pokemon::Pokemon, pokemove::move_name::MoveName},
+    core::{
+        field::weather::Weather,
+        mechanics::mechanics_profile::MechanicsProfile,
+        pokemon::{pokemon::Pokemon, species::Species},
+        pokemove::{move_category::MoveCategory, move_name::MoveName},
+        side_condition::side_condition::SideCondition,
+    },
+    dex::{
+        combined_handler::CombinedHandler, pokemon::mega_evolutions, pokemove::move_dex,
+        side_condition::side_condition_handlers,
+    },
 };
 use rand::{Rng, SeedableRng, rngs::StdRng};
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
+
+const MAX_SCRIPTED_OUTCOMES: u32 = 16;
 
 #[derive(Clone, Serialize)]
 pub struct BattleState {
     trainer_1_state: SingleSideState,
     trainer_2_state: SingleSideState,
+    pub weather: Option<Weather>,
+    pub weather_turns: u8,
+    pub game_type: GameType,
+    pub turn: u32, // number of completed turns
+    pub mechanics: MechanicsProfile,
 
     #[serde(skip)]
     rng: StdRng,
+    #[serde(skip)]
+    scripted_rolls: Option<ScriptedRolls>, // replaces the rng while set
 }
 
 impl BattleState {
-    pub fn new(trainer_1_mons: Vec<Pokemon>, trainer_2_mons: Vec<Pokemon>) -> Self {
+    pub fn new(
+        trainer_1_mons: Vec<Pokemon>,
+        trainer_2_mons: Vec<Pokemon>,
+        game_type: GameType,
+    ) -> Self {
+        let active_slots = game_type.active_slots();
         Self {
-            trainer_1_state: SingleSideState::new(trainer_1_mons, true),
-            trainer_2_state: SingleSideState::new(trainer_2_mons, false),
+            trainer_1_state: SingleSideState::new(trainer_1_mons, true, active_slots),
+            trainer_2_state: SingleSideState::new(trainer_2_mons, false, active_slots),
+            game_type,
+            turn: 0,
+            mechanics: MechanicsProfile::default(),
+            weather: None,
+            weather_turns: 0,
             rng: StdRng::from_entropy(),
+            scripted_rolls: None,
         }
     }
 
@@ -35,26 +69,53 @@ impl BattleState {
         }
     }
 
-    pub fn deal_damage(&mut self, trainer_1_src: bool, damage: u32) {
-        let side_state = self.get_side_mut(!trainer_1_src);
-        side_state.take_damage(damage);
+    pub fn get_active_pokemon(&self, trainer_1: bool, slot: usize) -> &PokemonBattleInstance {
+        self.get_side(trainer_1).get_active_pokemon(slot)
     }
 
-    pub fn get_active_pokemon(&self, trainer_1: bool) -> &PokemonBattleInstance {
-        self.get_side(trainer_1).get_active_pokemon()
+    pub fn get_active_pokemon_mut(
+        &mut self,
+        trainer_1: bool,
+        slot: usize,
+    ) -> &mut PokemonBattleInstance {
+        self.get_side_mut(trainer_1).get_active_pokemon_mut(slot)
     }
 
-    pub fn get_active_pokemon_mut(&mut self, trainer_1: bool) -> &mut PokemonBattleInstance {
-        self.get_side_mut(trainer_1).get_active_pokemon_mut()
+    // Every active slot on both sides, trainer 1 first
+    pub fn get_active_positions(&self) -> Vec<(bool, usize)> {
+        [true, false]
+            .into_iter()
+            .flat_map(|trainer_1| {
+                (0..self.get_side(trainer_1).num_active_slots()).map(move |slot| (trainer_1, slot))
+            })
+            .collect()
     }
 
-    pub fn get_move_for_action(&self, trainer_1: bool, action: &Action) -> Option<MoveName> {
-        let pokemon = self.get_active_pokemon(trainer_1);
-        pokemon.pokemon.get_move_for_action(action)
+    // Returns None for switches and for a pokemon that is recharging
+    pub fn get_move_for_action(
+        &self,
+        trainer_1: bool,
+        slot: usize,
+        action: &Action,
+    ) -> Option<MoveName> {
+        let pokemon = self.get_active_pokemon(trainer_1, slot);
+        match action {
+            Action::Struggle => Some(MoveName::Struggle),
+            Action::Locked => pokemon
+                .move_lock
+                .expect("Locked action for pokemon without a move lock")
+                .locked_move(),
+            _ => pokemon.pokemon.get_move_for_action(action),
+        }
     }
 
-    pub fn get_move_for_move_action(&self, trainer_1: bool, action: &Action) -> MoveName {
-        self.get_move_for_action(trainer_1, action)
+    pub fn get_move_for_move_action(
+        &self,
+        trainer_1: bool,
+        slot: usize,
+        action: &Action,
+    ) -> MoveName {
+        self.get_move_for_action(trainer_1, slot, action)
             .expect("Unexpected action for move")
     }
 
@@ -66,56 +127,240 @@ impl BattleState {
         }
     }
 
+    // Makes every random roll from here on reproducible
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    pub fn set_scripted_rolls(&mut self, scripted_rolls: Option<ScriptedRolls>) {
+        self.scripted_rolls = scripted_rolls;
+    }
+
+    pub fn take_scripted_rolls(&mut self) -> Option<ScriptedRolls> {
+        self.scripted_rolls.take()
+    }
+
+    pub fn has_scripted_rolls(&self) -> bool {
+        self.scripted_rolls.is_some()
+    }
+
+    // Takes one of the listed outcomes while rolls are scripted, None otherwise
+    pub fn get_scripted_outcome(&mut self, probabilities: Vec<f64>) -> Option<usize> {
+        self.scripted_rolls
+            .as_mut()
+            .map(|scripted_rolls| scripted_rolls.next_outcome(probabilities))
+    }
+
+    // Identifies the state when merging search outcomes. Serialization skips the maps of
+    //  handlers and the boosts, so they are added to the key separately
+    pub fn get_state_key(&self) -> String {
+        let mut key = serde_json::to_string(self).expect("Battle state should serialize");
+        for side in [&self.trainer_1_state, &self.trainer_2_state] {
+            let mut side_conditions: Vec<u8> = side
+                .side_conditions
+                .keys()
+                .map(|side_condition| *side_condition as u8)
+                .collect();
+            side_conditions.sort();
+            key.push_str(&format!("|{side_conditions:?}"));
+
+            for pokemon in side.pokemon.iter() {
+                let mut volatile_statuses: Vec<u8> = pokemon
+                    .volatile_statuses
+                    .keys()
+                    .map(|volatile_status| *volatile_status as u8)
+                    .collect();
+                volatile_statuses.sort();
+                let boosts: Vec<i8> = pokemon.boosts.values().copied().collect();
+                key.push_str(&format!("|{volatile_statuses:?}{boosts:?}"));
+            }
+        }
+        key
+    }
+
     pub fn get_random_check(&mut self, numerator: u32, denominator: u32) -> bool {
+        if let Some(scripted_rolls) = &mut self.scripted_rolls {
+            // checks that can't fail or can't pass aren't worth branching on
+            if numerator == 0 || numerator >= denominator {
+                return numerator > 0;
+            }
+            let chance = numerator as f64 / denominator as f64;
+            return scripted_rolls.next_outcome(vec![chance, 1.0 - chance]) == 0;
+        }
+
         let rand_num = self.get_rand_num(denominator);
         rand_num < numerator
     }
 
     pub fn get_rand_num(&mut self, upper_bound: u32) -> u32 {
+        // rolls with too many outcomes to list still use the rng
+        if let Some(scripted_rolls) = &mut self.scripted_rolls
+            && upper_bound <= MAX_SCRIPTED_OUTCOMES
+        {
+            let probability = 1.0 / upper_bound as f64;
+            return scripted_rolls.next_outcome(vec![probability; upper_bound as usize]) as u32;
+        }
         self.rng.gen_range(0..upper_bound)
     }
 
     pub fn get_rand_num_inclusive(&mut self, lower_bound: u8, upper_bound: u8) -> u8 {
+        if self.scripted_rolls.is_some() {
+            let range = (upper_bound - lower_bound) as u32 + 1;
+            return lower_bound + self.get_rand_num(range) as u8;
+        }
         self.rng.gen_range(lower_bound..=upper_bound)
     }
 }
 
 #[derive(Clone, Serialize)]
 pub struct SingleSideState {
-    active_pokemon_idx: usize,
+    active_pokemon_idxs: Vec<usize>, // party index of the pokemon in each active slot
     pokemon: Vec<PokemonBattleInstance>,
     trainer_1: bool,
+    seen_pokemon: Vec<usize>, // party indices in the order they were first sent out
+    pub team_preview: Vec<Species>, // species the opponent was shown at team preview
+
+    #[serde(skip)]
+    pub side_conditions: HashMap<SideCondition, Arc<dyn CombinedHandler>>,
+    pub spikes_layers: u8,
+    pub reflect_turns: u8,
+    pub light_screen_turns: u8,
+    // each gimmick can be used once per battle
+    pub mega_used: bool,
+    pub z_move_used: bool,
+    pub dynamax_used: bool,
+    pub tera_used: bool,
 }
 
 impl SingleSideState {
-    pub fn new(pokemon: Vec<Pokemon>, trainer_1: bool) -> Self {
+    pub fn new(pokemon: Vec<Pokemon>, trainer_1: bool, active_slots: usize) -> Self {
         Self {
-            active_pokemon_idx: 0,
+            active_pokemon_idxs: (0..active_slots).collect(),
             pokemon: pokemon
                 .iter()
                 .map(|p| PokemonBattleInstance::new(p.clone(), trainer_1))
                 .collect(),
             trainer_1,
+            seen_pokemon: vec![],
+            team_preview: vec![],
+            side_conditions: HashMap::new(),
+            spikes_layers: 0,
+            reflect_turns: 0,
+            light_screen_turns: 0,
+            mega_used: false,
+            z_move_used: false,
+            dynamax_used: false,
+            tera_used: false,
         }
     }
 
-    pub fn get_active_pokemon(&self) -> &PokemonBattleInstance {
-        &self.pokemon[self.active_pokemon_idx]
+    // Keeps only the chosen pokemon, in the given order
+    pub fn select_team(&mut self, order: &[usize]) {
+        let mut pokemon: Vec<Option<PokemonBattleInstance>> =
+            self.pokemon.drain(..).map(Some).collect();
+        self.pokemon = order
+            .iter()
+            .map(|idx| {
+                pokemon
+                    .get_mut(*idx)
+                    .and_then(Option::take)
+                    .expect("Team order should contain unique party indices")
+            })
+            .collect();
+    }
+
+    pub fn get_active_pokemon_idx(&self, slot: usize) -> usize {
+        self.active_pokemon_idxs[slot]
+    }
+
+    pub fn num_active_slots(&self) -> usize {
+        self.active_pokemon_idxs.len()
+    }
+
+    pub fn get_pokemon(&self, idx: usize) -> &PokemonBattleInstance {
+        &self.pokemon[idx]
     }
 
-    pub fn get_active_pokemon_mut(&mut self) -> &mut PokemonBattleInstance {
-        &mut self.pokemon[self.active_pokemon_idx]
+    pub fn get_pokemon_mut(&mut self, idx: usize) -> &mut PokemonBattleInstance {
+        &mut self.pokemon[idx]
     }
 
-    pub fn set_active_pokemon(&mut self, idx: usize) -> &PokemonBattleInstance {
-        self.active_pokemon_idx = idx;
-        self.get_active_pokemon()
+    pub fn num_pokemon(&self) -> usize {
+        self.pokemon.len()
+    }
+
+    pub fn get_seen_pokemon(&self) -> &[usize] {
+        &self.seen_pokemon
+    }
+
+    pub fn is_active(&self, idx: usize) -> bool {
+        self.active_pokemon_idxs.contains(&idx)
+    }
+
+    pub fn has_side_condition(&self, side_condition: &SideCondition) -> bool {
+        self.side_conditions.contains_key(side_condition)
+    }
+
+    // Returns the handler to register if the condition was newly added
+    pub fn add_side_condition(
+        &mut self,
+        side_condition: SideCondition,
+    ) -> Option<&Arc<dyn CombinedHandler>> {
+        if self.side_conditions.contains_key(&side_condition) {
+            // only Spikes can be stacked, up to 3 layers
+            if side_condition == SideCondition::Spikes {
+                self.spikes_layers = (self.spikes_layers + 1).min(3);
+            }
+            return None;
+        }
+
+        match side_condition {
+            SideCondition::Spikes => self.spikes_layers = 1,
+            SideCondition::Reflect => self.reflect_turns = 5,
+            SideCondition::LightScreen => self.light_screen_turns = 5,
+            SideCondition::StealthRock => {}
+        }
+
+        let handler =
+            side_condition_handlers::get_side_condition_handler(side_condition, self.trainer_1);
+        self.side_conditions.insert(side_condition, handler);
+        self.side_conditions.get(&side_condition)
+    }
+
+    pub fn remove_side_condition(
+        &mut self,
+        side_condition: &SideCondition,
+    ) -> Option<Arc<dyn CombinedHandler>> {
+        match side_condition {
+            SideCondition::Spikes => self.spikes_layers = 0,
+            SideCondition::Reflect => self.reflect_turns = 0,
+            SideCondition::LightScreen => self.light_screen_turns = 0,
+            SideCondition::StealthRock => {}
+        }
+        self.side_conditions.remove(side_condition)
+    }
+
+    pub fn get_active_pokemon(&self, slot: usize) -> &PokemonBattleInstance {
+        &self.pokemon[self.active_pokemon_idxs[slot]]
+    }
+
+    pub fn get_active_pokemon_mut(&mut self, slot: usize) -> &mut PokemonBattleInstance {
+        &mut self.pokemon[self.active_pokemon_idxs[slot]]
+    }
+
+    pub fn set_active_pokemon(&mut self, slot: usize, idx: usize) -> &PokemonBattleInstance {
+        self.active_pokemon_idxs[slot] = idx;
+        self.pokemon[idx].set_slot(slot);
+        if !self.seen_pokemon.contains(&idx) {
+            self.seen_pokemon.push(idx);
+        }
+        self.get_active_pokemon(slot)
     }
 
     // Returns true if the active Pokemon fainted
     //  also returns damage dealt
-    pub fn take_damage(&mut self, damage: u32) -> (bool, u32) {
-        let active_pokemon = self.get_active_pokemon_mut();
+    pub fn take_damage(&mut self, slot: usize, damage: u32) -> (bool, u32) {
+        let active_pokemon = self.get_active_pokemon_mut(slot);
         if damage >= active_pokemon.pokemon.hp as u32 {
             let damage_dealt = active_pokemon.pokemon.hp as u32;
             active_pokemon.pokemon.hp = 0;
@@ -126,24 +371,51 @@ impl SingleSideState {
         }
     }
 
-    pub fn heal(&mut self, heal_amt: u32) {
-        let active_pokemon = self.get_active_pokemon_mut();
-        let new_hp =
-            (active_pokemon.pokemon.hp + heal_amt as u16).min(active_pokemon.pokemon.max_hp);
-        active_pokemon.pokemon.hp = new_hp;
+    // returns amount healed
+    pub fn heal(&mut self, slot: usize, heal_amt: u32) -> u32 {
+        let active_pokemon = self.get_active_pokemon_mut(slot);
+        if active_pokemon.is_fainted() {
+            return 0;
+        }
+
+        let prev_hp = active_pokemon.pokemon.hp;
+        let new_hp = (prev_hp as u32 + heal_amt).min(active_pokemon.pokemon.max_hp as u32);
+        active_pokemon.pokemon.hp = new_hp as u16;
+        new_hp - prev_hp as u32
+    }
+
+    pub fn num_usable_pokemon(&self) -> usize {
+        self.pokemon.iter().filter(|p| !p.is_fainted()).count()
+    }
+
+    // Fraction of the team's total hp that remains
+    pub fn remaining_hp_fraction(&self) -> f32 {
+        let (hp, max_hp) = self.pokemon.iter().fold((0, 0), |(hp, max_hp), p| {
+            (hp + p.pokemon.hp as u32, max_hp + p.pokemon.max_hp as u32)
+        });
+        hp as f32 / max_hp as f32
     }
 
     pub fn out_of_usable_pokemon(&self) -> bool {
         self.pokemon.iter().all(|p| p.is_fainted())
     }
 
-    pub fn get_valid_moves(&self) -> ValidMoves {
-        let pp_arr = self.get_active_pokemon().pp;
-        let move_names = self.get_active_pokemon().pokemon.moves;
+    pub fn get_valid_moves(&self, slot: usize) -> ValidMoves {
+        let active_pokemon = self.get_active_pokemon(slot);
+        if active_pokemon.move_lock.is_some() {
+            return ValidMoves::Locked;
+        }
+
+        let pp_arr = active_pokemon.pp;
+        let move_names = active_pokemon.pokemon.moves;
+        let restriction = active_pokemon.get_move_restriction();
 
         let mut valid: Vec<usize> = Vec::new();
         for (i, (pp_val, move_name)) in pp_arr.iter().zip(move_names.iter()).enumerate() {
-            if *move_name != MoveName::Empty && *pp_val > 0 {
+            if *move_name != MoveName::Empty
+                && *pp_val > 0
+                && restriction.is_none_or(|restricted| restricted == *move_name)
+            {
                 valid.push(i);
             }
         }
@@ -160,7 +432,7 @@ impl SingleSideState {
         let mut valid: Vec<usize> = Vec::new();
 
         for (i, pokemon) in self.pokemon.iter().enumerate() {
-            if i != self.active_pokemon_idx && !pokemon.is_fainted() {
+            if !self.is_active(i) && !pokemon.is_fainted() {
                 valid.push(i);
             }
         }
@@ -172,13 +444,51 @@ impl SingleSideState {
         }
     }
 
-    pub fn get_valid_actions(&self) -> ValidActions {
-        let valid_moves = self.get_valid_moves();
-        let valid_switches = self.get_valid_switches();
+    pub fn get_valid_actions(&self, slot: usize, mechanics: &MechanicsProfile) -> ValidActions {
+        let valid_moves = self.get_valid_moves(slot);
+        let pokemon = &self.get_active_pokemon(slot).pokemon;
+
+        // gimmicks are used alongside a chosen move, never with Struggle or a locked move
+        let move_idxs: &[usize] = match &valid_moves {
+            ValidMoves::Moves(move_idxs) => move_idxs,
+            _ => &[],
+        };
+        let can_choose_move = !move_idxs.is_empty();
+        let can_mega_evolve = mechanics.mega_evolution
+            && !self.mega_used
+            && can_choose_move
+            && pokemon.item.is_some_and(|item| {
+                mega_evolutions::get_mega_evolution(&pokemon.species, &item).is_some()
+            });
+        let z_crystal_type = pokemon
+            .item
+            .and_then(|item| item.get_z_crystal_type())
+            .filter(|_| mechanics.z_moves && !self.z_move_used);
+        let z_moves = move_idxs
+            .iter()
+            .copied()
+            .filter(|idx| {
+                let pokemove = move_dex::get_move_data(&pokemon.moves[*idx]);
+                z_crystal_type == Some(pokemove.move_type)
+                    && pokemove.category != MoveCategory::Status
+            })
+            .collect();
+        let can_dynamax = mechanics.dynamax && !self.dynamax_used && can_choose_move;
+        let can_terastallize = mechanics.terastallization && !self.tera_used && can_choose_move;
+
+        // a pokemon locked into a move cannot switch out
+        let valid_switches = match valid_moves {
+            ValidMoves::Locked => None,
+            _ => self.get_valid_switches(),
+        };
 
         ValidActions {
             valid_moves,
             valid_switches,
+            can_mega_evolve,
+            z_moves,
+            can_dynamax,
+            can_terastallize,
         }
     }
 }
@@ -1,6 +1,8 @@
 use crate::{
-    common::subscriber::Subscriber,
-    core::pokemove::move_category::MoveCategory,
+    battle::{move_lock::MoveLock, state::BattleState},
+    common::{context::MoveContext, subscriber::Subscriber},
+    core::{pokemove::move_name::MoveName, status::volatile_status::VolatileStatus},
+    dex::pokemove::{gimmick_moves, move_dex},
     query::{
         payload::Payload,
         query::{Query, QueryKind},
@@ -15,6 +17,8 @@ impl Subscriber<Query> for StaticBattleHandler {
         &[
             QueryKind::OnPriority,
             QueryKind::OnBasePower,
+            QueryKind::OnMod1,
+            QueryKind::IsCrit,
             QueryKind::MultiHitHits,
             QueryKind::CanApplyStatus,
             QueryKind::CanApplyVolatileStatus,
@@ -25,6 +29,8 @@ impl Subscriber<Query> for StaticBattleHandler {
         match kind {
             QueryKind::OnBasePower => 0,
             QueryKind::OnPriority => 0,
+            QueryKind::OnMod1 => 0,
+            QueryKind::IsCrit => 0,
             QueryKind::MultiHitHits => 0,
             QueryKind::CanApplyStatus => 0,
             QueryKind::CanApplyVolatileStatus => 0,
@@ -33,6 +39,17 @@ impl Subscriber<Query> for StaticBattleHandler {
     }
 }
 
+impl StaticBattleHandler {
+    // Weak moves of the tera type are raised to 60 power, except multi-hit and priority moves
+    fn gets_tera_power_floor(battle_state: &BattleState, context: MoveContext) -> bool {
+        let pokemon = battle_state.get_active_pokemon(context.src_trainer, context.src_slot);
+        pokemon.terastallized
+            && pokemon.pokemon.tera_type == context.pokemove.move_type
+            && !context.pokemove.is_multi_hit
+            && context.pokemove.priority <= 0
+    }
+}
+
 impl QueryHandler for StaticBattleHandler {
     fn handle(
         &self,
@@ -41,12 +58,52 @@ impl QueryHandler for StaticBattleHandler {
     ) {
         match query {
             Query::OnBasePower(payload) => {
-                let power = payload
-                    .context
-                    .pokemove
-                    .power
-                    .expect("Getting base power of move with no power");
-                payload.get_vec_f32().push(power as f32)
+                // Z-Moves and Max Moves take their power from the move they replace
+                if let Some(base_move_name) = payload.context.base_move {
+                    let base_move = move_dex::get_move_data(&base_move_name);
+                    let base_power = battle_state.mechanics.get_move_power(base_move);
+                    let move_name = &payload.context.move_name;
+                    if gimmick_moves::is_z_move(move_name) {
+                        let power = gimmick_moves::get_z_move_power(base_move, base_power);
+                        payload.get_vec_f32().push(power as f32);
+                    } else if gimmick_moves::is_max_move(move_name) {
+                        let power = gimmick_moves::get_max_move_power(base_move, base_power);
+                        payload.get_vec_f32().push(power as f32);
+                    }
+                    return;
+                }
+
+                // moves without a fixed power have it pushed by their base power handler
+                if let Some(power) = battle_state
+                    .mechanics
+                    .get_move_power(payload.context.pokemove)
+                {
+                    let power = if Self::gets_tera_power_floor(battle_state, payload.context) {
+                        power.max(60)
+                    } else {
+                        power
+                    };
+                    payload.get_vec_f32().push(power as f32);
+                }
+
+                // Rollout and Ice Ball double in power for each consecutive hit
+                if let Some(MoveLock::Rollout(_, hits)) = battle_state
+                    .get_active_pokemon(payload.context.src_trainer, payload.context.src_slot)
+                    .move_lock
+                {
+                    payload.get_vec_f32().push((1 << hits) as f32);
+                }
+            }
+            Query::OnMod1(payload) => {
+                // moves that hit more than one target deal 75% damage to each
+                if payload.context.is_spread {
+                    payload.get_vec_f32().push(0.75);
+                }
+            }
+            Query::IsCrit(payload) => {
+                let (numerator, denominator) = battle_state.mechanics.crit_chance;
+                payload.payload =
+                    Payload::Bool(battle_state.get_random_check(numerator, denominator));
             }
             Query::OnPriority(payload) => {
                 payload.payload = Payload::I8(payload.context.pokemove.priority);
@@ -56,16 +113,31 @@ impl QueryHandler for StaticBattleHandler {
                     battle_state.get_rand_num_inclusive(payload.min_hits, payload.max_hits);
             }
             Query::CanApplyStatus(payload) => {
-                payload.can_apply = battle_state
-                    .get_active_pokemon(payload.target)
+                let has_status = battle_state
+                    .get_active_pokemon(payload.target, payload.target_slot)
                     .status
                     .is_some();
+                if has_status && !payload.replaces_status {
+                    payload.can_apply = false;
+                }
             }
             Query::CanApplyVolatileStatus(payload) => {
-                payload.can_apply = !battle_state
-                    .get_active_pokemon(payload.target)
+                let target_pokemon =
+                    battle_state.get_active_pokemon(payload.target, payload.target_slot);
+                payload.can_apply = !target_pokemon
                     .volatile_statuses
                     .contains_key(&payload.volatile_status);
+
+                if payload.volatile_status == VolatileStatus::Encore {
+                    // Encore fails if the target has no move it can be locked into
+                    payload.can_apply &= match target_pokemon.last_move_used {
+                        None | Some(MoveName::Struggle) | Some(MoveName::Encore) => false,
+                        Some(move_name) => {
+                            let idx = target_pokemon.pokemon.get_idx_for_move_name(&move_name);
+                            target_pokemon.pp[idx] > 0
+                        }
+                    };
+                }
             }
             _ => panic!("unhandled query for move handler"),
         }
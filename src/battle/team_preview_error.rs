@@ -0,0 +1,15 @@
+// Reasons team preview can't go ahead, pokemon are referred to by party index
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TeamPreviewErrorKind {
+    InvalidTeamSize { team_size: usize, party_size: usize },
+    MissingTeamOrder,
+    WrongOrderLength { len: usize, team_size: usize },
+    IndexOutOfRange { idx: usize, party_size: usize },
+    DuplicateIndex { idx: usize },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TeamPreviewError {
+    pub trainer_1: bool, // side the error is for
+    pub kind: TeamPreviewErrorKind,
+}
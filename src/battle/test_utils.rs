@@ -0,0 +1,62 @@
+use crate::{
+    battle::{
+        actions::Action,
+        battle::Battle,
+        battle_input::{BattleInput, SingleInput},
+        game_type::GameType,
+    },
+    showdown::text,
+};
+
+// Starts a singles battle between two teams written in Showdown's export format
+pub fn get_battle(team_1: &str, team_2: &str, seed: u64) -> Battle {
+    let mut battle = Battle::new(
+        text::parse_team(team_1).expect("team 1 should parse"),
+        text::parse_team(team_2).expect("team 2 should parse"),
+    );
+    battle.set_seed(seed);
+    battle.start();
+    battle
+}
+
+// Input for one turn of singles, continuing a locked move in place of the given action
+pub fn get_input(battle: &Battle, action_1: Action, action_2: Action) -> BattleInput {
+    let get_side_input = |trainer: bool, action: Action| {
+        let action = match battle
+            .battle_state()
+            .get_active_pokemon(trainer, 0)
+            .move_lock
+        {
+            Some(_) => Action::Locked,
+            None => action,
+        };
+        vec![Some(SingleInput::StandardInput(action))]
+    };
+    (
+        get_side_input(true, action_1),
+        get_side_input(false, action_2),
+    )
+}
+
+// Starts a doubles battle, leading with the first two pokemon of each team
+pub fn get_doubles_battle(team_1: &str, team_2: &str, seed: u64) -> Battle {
+    let mut battle = Battle::new_with_game_type(
+        text::parse_team(team_1).expect("team 1 should parse"),
+        text::parse_team(team_2).expect("team 2 should parse"),
+        GameType::Doubles,
+    );
+    battle.set_seed(seed);
+    battle.start();
+    battle
+}
+
+// Input for one turn of doubles, with an action for each active slot
+pub fn get_doubles_input(actions_1: [Action; 2], actions_2: [Action; 2]) -> BattleInput {
+    let get_side_input = |actions: [Action; 2]| {
+        actions
+            .into_iter()
+            .map(|action| Some(SingleInput::StandardInput(action)))
+            .collect()
+    };
+    (get_side_input(actions_1), get_side_input(actions_2))
+}
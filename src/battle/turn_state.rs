@@ -1,5 +1,6 @@
+#[derive(Default)]
 pub struct TurnState {
-    pub fainted_sides: Vec<bool>, // ordered faints in this turn
+    pub fainted_sides: Vec<(bool, usize)>, // ordered (trainer, slot) faints in this turn
 }
 
 impl TurnState {
@@ -9,7 +10,7 @@ impl TurnState {
         }
     }
 
-    pub fn record_faint(&mut self, trainer_1: bool) {
-        self.fainted_sides.push(trainer_1);
+    pub fn record_faint(&mut self, trainer_1: bool, slot: usize) {
+        self.fainted_sides.push((trainer_1, slot));
     }
 }
@@ -1,5 +1,8 @@
+use crate::battle::actions::{Action, MoveModifier, MoveSlot, SwitchSlot};
+
 pub enum ValidMoves {
     Struggle,
+    Locked,
     Moves(Vec<usize>),
 }
 
@@ -10,4 +13,31 @@ pub struct ValidSwitches {
 pub struct ValidActions {
     pub valid_moves: ValidMoves,
     pub valid_switches: Option<ValidSwitches>,
+    pub can_mega_evolve: bool,
+    pub z_moves: Vec<usize>, // move indices that can be used as a Z-Move
+    pub can_dynamax: bool,
+    pub can_terastallize: bool,
+}
+
+impl ValidActions {
+    // Every move and switch that can be chosen, without gimmicks or explicit targets
+    pub fn get_actions(&self) -> Vec<Action> {
+        let mut actions = match &self.valid_moves {
+            ValidMoves::Struggle => vec![Action::Struggle],
+            ValidMoves::Locked => vec![Action::Locked],
+            ValidMoves::Moves(move_idxs) => move_idxs
+                .iter()
+                .map(|idx| Action::Move(MoveSlot::from_index(*idx), MoveModifier::None))
+                .collect(),
+        };
+        if let Some(valid_switches) = &self.valid_switches {
+            actions.extend(
+                valid_switches
+                    .switches
+                    .iter()
+                    .map(|idx| Action::Switch(SwitchSlot::from_index(*idx))),
+            );
+        }
+        actions
+    }
 }
@@ -0,0 +1,231 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use pokemon_simple_env::{
+    agent::{
+        expectiminimax_agent::{ExpectiminimaxAgent, ExpectiminimaxConfig},
+        max_damage_agent::MaxDamageAgent,
+        mcts_agent::{MctsAgent, MctsConfig},
+        random_agent::RandomAgent,
+    },
+    core::pokemon::pokemon::Pokemon,
+    showdown::packed,
+    tournament::tournament::{AgentFactory, Tournament, TournamentConfig, TournamentFormat},
+};
+
+const USAGE: &str = "\
+Usage: tournament --teams <file> --agents <agent,agent,...> [options]
+
+Teams are read one packed Showdown team per line, blank lines and lines starting
+with # are skipped.
+
+Agents:
+  random
+  max-damage
+  mcts[:iterations]          defaults to 1000 iterations
+  expectiminimax[:depth]     defaults to depth 1
+
+Options:
+  --format <round-robin|swiss:rounds>   defaults to round-robin
+  --battles <n>                         battles per pairing, defaults to 2
+  --seed <n>                            defaults to 0
+  --threads <n>                         defaults to the number of cpus
+  --json <file>                         standings and every battle as JSON
+  --standings-csv <file>
+  --battles-csv <file>";
+
+struct Args {
+    teams: PathBuf,
+    agents: Vec<String>,
+    format: TournamentFormat,
+    battles: u32,
+    seed: u64,
+    threads: Option<usize>,
+    json: Option<PathBuf>,
+    standings_csv: Option<PathBuf>,
+    battles_csv: Option<PathBuf>,
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{message}\n\n{USAGE}");
+    process::exit(2);
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> T {
+    value
+        .parse()
+        .unwrap_or_else(|_| exit_with_usage(&format!("Invalid number for {flag}: {value}")))
+}
+
+fn parse_args() -> Args {
+    let mut teams = None;
+    let mut agents = vec![];
+    let mut args = Args {
+        teams: PathBuf::new(),
+        agents: vec![],
+        format: TournamentFormat::RoundRobin,
+        battles: 2,
+        seed: 0,
+        threads: None,
+        json: None,
+        standings_csv: None,
+        battles_csv: None,
+    };
+
+    let mut argv = env::args().skip(1);
+    while let Some(flag) = argv.next() {
+        if flag == "--help" || flag == "-h" {
+            println!("{USAGE}");
+            process::exit(0);
+        }
+        let value = argv
+            .next()
+            .unwrap_or_else(|| exit_with_usage(&format!("Missing value for {flag}")));
+        match flag.as_str() {
+            "--teams" => teams = Some(PathBuf::from(value)),
+            "--agents" => agents = value.split(',').map(str::to_string).collect(),
+            "--format" => {
+                args.format = match value.split_once(':') {
+                    None if value == "round-robin" => TournamentFormat::RoundRobin,
+                    Some(("swiss", rounds)) => TournamentFormat::Swiss(parse_number(&flag, rounds)),
+                    _ => exit_with_usage(&format!("Unknown format: {value}")),
+                }
+            }
+            "--battles" => args.battles = parse_number(&flag, &value),
+            "--seed" => args.seed = parse_number(&flag, &value),
+            "--threads" => args.threads = Some(parse_number(&flag, &value)),
+            "--json" => args.json = Some(PathBuf::from(value)),
+            "--standings-csv" => args.standings_csv = Some(PathBuf::from(value)),
+            "--battles-csv" => args.battles_csv = Some(PathBuf::from(value)),
+            _ => exit_with_usage(&format!("Unknown option: {flag}")),
+        }
+    }
+
+    args.teams = teams.unwrap_or_else(|| exit_with_usage("Missing --teams"));
+    if agents.len() < 2 {
+        exit_with_usage("At least two agents are needed");
+    }
+    args.agents = agents;
+    args
+}
+
+fn read_teams(path: &PathBuf) -> Vec<Vec<Pokemon>> {
+    let contents = fs::read_to_string(path).unwrap_or_else(|error| {
+        eprintln!("Could not read {}: {error}", path.display());
+        process::exit(1);
+    });
+
+    contents
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_idx, line)| {
+            packed::parse_packed_team(line).unwrap_or_else(|errors| {
+                eprintln!("Invalid team on line {}: {errors:?}", line_idx + 1);
+                process::exit(1);
+            })
+        })
+        .collect()
+}
+
+fn get_agent_factory(name: &str) -> AgentFactory {
+    let (kind, param) = match name.split_once(':') {
+        Some((kind, param)) => (kind, Some(param)),
+        None => (name, None),
+    };
+    match (kind, param) {
+        ("random", None) => Box::new(|seed| Box::new(RandomAgent::new(seed))),
+        ("max-damage", None) => Box::new(|_| Box::new(MaxDamageAgent)),
+        ("mcts", _) => {
+            let iterations = param.map_or(1000, |param| parse_number(name, param));
+            Box::new(move |seed| {
+                Box::new(MctsAgent::with_random_rollouts(
+                    MctsConfig::builder()
+                        .iterations(Some(iterations))
+                        .seed(seed)
+                        .build(),
+                ))
+            })
+        }
+        ("expectiminimax", _) => {
+            let depth = param.map_or(1, |param| parse_number(name, param));
+            Box::new(move |seed| {
+                Box::new(ExpectiminimaxAgent::with_hp_evaluation(
+                    ExpectiminimaxConfig::builder()
+                        .depth(depth)
+                        .seed(seed)
+                        .build(),
+                ))
+            })
+        }
+        _ => exit_with_usage(&format!("Unknown agent: {name}")),
+    }
+}
+
+fn exit_on_write_error(result: io::Result<()>, path: &Path) {
+    if let Err(error) = result {
+        eprintln!("Could not write {}: {error}", path.display());
+        process::exit(1);
+    }
+}
+
+fn main() {
+    let args = parse_args();
+    let teams = read_teams(&args.teams);
+    if teams.is_empty() {
+        eprintln!("No teams in {}", args.teams.display());
+        process::exit(1);
+    }
+
+    let config = TournamentConfig::builder()
+        .format(args.format)
+        .battles_per_pairing(args.battles)
+        .seed(args.seed);
+    let config = match args.threads {
+        Some(threads) => config.threads(threads).build(),
+        None => config.build(),
+    };
+    let mut tournament = Tournament::new(config, teams);
+    for name in &args.agents {
+        tournament.register_agent(name, get_agent_factory(name));
+    }
+
+    let results = tournament.run();
+
+    let mut standings: Vec<_> = results.standings.iter().collect();
+    standings.sort_by(|a, b| b.elo.rating.total_cmp(&a.elo.rating));
+    println!(
+        "{:<24} {:>7} {:>5} {:>6} {:>5} {:>17} {:>7} {:>13}",
+        "agent", "battles", "wins", "losses", "draws", "win rate (95%)", "elo", "glicko"
+    );
+    for s in standings {
+        println!(
+            "{:<24} {:>7} {:>5} {:>6} {:>5} {:>5.3} [{:.2}-{:.2}] {:>7.0} {:>6.0} ±{:<4.0}",
+            s.name,
+            s.battles,
+            s.wins,
+            s.losses,
+            s.draws,
+            s.win_rate,
+            s.win_rate_low,
+            s.win_rate_high,
+            s.elo.rating,
+            s.glicko.rating,
+            2.0 * s.glicko.deviation
+        );
+    }
+
+    if let Some(path) = &args.json {
+        exit_on_write_error(results.write_json(path), path);
+    }
+    if let Some(path) = &args.standings_csv {
+        exit_on_write_error(results.write_standings_csv(path), path);
+    }
+    if let Some(path) = &args.battles_csv {
+        exit_on_write_error(results.write_battles_csv(path), path);
+    }
+}
@@ -1,9 +1,28 @@
-use crate::core::pokemove::{move_name::MoveName, pokemove::PokeMove};
+use crate::{
+    core::pokemove::{move_name::MoveName, pokemove::PokeMove},
+    dex::pokemove::move_dex,
+};
 
 #[derive(Clone, Copy)]
 pub struct MoveContext {
     pub src_trainer: bool,
+    pub src_slot: usize,
     pub target_trainer: bool,
+    pub target_slot: usize,
     pub move_name: MoveName,
     pub pokemove: &'static PokeMove,
+    pub is_spread: bool,             // the move is hitting more than one target
+    pub base_move: Option<MoveName>, // the chosen move, when a Z-Move or Max Move replaces it
+}
+
+impl MoveContext {
+    // The move the trainer picked, which pp, choice locks and last move used refer to
+    pub fn get_selected_move(&self) -> MoveName {
+        self.base_move.unwrap_or(self.move_name)
+    }
+
+    // Z-Moves and Max Moves take their category from the chosen move
+    pub fn get_category_move(&self) -> &'static PokeMove {
+        move_dex::get_move_data(&self.get_selected_move())
+    }
 }
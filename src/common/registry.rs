@@ -10,6 +10,29 @@ where
     subscribers: HashMap<T::Kind, Vec<Arc<U>>>,
 }
 
+// Handlers are shared between clones, which keeps removal by pointer working on both
+impl<T, U> Clone for Registry<T, U>
+where
+    T: HasKind,
+    U: ?Sized + Subscriber<T>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            subscribers: self.subscribers.clone(),
+        }
+    }
+}
+
+impl<T, U> Default for Registry<T, U>
+where
+    T: HasKind,
+    U: ?Sized + Subscriber<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T, U> Registry<T, U>
 where
     T: HasKind,
@@ -37,7 +60,7 @@ where
 
     pub fn add_handler(&mut self, handler: Arc<U>) {
         handler.as_ref().subscriptions().iter().for_each(|kind| {
-            let vec = self.subscribers.entry(*kind).or_insert_with(Vec::new);
+            let vec = self.subscribers.entry(*kind).or_default();
 
             let idx = vec
                 .binary_search_by(|s| s.priority(kind).cmp(&handler.priority(kind)).reverse())
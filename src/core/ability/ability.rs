@@ -1,9 +1,16 @@
 use enum_map::Enum;
 use serde::{Deserialize, Serialize};
+use strum::{EnumCount, EnumIter};
 
-#[derive(Clone, Copy, Enum, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, Debug, Enum, EnumCount, EnumIter, Serialize, Deserialize, PartialEq, Eq, Hash,
+)]
 pub enum Ability {
     Blaze,
     Overgrow,
     Torrent,
+    ThickFat,
+    Technician,
+    SandForce,
+    Adaptability,
 }
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use strum::{EnumCount, EnumIter};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, EnumCount, EnumIter)]
+pub enum Weather {
+    Sun,
+    Rain,
+    Sand,
+    Hail,
+}
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use strum::{EnumCount, EnumIter};
+
+use crate::core::poketype::poketype::PokeType;
+
+#[derive(Clone, Copy, Debug, EnumCount, EnumIter, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Item {
+    ChoiceBand,
+    ChoiceSpecs,
+    ChoiceScarf,
+
+    // mega stones
+    Venusaurite,
+    Scizorite,
+    Garchompite,
+    Lucarionite,
+
+    // z crystals
+    NormaliumZ,
+    FiriumZ,
+    WateriumZ,
+    ElectriumZ,
+    GrassiumZ,
+    IciumZ,
+    FightiniumZ,
+    PoisoniumZ,
+    GroundiumZ,
+    FlyiniumZ,
+    PsychiumZ,
+    BuginiumZ,
+    RockiumZ,
+    GhostiumZ,
+    DragoniumZ,
+    DarkiniumZ,
+    SteeliumZ,
+    FairiumZ,
+}
+
+impl Item {
+    pub fn is_choice(&self) -> bool {
+        matches!(
+            self,
+            Item::ChoiceBand | Item::ChoiceSpecs | Item::ChoiceScarf
+        )
+    }
+
+    // Type of damaging move the holder can turn into a Z-Move
+    pub fn get_z_crystal_type(&self) -> Option<PokeType> {
+        match self {
+            Item::NormaliumZ => Some(PokeType::Normal),
+            Item::FiriumZ => Some(PokeType::Fire),
+            Item::WateriumZ => Some(PokeType::Water),
+            Item::ElectriumZ => Some(PokeType::Electric),
+            Item::GrassiumZ => Some(PokeType::Grass),
+            Item::IciumZ => Some(PokeType::Ice),
+            Item::FightiniumZ => Some(PokeType::Fighting),
+            Item::PoisoniumZ => Some(PokeType::Poison),
+            Item::GroundiumZ => Some(PokeType::Ground),
+            Item::FlyiniumZ => Some(PokeType::Flying),
+            Item::PsychiumZ => Some(PokeType::Psychic),
+            Item::BuginiumZ => Some(PokeType::Bug),
+            Item::RockiumZ => Some(PokeType::Rock),
+            Item::GhostiumZ => Some(PokeType::Ghost),
+            Item::DragoniumZ => Some(PokeType::Dragon),
+            Item::DarkiniumZ => Some(PokeType::Dark),
+            Item::SteeliumZ => Some(PokeType::Steel),
+            Item::FairiumZ => Some(PokeType::Fairy),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use enum_map::Enum;
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Clone, Copy, Debug, Enum, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum Generation {
+    Gen4 = 4,
+    Gen5 = 5,
+    Gen6 = 6,
+    Gen7 = 7,
+    Gen8 = 8,
+    Gen9 = 9,
+}
+
+impl Generation {
+    pub fn number(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_number(number: u8) -> Option<Self> {
+        match number {
+            4 => Some(Generation::Gen4),
+            5 => Some(Generation::Gen5),
+            6 => Some(Generation::Gen6),
+            7 => Some(Generation::Gen7),
+            8 => Some(Generation::Gen8),
+            9 => Some(Generation::Gen9),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use serde::Serialize;
+
+use crate::{
+    battle::actions::MoveModifier,
+    core::{
+        mechanics::generation::Generation,
+        pokemove::{move_category::MoveCategory, pokemove::PokeMove},
+    },
+    dex::pokemove::move_revisions,
+};
+
+// Numbers and rules that changed between generations, chosen once per battle
+#[derive(Clone, Copy, Serialize)]
+pub struct MechanicsProfile {
+    pub generation: Generation,
+    pub crit_multiplier: f32,
+    pub crit_chance: (u32, u32), // numerator and denominator without crit boosts
+    pub burn_damage_divisor: u32, // burn deals max hp / divisor each turn
+    pub paralysis_speed_mult: f32,
+    pub sleep_counter: (u8, u8), // inclusive range, the pokemon wakes when it reaches 1
+    pub mega_evolution: bool,
+    pub z_moves: bool,
+    pub dynamax: bool,
+    pub terastallization: bool,
+}
+
+impl MechanicsProfile {
+    pub fn for_generation(generation: Generation) -> Self {
+        Self {
+            generation,
+            crit_multiplier: if generation >= Generation::Gen6 {
+                1.5
+            } else {
+                2.0
+            },
+            crit_chance: if generation >= Generation::Gen7 {
+                (1, 24)
+            } else {
+                (1, 16)
+            },
+            burn_damage_divisor: if generation >= Generation::Gen7 {
+                16
+            } else {
+                8
+            },
+            paralysis_speed_mult: if generation >= Generation::Gen7 {
+                0.5
+            } else {
+                0.25
+            },
+            sleep_counter: if generation >= Generation::Gen5 {
+                (2, 4)
+            } else {
+                (2, 5)
+            },
+            mega_evolution: matches!(generation, Generation::Gen6 | Generation::Gen7),
+            z_moves: generation == Generation::Gen7,
+            dynamax: generation == Generation::Gen8,
+            terastallization: generation >= Generation::Gen9,
+        }
+    }
+
+    pub fn is_modifier_available(&self, modifier: MoveModifier) -> bool {
+        match modifier {
+            MoveModifier::None => true,
+            MoveModifier::MegaEvolve => self.mega_evolution,
+            MoveModifier::ZMove => self.z_moves,
+            MoveModifier::Dynamax => self.dynamax,
+            MoveModifier::Terastallize => self.terastallization,
+        }
+    }
+
+    // Every supported generation has the physical/special split, so no category changed
+    pub fn get_move_category(&self, pokemove: &PokeMove) -> MoveCategory {
+        pokemove.category
+    }
+
+    pub fn get_move_power(&self, pokemove: &PokeMove) -> Option<u32> {
+        move_revisions::get_move_revision(&pokemove.name, self.generation)
+            .and_then(|revision| revision.power)
+            .or(pokemove.power)
+    }
+
+    pub fn get_move_accuracy(&self, pokemove: &PokeMove) -> Option<u8> {
+        move_revisions::get_move_revision(&pokemove.name, self.generation)
+            .and_then(|revision| revision.accuracy)
+            .or(pokemove.accuracy)
+    }
+}
+
+impl Default for MechanicsProfile {
+    fn default() -> Self {
+        Self::for_generation(Generation::Gen9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::{
+        battle::{
+            actions::{Action, MoveSlot},
+            battle::Battle,
+            battle_engine::BattleEngine,
+            test_utils,
+        },
+        common::context::MoveContext,
+        core::{
+            pokemon::stat_enum::StatEnum, pokemove::move_name::MoveName, status::status::Status,
+        },
+        dex::pokemove::move_dex,
+    };
+
+    fn get_battle(generation: Generation, seed: u64) -> Battle {
+        let mut battle = test_utils::get_battle(
+            "Garchomp\nAbility: Sand Force\n- Earthquake",
+            "Snorlax\nAbility: Thick Fat\n- Tackle",
+            seed,
+        );
+        battle.set_generation(generation);
+        battle
+    }
+
+    #[test]
+    fn crits_deal_double_damage_before_gen_6() {
+        for (generation, crit_multiplier) in [(Generation::Gen5, 2.0), (Generation::Gen6, 1.5)] {
+            let damage_rolls = get_battle(generation, 0)
+                .get_damage_rolls(true, 0, &Action::Move(MoveSlot::Slot0, MoveModifier::None))
+                .unwrap();
+            for (roll, crit_roll) in damage_rolls.rolls.iter().zip(damage_rolls.crit_rolls) {
+                assert!((crit_roll as f64 / *roll as f64 - crit_multiplier).abs() < 0.02);
+            }
+        }
+    }
+
+    #[test]
+    fn sleep_lasts_longer_before_gen_5() {
+        let get_sleep_turns = |generation: Generation| {
+            (0..50)
+                .map(|seed| {
+                    let mut battle = get_battle(generation, seed);
+                    let move_context = MoveContext {
+                        src_trainer: true,
+                        src_slot: 0,
+                        target_trainer: false,
+                        target_slot: 0,
+                        move_name: MoveName::Spore,
+                        pokemove: move_dex::get_move_data(&MoveName::Spore),
+                        is_spread: false,
+                        base_move: None,
+                    };
+                    let mut battle_context = battle.battle_context();
+                    BattleEngine::set_status(
+                        &mut battle_context,
+                        &move_context,
+                        false,
+                        0,
+                        Status::Sleep,
+                    );
+                    battle_context
+                        .battle_state
+                        .get_active_pokemon(false, 0)
+                        .sleep_turns
+                })
+                .collect::<BTreeSet<_>>()
+        };
+
+        assert_eq!(
+            get_sleep_turns(Generation::Gen4),
+            BTreeSet::from([2, 3, 4, 5])
+        );
+        assert_eq!(get_sleep_turns(Generation::Gen5), BTreeSet::from([2, 3, 4]));
+    }
+
+    #[test]
+    fn paralysis_quarters_speed_before_gen_7() {
+        for (generation, speed_multiplier) in [(Generation::Gen6, 0.25), (Generation::Gen7, 0.5)] {
+            let mut battle = get_battle(generation, 0);
+            let mut battle_context = battle.battle_context();
+            let speed = BattleEngine::get_effective_stat_value(
+                &mut battle_context,
+                true,
+                0,
+                StatEnum::Speed,
+            );
+            BattleEngine::apply_status(&mut battle_context, true, 0, Status::Paralyze);
+            let paralyzed_speed = BattleEngine::get_effective_stat_value(
+                &mut battle_context,
+                true,
+                0,
+                StatEnum::Speed,
+            );
+            assert_eq!(
+                paralyzed_speed,
+                (speed as f32 * speed_multiplier).floor() as u32
+            );
+        }
+    }
+
+    #[test]
+    fn tackle_was_weaker_and_less_accurate_in_gen_4() {
+        let tackle = move_dex::get_move_data(&MoveName::Tackle);
+        for (generation, power, accuracy) in [
+            (Generation::Gen4, 35, 95),
+            (Generation::Gen5, 50, 100),
+            (Generation::Gen9, 40, 100),
+        ] {
+            let mechanics = MechanicsProfile::for_generation(generation);
+            assert_eq!(mechanics.get_move_power(tackle), Some(power));
+            assert_eq!(mechanics.get_move_accuracy(tackle), Some(accuracy));
+        }
+    }
+}
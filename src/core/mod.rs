@@ -1,6 +1,10 @@
 pub mod ability;
+pub mod field;
+pub mod item;
+pub mod mechanics;
 pub mod pokemon;
 pub mod pokemove;
 pub mod poketype;
+pub mod side_condition;
 pub mod status;
 pub mod util;
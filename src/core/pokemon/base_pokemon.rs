@@ -1,6 +1,12 @@
-use crate::core::{pokemon::stat_enum::StatEnum, poketype::pokemon_typing::PokemonTyping};
+use crate::core::{
+    pokemon::{species::Species, stat_enum::StatEnum},
+    poketype::pokemon_typing::PokemonTyping,
+};
 
+#[derive(Clone)]
 pub struct BasePokemon {
+    pub species: Species,
+
     pub hp: u16,
     pub attack: u16,
     pub spattack: u16,
@@ -9,6 +15,7 @@ pub struct BasePokemon {
     pub speed: u16,
 
     pub typing: PokemonTyping,
+    pub weight: f32, // kg
 }
 
 impl BasePokemon {
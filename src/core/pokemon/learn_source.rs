@@ -0,0 +1,31 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LearnMethod {
+    LevelUp(u8),
+    Machine, // TM, TR or HM
+    Egg,
+    Tutor,
+}
+
+// One way a species can learn a move in a given generation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LearnSource {
+    pub generation: u8,
+    pub method: LearnMethod,
+}
+
+impl LearnSource {
+    // Parses Showdown's learnset codes, e.g. "9L37" (level 37 in gen 9), "8M", "7E" and "7T"
+    pub fn from_code(code: &str) -> Option<Self> {
+        let method_idx = code.find(|c: char| !c.is_ascii_digit())?;
+        let generation = code[..method_idx].parse().ok()?;
+        let method = match &code[method_idx..method_idx + 1] {
+            "L" => LearnMethod::LevelUp(code[method_idx + 1..].parse().ok()?),
+            "M" => LearnMethod::Machine,
+            "E" => LearnMethod::Egg,
+            "T" => LearnMethod::Tutor,
+            _ => return None,
+        };
+
+        Some(Self { generation, method })
+    }
+}
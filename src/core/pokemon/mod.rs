@@ -1,5 +1,8 @@
 pub mod base_pokemon;
 pub mod boostable_stat;
+pub mod learn_source;
 pub mod nature;
 pub mod pokemon;
+pub mod pokemon_validation_error;
+pub mod species;
 pub mod stat_enum;
@@ -1,5 +1,9 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
 use crate::core::pokemon::stat_enum::StatEnum;
 
+#[derive(Clone, Copy, Debug, EnumIter, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Nature {
     Adamant,
     Bashful,
@@ -8,7 +8,14 @@ use crate::{
     battle::actions::{Action, MoveSlot},
     core::{
         ability::ability::Ability,
-        pokemon::{base_pokemon::BasePokemon, nature::Nature, stat_enum::StatEnum},
+        item::item::Item,
+        pokemon::{
+            base_pokemon::BasePokemon,
+            nature::Nature,
+            pokemon_validation_error::{MAX_EV, MAX_EV_TOTAL, MAX_IV, PokemonValidationError},
+            species::Species,
+            stat_enum::StatEnum,
+        },
         pokemove::move_name::MoveName,
         poketype::{pokemon_typing::PokemonTyping, poketype::PokeType},
         util::stat_utils,
@@ -17,6 +24,7 @@ use crate::{
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Pokemon {
+    pub species: Species,
     pub level: u8,
 
     pub max_hp: u16,
@@ -28,55 +36,77 @@ pub struct Pokemon {
     pub speed: u16,
 
     pub typing: PokemonTyping,
+    pub tera_type: PokeType, // defaults to the primary type
+    pub weight: f32,         // kg
+    pub happiness: u8,
 
     pub moves: [MoveName; 4],
 
     pub ability: Ability,
+    pub item: Option<Item>,
+
+    pub ivs: HashMap<StatEnum, u8>,
+    pub evs: HashMap<StatEnum, u8>,
+    pub nature: Nature,
 }
 
 impl Pokemon {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         base_pokemon: BasePokemon,
         level: u8,
         moves: Vec<MoveName>,
         ability: Ability,
+        item: Option<Item>,
         ivs: HashMap<StatEnum, u8>,
         evs: HashMap<StatEnum, u8>,
         nature: Nature,
-    ) -> Self {
-        if level == 0 || level > 100 {
-            panic!("Disallowed level")
+    ) -> Result<Self, Vec<PokemonValidationError>> {
+        let errors = Self::validate(level, &moves, &ivs, &evs);
+        if !errors.is_empty() {
+            return Err(errors);
         }
 
-        let num_moves = moves.len();
-        let unique_moves_len = moves
-            .clone()
-            .into_iter()
-            .collect::<HashSet<MoveName>>()
-            .len();
-        if unique_moves_len != num_moves {
-            panic!("Contains duplicate moves")
-        }
+        let mut moves_arr = [MoveName::Empty; 4];
+        moves_arr[..moves.len()].copy_from_slice(&moves);
 
-        let moves_arr = match moves.as_slice() {
-            [move1] => [*move1, MoveName::Empty, MoveName::Empty, MoveName::Empty],
-            [move1, move2] => [*move1, *move2, MoveName::Empty, MoveName::Empty],
-            [move1, move2, move3] => [*move1, *move2, *move3, MoveName::Empty],
-            [move1, move2, move3, move4] => [*move1, *move2, *move3, *move4],
-            _ => panic!("Unexpected number of moves!"),
-        };
+        let stat_map = Self::calculate_stats(&base_pokemon, level, &ivs, &evs, nature);
+
+        Ok(Self {
+            species: base_pokemon.species,
+            level,
+            max_hp: stat_map[StatEnum::HP],
+            hp: stat_map[StatEnum::HP],
+            attack: stat_map[StatEnum::Attack],
+            spattack: stat_map[StatEnum::SpecialAttack],
+            defense: stat_map[StatEnum::Defense],
+            spdefense: stat_map[StatEnum::SpecialDefense],
+            speed: stat_map[StatEnum::Speed],
+            tera_type: base_pokemon.typing.primary_type(),
+            typing: base_pokemon.typing,
+            weight: base_pokemon.weight,
+            happiness: 255,
+            moves: moves_arr,
+            ability,
+            item,
+            ivs,
+            evs,
+            nature,
+        })
+    }
 
+    fn calculate_stats(
+        base_pokemon: &BasePokemon,
+        level: u8,
+        ivs: &HashMap<StatEnum, u8>,
+        evs: &HashMap<StatEnum, u8>,
+        nature: Nature,
+    ) -> EnumMap<StatEnum, u16> {
         let (boosted_stat, neg_stat) = nature.get_changed_stats();
 
         let mut stat_map: EnumMap<StatEnum, u16> = EnumMap::default();
 
         for stat_enum in StatEnum::iter() {
-            assert!(
-                ivs.contains_key(&stat_enum) && ivs[&stat_enum] <= 31,
-                "Invalid iv for {stat_enum}"
-            );
-            assert!(evs.contains_key(&stat_enum), "Invalid ev for {stat_enum}");
-
             let iv = ivs[&stat_enum];
             let ev = evs[&stat_enum];
 
@@ -101,28 +131,101 @@ impl Pokemon {
             stat_map[stat_enum] = stat;
         }
 
-        Self {
-            level,
-            max_hp: stat_map[StatEnum::HP],
-            hp: stat_map[StatEnum::HP],
-            attack: stat_map[StatEnum::Attack],
-            spattack: stat_map[StatEnum::SpecialAttack],
-            defense: stat_map[StatEnum::Defense],
-            spdefense: stat_map[StatEnum::SpecialDefense],
-            speed: stat_map[StatEnum::Speed],
-            typing: base_pokemon.typing,
-            moves: moves_arr,
-            ability,
+        stat_map
+    }
+
+    // Takes on the mega's stats, typing and ability, hp is unchanged
+    pub fn mega_evolve(&mut self, mega_pokemon: &BasePokemon, ability: Ability) {
+        let stat_map =
+            Self::calculate_stats(mega_pokemon, self.level, &self.ivs, &self.evs, self.nature);
+
+        self.species = mega_pokemon.species;
+        self.attack = stat_map[StatEnum::Attack];
+        self.spattack = stat_map[StatEnum::SpecialAttack];
+        self.defense = stat_map[StatEnum::Defense];
+        self.spdefense = stat_map[StatEnum::SpecialDefense];
+        self.speed = stat_map[StatEnum::Speed];
+        self.typing = mega_pokemon.typing.clone();
+        self.weight = mega_pokemon.weight;
+        self.ability = ability;
+    }
+
+    // Assumes max ivs, no evs and a neutral nature, hp is unchanged
+    pub fn set_neutral_spread(&mut self, base_pokemon: &BasePokemon) {
+        self.ivs = StatEnum::iter().map(|stat| (stat, MAX_IV)).collect();
+        self.evs = StatEnum::iter().map(|stat| (stat, 0)).collect();
+        self.nature = Nature::Serious;
+
+        let stat_map =
+            Self::calculate_stats(base_pokemon, self.level, &self.ivs, &self.evs, self.nature);
+        self.attack = stat_map[StatEnum::Attack];
+        self.spattack = stat_map[StatEnum::SpecialAttack];
+        self.defense = stat_map[StatEnum::Defense];
+        self.spdefense = stat_map[StatEnum::SpecialDefense];
+        self.speed = stat_map[StatEnum::Speed];
+    }
+
+    // Checks everything that doesn't depend on the format, see format::team_validator
+    pub fn validate(
+        level: u8,
+        moves: &[MoveName],
+        ivs: &HashMap<StatEnum, u8>,
+        evs: &HashMap<StatEnum, u8>,
+    ) -> Vec<PokemonValidationError> {
+        let mut errors = vec![];
+
+        if level == 0 || level > 100 {
+            errors.push(PokemonValidationError::InvalidLevel(level));
+        }
+
+        if moves.is_empty() {
+            errors.push(PokemonValidationError::NoMoves);
+        } else if moves.len() > 4 {
+            errors.push(PokemonValidationError::TooManyMoves(moves.len()));
+        }
+        if moves.contains(&MoveName::Empty) {
+            errors.push(PokemonValidationError::EmptyMoveSlot);
         }
+        let mut seen_moves = HashSet::new();
+        for move_name in moves {
+            if *move_name != MoveName::Empty && !seen_moves.insert(*move_name) {
+                errors.push(PokemonValidationError::DuplicateMove(*move_name));
+            }
+        }
+
+        let mut ev_total: u32 = 0;
+        for stat_enum in StatEnum::iter() {
+            match ivs.get(&stat_enum) {
+                None => errors.push(PokemonValidationError::MissingIv(stat_enum)),
+                Some(iv) if *iv > MAX_IV => {
+                    errors.push(PokemonValidationError::IvAboveMax(stat_enum, *iv))
+                }
+                Some(_) => {}
+            }
+
+            match evs.get(&stat_enum) {
+                None => errors.push(PokemonValidationError::MissingEv(stat_enum)),
+                Some(ev) => {
+                    if *ev > MAX_EV {
+                        errors.push(PokemonValidationError::EvAboveMax(stat_enum, *ev));
+                    }
+                    ev_total += *ev as u32;
+                }
+            }
+        }
+        if ev_total > MAX_EV_TOTAL {
+            errors.push(PokemonValidationError::EvTotalAboveMax(ev_total));
+        }
+
+        errors
     }
 
     pub fn get_move_for_action(&self, action: &Action) -> Option<MoveName> {
-        match action {
-            Action::Move(MoveSlot::Slot0) => Some(self.moves[0]),
-            Action::Move(MoveSlot::Slot1) => Some(self.moves[1]),
-            Action::Move(MoveSlot::Slot2) => Some(self.moves[2]),
-            Action::Move(MoveSlot::Slot3) => Some(self.moves[3]),
-            _ => None,
+        match action.get_move_slot()? {
+            MoveSlot::Slot0 => Some(self.moves[0]),
+            MoveSlot::Slot1 => Some(self.moves[1]),
+            MoveSlot::Slot2 => Some(self.moves[2]),
+            MoveSlot::Slot3 => Some(self.moves[3]),
         }
     }
 
@@ -0,0 +1,20 @@
+use crate::core::{pokemon::stat_enum::StatEnum, pokemove::move_name::MoveName};
+
+pub const MAX_IV: u8 = 31;
+pub const MAX_EV: u8 = 252;
+pub const MAX_EV_TOTAL: u32 = 510;
+
+// Problems with a single pokemon, all of them are collected rather than stopping at the first
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PokemonValidationError {
+    InvalidLevel(u8),
+    NoMoves,
+    TooManyMoves(usize),
+    DuplicateMove(MoveName),
+    EmptyMoveSlot, // MoveName::Empty passed as a move
+    MissingIv(StatEnum),
+    IvAboveMax(StatEnum, u8),
+    MissingEv(StatEnum),
+    EvAboveMax(StatEnum, u8),
+    EvTotalAboveMax(u32),
+}
@@ -0,0 +1,44 @@
+use enum_map::Enum;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumCount, EnumIter};
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Enum,
+    EnumCount,
+    EnumIter,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    Display,
+)]
+pub enum Species {
+    Venusaur,
+    Charizard,
+    Blastoise,
+    Pikachu,
+    Alakazam,
+    Machamp,
+    Gengar,
+    Starmie,
+    Snorlax,
+    Dragonite,
+    Tyranitar,
+    Skarmory,
+    Scizor,
+    Heatran,
+    Garchomp,
+    Lucario,
+    Ferrothorn,
+    Amoonguss,
+
+    // mega evolutions, only reached in battle
+    VenusaurMega,
+    ScizorMega,
+    GarchompMega,
+    LucarioMega,
+}
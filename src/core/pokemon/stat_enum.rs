@@ -1,7 +1,21 @@
 use enum_map::Enum;
+use serde::{Deserialize, Serialize};
 use strum::{Display, EnumIter};
 
-#[derive(Clone, Copy, Enum, EnumIter, Default, Hash, Eq, PartialEq, Display)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Enum,
+    EnumIter,
+    Default,
+    Hash,
+    Eq,
+    PartialEq,
+    Display,
+    Serialize,
+    Deserialize,
+)]
 pub enum StatEnum {
     #[default] // this should never be used as a default, but EnumMap requires it
     HP,
@@ -0,0 +1,8 @@
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FixedDamage {
+    Amount(u32),         // e.g. Dragon Rage
+    Level,               // damage equal to the user's level, e.g. Seismic Toss
+    TargetHpPercent(u8), // percent of the target's current hp, e.g. Super Fang
+    Retaliation,         // set by the move's handler from damage taken, e.g. Counter
+    OneHitKo,            // the target's current hp, e.g. Fissure
+}
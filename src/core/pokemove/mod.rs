@@ -1,5 +1,8 @@
+pub mod fixed_damage;
 pub mod move_category;
+pub mod move_heal;
 pub mod move_name;
 pub mod move_target;
+pub mod multi_turn;
 pub mod pokemove;
 pub mod secondary_effect;
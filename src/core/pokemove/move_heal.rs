@@ -0,0 +1,5 @@
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MoveHeal {
+    Percent(u8), // percent of the user's max hp
+    Weather,     // 1/2 normally, 2/3 in sun and 1/4 in other weather
+}
@@ -1,7 +1,10 @@
 use enum_map::Enum;
 use serde::{Deserialize, Serialize};
+use strum::{EnumCount, EnumIter};
 
-#[derive(Clone, Copy, Debug, Enum, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(
+    Clone, Copy, Debug, Enum, EnumCount, EnumIter, Serialize, Deserialize, PartialEq, Eq, Hash,
+)]
 pub enum MoveName {
     Empty,
     Struggle,
@@ -12,4 +15,98 @@ pub enum MoveName {
     Bite,
     Ember,
     WillOWisp,
+    SolarBeam,
+    SkyAttack,
+    HyperBeam,
+    Outrage,
+    Rollout,
+    IceBall,
+    Encore,
+    GigaDrain,
+    DrainPunch,
+    Recover,
+    Roost,
+    Synthesis,
+    SeismicToss,
+    NightShade,
+    SuperFang,
+    DragonRage,
+    GyroBall,
+    ElectroBall,
+    LowKick,
+    GrassKnot,
+    HeavySlam,
+    Eruption,
+    WaterSpout,
+    Facade,
+    Hex,
+    KnockOff,
+    Acrobatics,
+    StoredPower,
+    Reversal,
+    Return,
+    Frustration,
+    SuckerPunch,
+    Counter,
+    SwordsDance,
+    CloseCombat,
+    Rest,
+    Spikes,
+    StealthRock,
+    Reflect,
+    LightScreen,
+    BrickBreak,
+    SunnyDay,
+    RainDance,
+    Earthquake,
+    RockSlide,
+    HeatWave,
+    FollowMe,
+    RagePowder,
+    HelpingHand,
+    DoubleTeam,
+    Fissure,
+    SheerCold,
+    Spore,
+
+    // z-moves, used in place of a damaging move of the same type
+    BreakneckBlitz,
+    InfernoOverdrive,
+    HydroVortex,
+    GigavoltHavoc,
+    BloomDoom,
+    SubzeroSlammer,
+    AllOutPummeling,
+    AcidDownpour,
+    TectonicRage,
+    SupersonicSkystrike,
+    ShatteredPsyche,
+    SavageSpinOut,
+    ContinentalCrush,
+    NeverEndingNightmare,
+    DevastatingDrake,
+    BlackHoleEclipse,
+    CorkscrewCrash,
+    TwinkleTackle,
+
+    // max moves, used in place of every move while dynamaxed
+    MaxStrike,
+    MaxFlare,
+    MaxGeyser,
+    MaxLightning,
+    MaxOvergrowth,
+    MaxHailstorm,
+    MaxKnuckle,
+    MaxOoze,
+    MaxQuake,
+    MaxAirstream,
+    MaxMindstorm,
+    MaxFlutterby,
+    MaxRockfall,
+    MaxPhantasm,
+    MaxWyrmwind,
+    MaxDarkness,
+    MaxSteelspike,
+    MaxStarfall,
+    MaxGuard,
 }
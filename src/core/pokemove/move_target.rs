@@ -1,4 +1,37 @@
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum MoveTarget {
     User,
-    Opponent,
+    Opponent, // a single adjacent pokemon, an opponent unless an ally is chosen
+    Ally,
+    AllAdjacent,     // every pokemon next to the user, allies included
+    AllAdjacentFoes, // every opponent next to the user
+    UserSide,        // the user's side of the field, e.g. Reflect
+    OpponentSide,    // the opposing side of the field, e.g. Spikes
+    Field,           // the whole field, e.g. weather
+}
+
+impl MoveTarget {
+    // true if the move is aimed at a pokemon rather than a side or the field
+    pub fn targets_pokemon(&self) -> bool {
+        matches!(
+            self,
+            MoveTarget::User
+                | MoveTarget::Opponent
+                | MoveTarget::Ally
+                | MoveTarget::AllAdjacent
+                | MoveTarget::AllAdjacentFoes
+        )
+    }
+
+    pub fn targets_user_side(&self) -> bool {
+        matches!(
+            self,
+            MoveTarget::User | MoveTarget::Ally | MoveTarget::UserSide | MoveTarget::Field
+        )
+    }
+
+    // true if the move can hit more than one pokemon
+    pub fn is_spread(&self) -> bool {
+        matches!(self, MoveTarget::AllAdjacent | MoveTarget::AllAdjacentFoes)
+    }
 }
@@ -0,0 +1,7 @@
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MultiTurn {
+    Charge,   // charges on the first turn, strikes on the second
+    Recharge, // user must recharge on the turn after using it
+    Rampage,  // user is locked in for 2-3 turns, then becomes confused
+    Rollout,  // user is locked in for up to 5 turns, doubling power each hit
+}
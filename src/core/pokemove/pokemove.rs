@@ -1,19 +1,16 @@
-use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
-use crate::{
-    core::{
-        pokemon::{boostable_stat::BoostableStat, stat_enum::StatEnum},
-        pokemove::{
-            move_category::{self, MoveCategory},
-            move_name::MoveName,
-            move_target::MoveTarget,
-            secondary_effect::SecondaryEffect,
-        },
-        poketype::poketype::PokeType,
-        status::{status::Status, volatile_status::VolatileStatus},
+use crate::core::{
+    field::weather::Weather,
+    pokemon::boostable_stat::BoostableStat,
+    pokemove::{
+        fixed_damage::FixedDamage, move_category::MoveCategory, move_heal::MoveHeal,
+        move_name::MoveName, move_target::MoveTarget, multi_turn::MultiTurn,
+        secondary_effect::SecondaryEffect,
     },
-    event::event_handler::EventHandler,
+    poketype::poketype::PokeType,
+    side_condition::side_condition::SideCondition,
+    status::{status::Status, volatile_status::VolatileStatus},
 };
 
 #[derive(TypedBuilder)]
@@ -32,6 +29,14 @@ pub struct PokeMove {
     pub is_multi_hit: bool,
     #[builder(default)]
     pub recoil: Option<u8>,
+    #[builder(default)]
+    pub drain: Option<u8>,
+    #[builder(default)]
+    pub heal: Option<MoveHeal>,
+    #[builder(default)]
+    pub fixed_damage: Option<FixedDamage>,
+    #[builder(default)]
+    pub multi_turn: Option<MultiTurn>,
 
     #[builder(default)]
     pub secondary_effects: Option<Vec<(u8, Vec<SecondaryEffect>)>>,
@@ -42,7 +47,33 @@ pub struct PokeMove {
     pub volatile_status: Option<VolatileStatus>,
     #[builder(default)]
     pub boosts: Option<Vec<(BoostableStat, i8)>>,
+    #[builder(default)]
+    pub self_boosts: Option<Vec<(BoostableStat, i8)>>, // always applied to the user
+    #[builder(default)]
+    pub side_condition: Option<SideCondition>,
+    #[builder(default)]
+    pub weather: Option<Weather>,
 
     #[builder(default=MoveTarget::Opponent)]
     pub target: MoveTarget,
 }
+
+impl PokeMove {
+    pub fn is_ohko(&self) -> bool {
+        self.fixed_damage == Some(FixedDamage::OneHitKo)
+    }
+
+    // e.g. Double Team, banned by Evasion Clause
+    pub fn raises_user_evasion(&self) -> bool {
+        let raises_evasion = |boosts: &Option<Vec<(BoostableStat, i8)>>| {
+            boosts.as_ref().is_some_and(|boosts| {
+                boosts
+                    .iter()
+                    .any(|(stat, amount)| *stat == BoostableStat::Evasion && *amount > 0)
+            })
+        };
+
+        (self.target == MoveTarget::User && raises_evasion(&self.boosts))
+            || raises_evasion(&self.self_boosts)
+    }
+}
@@ -1,11 +1,13 @@
 use crate::core::{
-    pokemon::{boostable_stat::BoostableStat, stat_enum::StatEnum},
+    pokemon::boostable_stat::BoostableStat,
     status::{status::Status, volatile_status::VolatileStatus},
 };
 
 pub enum SecondaryEffect {
     Status(Status),
     VolatileStatus(VolatileStatus),
+    UserStatus(Status),
+    UserVolatileStatus(VolatileStatus),
     UserBoost(BoostableStat, i8),
     TargetBoost(BoostableStat, i8),
 }
@@ -15,12 +17,14 @@ impl SecondaryEffect {
         match self {
             SecondaryEffect::Status(_) => true,
             SecondaryEffect::VolatileStatus(_) => true,
+            SecondaryEffect::UserStatus(_) => false,
+            SecondaryEffect::UserVolatileStatus(_) => false,
             SecondaryEffect::UserBoost(_, _) => false,
             SecondaryEffect::TargetBoost(_, _) => true,
         }
     }
 
     pub fn affect_source(&self) -> bool {
-        return !self.affect_target();
+        !self.affect_target()
     }
 }
@@ -1,86 +1,214 @@
-use crate::core::poketype::poketype::PokeType;
+use std::sync::LazyLock;
 
-const TYPE_CHART: [[u8; 17]; 17] = [
-    /* ATK \ DEF:  NOR FIR WAT ELE GRA ICE FIG POI GRO FLY PSY BUG ROC GHO DRA DAR STL */
+use enum_map::{EnumMap, enum_map};
+use strum::IntoEnumIterator;
+
+use crate::core::{mechanics::generation::Generation, poketype::poketype::PokeType};
+
+const NUM_CHART_TYPES: usize = 18;
+
+type TypeChart = [[u8; NUM_CHART_TYPES]; NUM_CHART_TYPES];
+
+// Current matchups, older generations apply `CHART_REVISIONS` on top
+const TYPE_CHART: TypeChart = [
+    /* ATK \ DEF:  NOR FIR WAT ELE GRA ICE FIG POI GRO FLY PSY BUG ROC GHO DRA DAR STL FAI */
     /* Normal */
     [
-        10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 5, 0, 10, 10, 5,
+        10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 5, 0, 10, 10, 5, 10,
     ],
     /* Fire   */
     [
-        10, 5, 5, 10, 20, 20, 10, 10, 10, 10, 10, 20, 5, 10, 5, 10, 20,
+        10, 5, 5, 10, 20, 20, 10, 10, 10, 10, 10, 20, 5, 10, 5, 10, 20, 10,
     ],
     /* Water  */
     [
-        10, 20, 5, 10, 5, 10, 10, 10, 20, 10, 10, 10, 20, 10, 5, 10, 10,
+        10, 20, 5, 10, 5, 10, 10, 10, 20, 10, 10, 10, 20, 10, 5, 10, 10, 10,
     ],
     /* Electric*/
     [
-        10, 10, 20, 5, 5, 10, 10, 10, 0, 20, 10, 10, 10, 10, 5, 10, 10,
+        10, 10, 20, 5, 5, 10, 10, 10, 0, 20, 10, 10, 10, 10, 5, 10, 10, 10,
+    ],
+    /* Grass  */
+    [
+        10, 5, 20, 10, 5, 10, 10, 5, 20, 5, 10, 5, 20, 10, 5, 10, 5, 10,
     ],
-    /* Grass  */ [10, 5, 20, 10, 5, 10, 10, 5, 20, 5, 10, 5, 20, 10, 5, 10, 5],
     /* Ice    */
     [
-        10, 5, 5, 10, 20, 5, 10, 10, 20, 20, 10, 10, 10, 10, 20, 10, 5,
+        10, 5, 5, 10, 20, 5, 10, 10, 20, 20, 10, 10, 10, 10, 20, 10, 5, 10,
     ],
     /* Fighting*/
     [
-        20, 10, 10, 10, 10, 20, 10, 5, 10, 5, 5, 5, 20, 0, 10, 20, 20,
+        20, 10, 10, 10, 10, 20, 10, 5, 10, 5, 5, 5, 20, 0, 10, 20, 20, 5,
     ],
     /* Poison */
     [
-        10, 10, 10, 10, 20, 10, 10, 5, 5, 10, 10, 10, 5, 5, 10, 10, 0,
+        10, 10, 10, 10, 20, 10, 10, 5, 5, 10, 10, 10, 5, 5, 10, 10, 0, 20,
     ],
     /* Ground */
     [
-        10, 20, 10, 20, 5, 10, 10, 20, 10, 0, 10, 5, 20, 10, 10, 10, 20,
+        10, 20, 10, 20, 5, 10, 10, 20, 10, 0, 10, 5, 20, 10, 10, 10, 20, 10,
     ],
     /* Flying */
     [
-        10, 10, 10, 5, 20, 10, 20, 10, 10, 10, 10, 20, 5, 10, 10, 10, 5,
+        10, 10, 10, 5, 20, 10, 20, 10, 10, 10, 10, 20, 5, 10, 10, 10, 5, 10,
     ],
     /* Psychic*/
     [
-        10, 10, 10, 10, 10, 10, 20, 20, 10, 10, 5, 10, 10, 10, 10, 0, 5,
+        10, 10, 10, 10, 10, 10, 20, 20, 10, 10, 5, 10, 10, 10, 10, 0, 5, 10,
+    ],
+    /* Bug    */
+    [
+        10, 5, 10, 10, 20, 10, 5, 5, 10, 5, 20, 10, 10, 5, 10, 20, 5, 5,
     ],
-    /* Bug    */ [10, 5, 10, 10, 20, 10, 5, 5, 10, 5, 20, 10, 10, 5, 10, 20, 5],
     /* Rock   */
     [
-        10, 20, 10, 10, 10, 20, 5, 10, 5, 20, 10, 20, 10, 10, 10, 10, 5,
+        10, 20, 10, 10, 10, 20, 5, 10, 5, 20, 10, 20, 10, 10, 10, 10, 5, 10,
     ],
     /* Ghost  */
     [
-        0, 10, 10, 10, 10, 10, 10, 10, 10, 10, 20, 10, 10, 20, 10, 5, 5,
+        0, 10, 10, 10, 10, 10, 10, 10, 10, 10, 20, 10, 10, 20, 10, 5, 10, 10,
     ],
     /* Dragon */
     [
-        10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 20, 10, 5,
+        10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 20, 10, 5, 0,
     ],
     /* Dark   */
     [
-        10, 10, 10, 10, 10, 10, 5, 10, 10, 10, 20, 10, 10, 20, 10, 5, 5,
+        10, 10, 10, 10, 10, 10, 5, 10, 10, 10, 20, 10, 10, 20, 10, 5, 10, 5,
     ],
     /* Steel  */
     [
-        10, 5, 5, 5, 10, 20, 10, 10, 10, 10, 10, 10, 20, 10, 10, 10, 5,
+        10, 5, 5, 5, 10, 20, 10, 10, 10, 10, 10, 10, 20, 10, 10, 10, 5, 20,
+    ],
+    /* Fairy  */
+    [
+        10, 5, 10, 10, 10, 10, 20, 5, 10, 10, 10, 10, 10, 10, 20, 20, 5, 10,
     ],
 ];
 
-#[inline]
-pub fn type_effectiveness(attack: PokeType, defense: PokeType) -> f32 {
-    if attack == PokeType::Typeless || defense == PokeType::Typeless {
-        return 1.0;
+// Matchups that changed between generations, as the generation of the change,
+//  the attacking type, the defending type and the multiplier before the change
+const CHART_REVISIONS: [(Generation, PokeType, PokeType, u8); 2] = [
+    (Generation::Gen6, PokeType::Ghost, PokeType::Steel, 5),
+    (Generation::Gen6, PokeType::Dark, PokeType::Steel, 5),
+];
+
+static TYPE_CHARTS: LazyLock<EnumMap<Generation, TypeChart>> =
+    LazyLock::new(|| enum_map! { generation => build_type_chart(generation) });
+
+fn build_type_chart(generation: Generation) -> TypeChart {
+    let mut chart = TYPE_CHART;
+
+    for (changed_in, attack, defense, multiplier) in CHART_REVISIONS {
+        if generation < changed_in {
+            chart[attack.idx() - 1][defense.idx() - 1] = multiplier;
+        }
+    }
+
+    // types are neutral to and from everything before they were introduced
+    for poke_type in PokeType::iter().filter(|t| chart_idx(*t).is_some()) {
+        if poke_type.introduced_in() > generation {
+            let idx = poke_type.idx() - 1;
+            chart[idx] = [10; NUM_CHART_TYPES];
+            for row in chart.iter_mut() {
+                row[idx] = 10;
+            }
+        }
     }
 
-    // Subtract 1 for Typeless type
-    TYPE_CHART[attack.idx() - 1][defense.idx() - 1] as f32 / 10.0
+    chart
+}
+
+// Typeless and Stellar are not part of the chart and are neutral to everything
+fn chart_idx(poke_type: PokeType) -> Option<usize> {
+    match poke_type {
+        PokeType::Typeless | PokeType::Stellar => None,
+        // Subtract 1 for Typeless type
+        _ => Some(poke_type.idx() - 1),
+    }
+}
+
+fn get_chart_multiplier(attack: PokeType, defense: PokeType, generation: Generation) -> u8 {
+    match (chart_idx(attack), chart_idx(defense)) {
+        (Some(attack_idx), Some(defense_idx)) => TYPE_CHARTS[generation][attack_idx][defense_idx],
+        _ => 10,
+    }
+}
+
+#[inline]
+pub fn type_effectiveness(attack: PokeType, defense: PokeType, generation: Generation) -> f32 {
+    get_chart_multiplier(attack, defense, generation) as f32 / 10.0
 }
 
 #[inline]
-pub fn type_immunity(attack: PokeType, defense: PokeType) -> bool {
-    if attack == PokeType::Typeless || defense == PokeType::Typeless {
-        return false;
+pub fn type_immunity(attack: PokeType, defense: PokeType, generation: Generation) -> bool {
+    get_chart_multiplier(attack, defense, generation) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steel_resists_ghost_and_dark_before_gen_6() {
+        for attack in [PokeType::Ghost, PokeType::Dark] {
+            assert_eq!(
+                type_effectiveness(attack, PokeType::Steel, Generation::Gen5),
+                0.5
+            );
+            assert_eq!(
+                type_effectiveness(attack, PokeType::Steel, Generation::Gen6),
+                1.0
+            );
+        }
+    }
+
+    #[test]
+    fn fairy_is_neutral_before_it_was_introduced() {
+        assert!(!type_immunity(
+            PokeType::Dragon,
+            PokeType::Fairy,
+            Generation::Gen5
+        ));
+        assert_eq!(
+            type_effectiveness(PokeType::Fairy, PokeType::Dragon, Generation::Gen5),
+            1.0
+        );
+        assert!(type_immunity(
+            PokeType::Dragon,
+            PokeType::Fairy,
+            Generation::Gen6
+        ));
+        assert_eq!(
+            type_effectiveness(PokeType::Fairy, PokeType::Dragon, Generation::Gen9),
+            2.0
+        );
+    }
+
+    #[test]
+    fn unchanged_matchups_match_every_generation() {
+        for generation in [Generation::Gen4, Generation::Gen9] {
+            assert_eq!(
+                type_effectiveness(PokeType::Water, PokeType::Fire, generation),
+                2.0
+            );
+            assert!(type_immunity(PokeType::Normal, PokeType::Ghost, generation));
+        }
     }
 
-    TYPE_CHART[attack.idx() - 1][defense.idx() - 1] == 0
+    #[test]
+    fn typeless_and_stellar_are_neutral() {
+        for other in PokeType::iter() {
+            for generation in [Generation::Gen4, Generation::Gen9] {
+                assert_eq!(
+                    type_effectiveness(PokeType::Typeless, other, generation),
+                    1.0
+                );
+                assert_eq!(
+                    type_effectiveness(other, PokeType::Stellar, generation),
+                    1.0
+                );
+            }
+        }
+    }
 }
@@ -5,3 +5,18 @@ pub enum PokemonTyping {
     MonoType(PokeType),
     DualType(PokeType, PokeType),
 }
+
+impl PokemonTyping {
+    pub fn has_type(&self, poke_type: PokeType) -> bool {
+        match self {
+            PokemonTyping::MonoType(t) => *t == poke_type,
+            PokemonTyping::DualType(t1, t2) => *t1 == poke_type || *t2 == poke_type,
+        }
+    }
+
+    pub fn primary_type(&self) -> PokeType {
+        match self {
+            PokemonTyping::MonoType(t) | PokemonTyping::DualType(t, _) => *t,
+        }
+    }
+}
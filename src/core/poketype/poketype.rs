@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
+use strum::{EnumCount, EnumIter};
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+use crate::core::mechanics::generation::Generation;
+
+#[derive(Clone, Copy, Debug, EnumCount, EnumIter, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum PokeType {
     Typeless,
     Normal,
@@ -20,6 +23,8 @@ pub enum PokeType {
     Dragon,
     Dark,
     Steel,
+    Fairy,
+    Stellar, // only used as a tera type
 }
 
 impl PokeType {
@@ -27,4 +32,12 @@ impl PokeType {
     pub const fn idx(self) -> usize {
         self as usize
     }
+
+    pub fn introduced_in(&self) -> Generation {
+        match self {
+            PokeType::Fairy => Generation::Gen6,
+            PokeType::Stellar => Generation::Gen9,
+            _ => Generation::Gen4,
+        }
+    }
 }
@@ -0,0 +1 @@
+pub mod side_condition;
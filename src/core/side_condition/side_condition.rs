@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum SideCondition {
+    Spikes,
+    StealthRock,
+    Reflect,
+    LightScreen,
+}
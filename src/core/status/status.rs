@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use strum::EnumCount;
 
-#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug, EnumCount)]
 pub enum Status {
     Burn,
     Frozen,
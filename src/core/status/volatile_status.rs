@@ -1,9 +1,16 @@
 use serde::{Deserialize, Serialize};
+use strum::EnumCount;
 
-#[derive(Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash, EnumCount)]
 pub enum VolatileStatus {
     Confusion,
     Infatuation,
     LeechSeed,
     Flinch,
+    Encore,
+    Roost,
+    FollowMe,
+    RagePowder,
+    HelpingHand,
+    MaxGuard,
 }
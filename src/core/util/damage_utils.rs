@@ -1,12 +1,10 @@
-pub fn rounded_damage_from_modifiers_with_default(
-    modifiers: &Vec<f32>,
-    default: Option<u32>,
-) -> u32 {
+use typed_builder::TypedBuilder;
+
+pub fn rounded_damage_from_modifiers_with_default(modifiers: &[f32], default: Option<u32>) -> u32 {
     if modifiers.is_empty() {
-        if default.is_none() {
-            panic!("No modifiers provided")
-        } else {
-            return default.unwrap();
+        match default {
+            Some(default) => return default,
+            None => panic!("No modifiers provided"),
         }
     }
 
@@ -23,37 +21,97 @@ pub fn rounded_damage_from_modifiers_with_default(
     modifier.floor() as u32
 }
 
-pub fn rounded_damage_from_modifiers(modifiers: &Vec<f32>) -> u32 {
+pub fn rounded_damage_from_modifiers(modifiers: &[f32]) -> u32 {
     rounded_damage_from_modifiers_with_default(modifiers, None)
 }
 
-pub fn get_damage_for_move(
-    level: u8,
-    bp: u32,
-    atk: u32,
-    def: u32,
-    mod1: u32,
-    mod2: u32,
-    mod3: u32,
-    crit_mult: f32,
-    r: u32,
-    stab_mult: f32,
-    type1_mult: f32,
-    type2_mult: f32,
-) -> u32 {
+// Inputs to the damage formula once every query has been applied. The multipliers
+//  default to 1.0 and r to the highest roll
+#[derive(Clone, Copy, TypedBuilder)]
+pub struct DamageParams {
+    pub level: u8,
+    pub bp: u32,
+    pub atk: u32,
+    pub def: u32,
+    #[builder(default = 1.0)]
+    pub mod1: f32,
+    #[builder(default = 1.0)]
+    pub mod2: f32,
+    #[builder(default = 1.0)]
+    pub mod3: f32,
+    #[builder(default = 1.0)]
+    pub crit_mult: f32,
+    #[builder(default = 100)]
+    pub r: u32,
+    #[builder(default = 1.0)]
+    pub stab_mult: f32,
+    #[builder(default = 1.0)]
+    pub type1_mult: f32,
+    #[builder(default = 1.0)]
+    pub type2_mult: f32,
+}
+
+pub fn get_damage_for_move(params: &DamageParams) -> u32 {
+    let DamageParams {
+        level,
+        bp,
+        atk,
+        def,
+        mod1,
+        mod2,
+        mod3,
+        crit_mult,
+        r,
+        stab_mult,
+        type1_mult,
+        type2_mult,
+    } = *params;
+
     let inner1: f32 = (((level * 2) as f32) / 5.0).floor() + 2.0;
     let inner2: f32 =
-        ((((inner1 * (bp as f32)).floor() * (atk as f32)) / 50.0).floor() / (def as f32)).floor()
-            * (mod1 as f32)
+        (((((inner1 * (bp as f32)).floor() * (atk as f32)) / 50.0).floor() / (def as f32)).floor()
+            * mod1)
+            .floor()
             + 2.0;
-    ((((((((inner2 * crit_mult).floor() * (mod2 as f32)).floor() * (r as f32)).floor() / 100.0)
-        .floor()
+    ((((((((inner2 * crit_mult).floor() * mod2).floor() * (r as f32)).floor() / 100.0).floor()
         * stab_mult)
         .floor()
         * type1_mult)
         .floor()
         * type2_mult)
         .floor()
-        * (mod3 as f32))
+        * mod3)
         .floor() as u32
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modifiers_are_floored_after_each_step() {
+        assert_eq!(rounded_damage_from_modifiers(&[100.0, 1.5, 1.5]), 225);
+        assert_eq!(rounded_damage_from_modifiers(&[99.0, 0.5, 1.5]), 73);
+        assert_eq!(rounded_damage_from_modifiers_with_default(&[], Some(7)), 7);
+    }
+
+    #[test]
+    fn damage_formula_matches_known_calc() {
+        // level 100, 80 power, 200 attack into 100 defense with STAB on the top roll
+        let params = DamageParams::builder()
+            .level(100)
+            .bp(80)
+            .atk(200)
+            .def(100)
+            .stab_mult(1.5)
+            .build();
+        assert_eq!(get_damage_for_move(&params), 204);
+
+        let resisted = DamageParams {
+            type1_mult: 0.5,
+            r: 85,
+            ..params
+        };
+        assert_eq!(get_damage_for_move(&resisted), 86);
+    }
+}
@@ -11,3 +11,11 @@ pub fn calculate_hp_stat(level: u8, base_stat: u16, iv: u8, ev: u8) -> u16 {
 
     ((((2 * base_stat) + iv as u16 + ev_inc as u16) * level as u16) / 100) + level as u16 + 10
 }
+
+pub fn get_stat_boost_to_multiplier(boost: i8) -> f32 {
+    if boost >= 0 {
+        (2.0 + boost as f32) / 2.0
+    } else {
+        2.0 / (2 - boost) as f32
+    }
+}
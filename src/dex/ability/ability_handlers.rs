@@ -1,29 +1,24 @@
-use std::{
-    collections::HashMap,
-    rc::Rc,
-    sync::{Arc, LazyLock},
-};
-
-use enum_map::{EnumMap, enum_map};
+use std::sync::Arc;
 
 use crate::{
-    ability_map,
-    battle::state::BattleState,
-    core::{ability::ability::Ability, pokemon::pokemon::Pokemon, poketype::poketype::PokeType},
+    core::{
+        ability::ability::Ability, field::weather::Weather, pokemon::pokemon::Pokemon,
+        poketype::poketype::PokeType,
+    },
     dex::combined_handler::CombinedHandler,
-    event::event_handler::EventHandler,
     handler,
+    query::payload::Payload,
 };
 
 handler! {Blaze ( s, state ) {
     queries {
         OnBasePower ( payload ) => {
-            if payload.context.src_trainer != s.trainer_side {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
                 return
             }
 
             let is_boosted = {
-                let src_pokemon: &Pokemon = &state.get_active_pokemon(payload.context.src_trainer).pokemon;
+                let src_pokemon: &Pokemon = &state.get_active_pokemon(payload.context.src_trainer, payload.context.src_slot).pokemon;
 
                 let move_type = payload.context.pokemove.move_type;
                 move_type == PokeType::Fire && src_pokemon.hp <= src_pokemon.max_hp / 3
@@ -39,12 +34,12 @@ handler! {Blaze ( s, state ) {
 handler! {Torrent ( s, state ) {
     queries {
         OnBasePower ( payload ) => {
-            if payload.context.src_trainer != s.trainer_side {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
                 return
             }
 
             let is_boosted = {
-                let src_pokemon: &Pokemon = &state.get_active_pokemon(payload.context.src_trainer).pokemon;
+                let src_pokemon: &Pokemon = &state.get_active_pokemon(payload.context.src_trainer, payload.context.src_slot).pokemon;
 
                 let move_type = payload.context.pokemove.move_type;
                 move_type == PokeType::Water && src_pokemon.hp <= src_pokemon.max_hp / 3
@@ -61,12 +56,12 @@ handler! {Torrent ( s, state ) {
 handler! {Overgrow ( s, state ) {
     queries {
         OnBasePower ( payload ) => {
-            if payload.context.src_trainer != s.trainer_side {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
                 return
             }
 
             let is_boosted = {
-                let src_pokemon: &Pokemon = &state.get_active_pokemon(payload.context.src_trainer).pokemon;
+                let src_pokemon: &Pokemon = &state.get_active_pokemon(payload.context.src_trainer, payload.context.src_slot).pokemon;
 
                 let move_type = payload.context.pokemove.move_type;
                 move_type == PokeType::Grass && src_pokemon.hp <= src_pokemon.max_hp / 3
@@ -80,10 +75,99 @@ handler! {Overgrow ( s, state ) {
     }
 }}
 
-pub fn get_ability_handler(ability: &Ability, trainer_side: bool) -> Arc<dyn CombinedHandler> {
+handler! {ThickFat ( s, state ) {
+    queries {
+        OnBasePower ( payload ) => {
+            if !s.is_own_pokemon(payload.context.target_trainer, payload.context.target_slot) {
+                return
+            }
+
+            let move_type = payload.context.pokemove.move_type;
+            if move_type == PokeType::Fire || move_type == PokeType::Ice {
+                payload.get_vec_f32().push(0.5);
+            }
+        },
+    }
+}}
+
+handler! {Technician ( s, state ) {
+    queries {
+        OnBasePower ( payload ) => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return
+            }
+
+            // moves without a fixed power aren't boosted
+            let power = state.mechanics.get_move_power(payload.context.pokemove);
+            if power.is_some_and(|power| power <= 60) {
+                payload.get_vec_f32().push(1.5);
+            }
+        },
+    }
+}}
+
+handler! {SandForce ( s, state ) {
+    queries {
+        OnBasePower ( payload ) => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return
+            }
+
+            let move_type = payload.context.pokemove.move_type;
+            if state.weather == Some(Weather::Sand)
+                && matches!(move_type, PokeType::Rock | PokeType::Ground | PokeType::Steel)
+            {
+                payload.get_vec_f32().push(1.3);
+            }
+        },
+    }
+}}
+
+handler! {Adaptability ( s, state ) {
+    queries {
+        StabMult ( payload ) => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return
+            }
+
+            // 2x STAB becomes 2.25x after terastallizing into an original type
+            let stab_mult = if payload.get_f32() >= 2.0 { 2.25 } else { 2.0 };
+            payload.payload = Payload::F32(stab_mult);
+        },
+    }
+}}
+
+// Stands in for an ability the opposing trainer hasn't seen yet
+handler! {UnrevealedAbility}
+
+impl CombinedHandler for UnrevealedAbility {}
+
+pub fn get_unrevealed_ability_handler() -> Arc<dyn CombinedHandler> {
+    Arc::new(UnrevealedAbility)
+}
+
+pub fn get_ability_handler(
+    ability: &Ability,
+    trainer_side: bool,
+    slot: usize,
+) -> Arc<dyn CombinedHandler> {
     match ability {
-        Ability::Blaze => Arc::new(Blaze::new(trainer_side)) as Arc<dyn CombinedHandler>,
-        Ability::Overgrow => Arc::new(Overgrow::new(trainer_side)) as Arc<dyn CombinedHandler>,
-        Ability::Torrent => Arc::new(Torrent::new(trainer_side)) as Arc<dyn CombinedHandler>,
+        Ability::Blaze => Arc::new(Blaze::new(trainer_side, slot)) as Arc<dyn CombinedHandler>,
+        Ability::Overgrow => {
+            Arc::new(Overgrow::new(trainer_side, slot)) as Arc<dyn CombinedHandler>
+        }
+        Ability::Torrent => Arc::new(Torrent::new(trainer_side, slot)) as Arc<dyn CombinedHandler>,
+        Ability::ThickFat => {
+            Arc::new(ThickFat::new(trainer_side, slot)) as Arc<dyn CombinedHandler>
+        }
+        Ability::Technician => {
+            Arc::new(Technician::new(trainer_side, slot)) as Arc<dyn CombinedHandler>
+        }
+        Ability::SandForce => {
+            Arc::new(SandForce::new(trainer_side, slot)) as Arc<dyn CombinedHandler>
+        }
+        Ability::Adaptability => {
+            Arc::new(Adaptability::new(trainer_side, slot)) as Arc<dyn CombinedHandler>
+        }
     }
 }
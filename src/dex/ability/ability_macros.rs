@@ -16,6 +16,6 @@ macro_rules! ability_map {
 #[macro_export]
 macro_rules! register_abilities {
     ($name:ident) => {
-        Ability::$name => Arc::new($name::new(trainer_side)) as Arc<dyn CombinedHandler>,
+        Ability::$name => Arc::new($name::new(trainer_side, slot)) as Arc<dyn CombinedHandler>,
     }
 }
@@ -17,25 +17,33 @@ macro_rules! handler {
     ) => {
         struct $name {
             trainer_side: bool,
+            slot: usize, // active slot of the pokemon this handler belongs to
         }
 
         impl $name {
-            pub fn new(trainer_side: bool) -> Self {
+            pub fn new(trainer_side: bool, slot: usize) -> Self {
                 Self {
                     trainer_side,
+                    slot,
                 }
             }
+
+            #[allow(dead_code)]
+            fn is_own_pokemon(&self, trainer_side: bool, slot: usize) -> bool {
+                self.trainer_side == trainer_side && self.slot == slot
+            }
         }
 
-        impl crate::dex::combined_handler::CombinedHandler for $name {}
+        impl $crate::dex::combined_handler::CombinedHandler for $name {}
 
-        impl crate::query::query_handler::QueryHandler for $name {
-            fn handle(&self, query: &mut crate::query::query::Query, state: &mut crate::battle::state::BattleState) {
+        impl $crate::query::query_handler::QueryHandler for $name {
+            fn handle(&self, query: &mut $crate::query::query::Query, state: &mut $crate::battle::state::BattleState) {
+                #[allow(unused_variables)]
                 let $state_var = state;
                 let $self = self;
                 match query {
                     $(
-                        crate::query::query::Query::$variant_q($($pat_q)*) => {
+                        $crate::query::query::Query::$variant_q($($pat_q)*) => {
                             $handler_q
                         }
                     )*
@@ -44,13 +52,14 @@ macro_rules! handler {
             }
         }
 
-        impl crate::event::event_handler::EventHandler for $name {
-            fn handle(&self, event: &crate::event::event_type::Event, state: &mut crate::battle::state::BattleState) -> Vec<crate::event::event_handler_effect::EventHandlerEffect> {
+        impl $crate::event::event_handler::EventHandler for $name {
+            fn handle(&self, event: &$crate::event::event_type::Event, state: &mut $crate::battle::state::BattleState) -> Vec<$crate::event::event_handler_effect::EventHandlerEffect> {
+                #[allow(unused_variables)]
                 let $state_var = state;
                 let $self = self;
                 match event {
                     $(
-                        crate::event::event_type::Event::$variant_e $( ( $($pat_e)* ) )? => {
+                        $crate::event::event_type::Event::$variant_e $( ( $($pat_e)* ) )? => {
                             $handler_e
                         }
                     )*
@@ -59,42 +68,42 @@ macro_rules! handler {
             }
         }
 
-        impl crate::common::subscriber::Subscriber<crate::query::query::Query> for $name {
-            fn subscriptions(&self) -> &'static [crate::query::query::QueryKind] {
+        impl $crate::common::subscriber::Subscriber<$crate::query::query::Query> for $name {
+            fn subscriptions(&self) -> &'static [$crate::query::query::QueryKind] {
                 &[
                     $(
                         {
-                            crate::query::query::QueryKind::$variant_q
+                            $crate::query::query::QueryKind::$variant_q
                         }
                     ),*
                 ]
             }
 
-            fn priority(&self, kind: &crate::query::query::QueryKind) -> i32 {
+            fn priority(&self, kind: &$crate::query::query::QueryKind) -> i32 {
                 match kind {
                     $(
-                        crate::query::query::QueryKind::$variant_q => crate::__priority!($([priority = $priority_q])?),
+                        $crate::query::query::QueryKind::$variant_q => $crate::__priority!($([priority = $priority_q])?),
                     )*
                     _ => panic!("Query priority for unhandled query")
                 }
             }
         }
 
-        impl crate::common::subscriber::Subscriber<crate::event::event_type::Event> for $name {
-            fn subscriptions(&self) -> &'static [crate::event::event_type::EventKind] {
+        impl $crate::common::subscriber::Subscriber<$crate::event::event_type::Event> for $name {
+            fn subscriptions(&self) -> &'static [$crate::event::event_type::EventKind] {
                 &[
                     $(
                         {
-                            crate::event::event_type::EventKind::$variant_e
+                            $crate::event::event_type::EventKind::$variant_e
                         }
                     ),*
                 ]
             }
 
-            fn priority(&self, kind: &crate::event::event_type::EventKind) -> i32 {
+            fn priority(&self, kind: &$crate::event::event_type::EventKind) -> i32 {
                 match kind {
                     $(
-                        crate::event::event_type::EventKind::$variant_e => crate::__priority!($([priority = $priority_e])?),
+                        $crate::event::event_type::EventKind::$variant_e => $crate::__priority!($([priority = $priority_e])?),
                     )*
                     _ => panic!("Event priority for unhandled event")
                 }
@@ -114,25 +123,33 @@ macro_rules! handler {
     ) => {
         struct $name {
             trainer_side: bool,
+            slot: usize, // active slot of the pokemon this handler belongs to
         }
 
         impl $name {
-            pub fn new(trainer_side: bool) -> Self {
+            pub fn new(trainer_side: bool, slot: usize) -> Self {
                 Self {
                     trainer_side,
+                    slot,
                 }
             }
+
+            #[allow(dead_code)]
+            fn is_own_pokemon(&self, trainer_side: bool, slot: usize) -> bool {
+                self.trainer_side == trainer_side && self.slot == slot
+            }
         }
 
-        impl crate::dex::combined_handler::CombinedHandler for $name {}
+        impl $crate::dex::combined_handler::CombinedHandler for $name {}
 
-        impl crate::event::event_handler::EventHandler for $name {
-            fn handle(&self, event: &crate::event::event_type::Event, state: &mut crate::battle::state::BattleState) -> Vec<crate::event::event_handler_effect::EventHandlerEffect> {
+        impl $crate::event::event_handler::EventHandler for $name {
+            fn handle(&self, event: &$crate::event::event_type::Event, state: &mut $crate::battle::state::BattleState) -> Vec<$crate::event::event_handler_effect::EventHandlerEffect> {
+                #[allow(unused_variables)]
                 let $state_var = state;
                 let $self = self;
                 match event {
                     $(
-                        crate::event::event_type::Event::$variant_e $( ( $($pat_e)* ) )? => {
+                        $crate::event::event_type::Event::$variant_e $( ( $($pat_e)* ) )? => {
                             $handler_e
                         }
                     )*
@@ -141,28 +158,28 @@ macro_rules! handler {
             }
         }
 
-        impl crate::common::subscriber::Subscriber<crate::event::event_type::Event> for $name {
-            fn subscriptions(&self) -> &'static [crate::event::event_type::EventKind] {
+        impl $crate::common::subscriber::Subscriber<$crate::event::event_type::Event> for $name {
+            fn subscriptions(&self) -> &'static [$crate::event::event_type::EventKind] {
                 &[
                     $(
                         {
-                            crate::event::event_type::EventKind::$variant_e
+                            $crate::event::event_type::EventKind::$variant_e
                         }
                     ),*
                 ]
             }
 
-            fn priority(&self, kind: &crate::event::event_type::EventKind) -> i32 {
+            fn priority(&self, kind: &$crate::event::event_type::EventKind) -> i32 {
                 match kind {
                     $(
-                        crate::event::event_type::EventKind::$variant_e => crate::__priority!($([priority = $priority_e])?),
+                        $crate::event::event_type::EventKind::$variant_e => $crate::__priority!($([priority = $priority_e])?),
                     )*
                     _ => panic!("Event priority for unhandled event")
                 }
             }
         }
 
-        crate::impl_empty_query_handler!($name);
+        $crate::impl_empty_query_handler!($name);
     };
 
     // only queries
@@ -173,8 +190,8 @@ macro_rules! handler {
     // ) => {
 
     //     struct $name;
-    //     crate::impl_empty_event_handler!($name);
-    //     crate::impl_query_handler!($name, $( $query ),*);
+    //     $crate::impl_empty_event_handler!($name);
+    //     $crate::impl_query_handler!($name, $( $query ),*);
     // };
 
     ($name:ident ( $self:ident, $state_var:tt ) {
@@ -186,25 +203,33 @@ macro_rules! handler {
     }) => {
         struct $name {
             trainer_side: bool,
+            slot: usize, // active slot of the pokemon this handler belongs to
         }
 
         impl $name {
-            pub fn new(trainer_side: bool) -> Self {
+            pub fn new(trainer_side: bool, slot: usize) -> Self {
                 Self {
                     trainer_side,
+                    slot,
                 }
             }
+
+            #[allow(dead_code)]
+            fn is_own_pokemon(&self, trainer_side: bool, slot: usize) -> bool {
+                self.trainer_side == trainer_side && self.slot == slot
+            }
         }
 
-        impl crate::dex::combined_handler::CombinedHandler for $name {}
+        impl $crate::dex::combined_handler::CombinedHandler for $name {}
 
-        impl crate::query::query_handler::QueryHandler for $name {
-            fn handle(&self, query: &mut crate::query::query::Query, state: &mut crate::battle::state::BattleState) {
+        impl $crate::query::query_handler::QueryHandler for $name {
+            fn handle(&self, query: &mut $crate::query::query::Query, state: &mut $crate::battle::state::BattleState) {
+                #[allow(unused_variables)]
                 let $state_var = state;
                 let $self = self;
                 match query {
                     $(
-                        crate::query::query::Query::$variant($($pat)*) => {
+                        $crate::query::query::Query::$variant($($pat)*) => {
                             $handler
                         }
                     )*
@@ -213,28 +238,28 @@ macro_rules! handler {
             }
         }
 
-        impl crate::common::subscriber::Subscriber<crate::query::query::Query> for $name {
-            fn subscriptions(&self) -> &'static [crate::query::query::QueryKind] {
+        impl $crate::common::subscriber::Subscriber<$crate::query::query::Query> for $name {
+            fn subscriptions(&self) -> &'static [$crate::query::query::QueryKind] {
                 &[
                     $(
                         {
-                            crate::query::query::QueryKind::$variant
+                            $crate::query::query::QueryKind::$variant
                         }
                     ),*
                 ]
             }
 
-            fn priority(&self, kind: &crate::query::query::QueryKind) -> i32 {
+            fn priority(&self, kind: &$crate::query::query::QueryKind) -> i32 {
                 match kind {
                     $(
-                        crate::query::query::QueryKind::$variant => crate::__priority!($([priority = $priority])?),
+                        $crate::query::query::QueryKind::$variant => $crate::__priority!($([priority = $priority])?),
                     )*
                     _ => panic!("Query priority for unhandled query")
                 }
             }
         }
 
-        crate::impl_empty_event_handler!($name);
+        $crate::impl_empty_event_handler!($name);
     };
 
     // neither (empty ability)
@@ -242,8 +267,8 @@ macro_rules! handler {
         $name:ident
     ) => {
         struct $name;
-        crate::impl_empty_event_handler!($name);
-        crate::impl_empty_query_handler!($name);
+        $crate::impl_empty_event_handler!($name);
+        $crate::impl_empty_query_handler!($name);
     };
 }
 
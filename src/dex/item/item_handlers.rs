@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use crate::{
+    core::{item::item::Item, pokemon::stat_enum::StatEnum},
+    dex::combined_handler::CombinedHandler,
+    handler,
+};
+
+handler!(ChoiceBandHandler ( s, state ) {
+    queries {
+        OnStat( payload ) => {
+            if !s.is_own_pokemon(payload.trainer, payload.slot) || payload.stat != StatEnum::Attack {
+                return;
+            }
+
+            payload.mults.push(1.5);
+        }
+    }
+});
+
+handler!(ChoiceSpecsHandler ( s, state ) {
+    queries {
+        OnStat( payload ) => {
+            if !s.is_own_pokemon(payload.trainer, payload.slot) || payload.stat != StatEnum::SpecialAttack {
+                return;
+            }
+
+            payload.mults.push(1.5);
+        }
+    }
+});
+
+handler!(ChoiceScarfHandler ( s, state ) {
+    queries {
+        OnStat( payload ) => {
+            if !s.is_own_pokemon(payload.trainer, payload.slot) || payload.stat != StatEnum::Speed {
+                return;
+            }
+
+            payload.mults.push(1.5);
+        }
+    }
+});
+
+// Mega stones and Z crystals have no effect of their own in battle
+pub fn get_item_handler(
+    item: &Item,
+    trainer_side: bool,
+    slot: usize,
+) -> Option<Arc<dyn CombinedHandler>> {
+    match item {
+        Item::ChoiceBand => Some(Arc::new(ChoiceBandHandler::new(trainer_side, slot))),
+        Item::ChoiceSpecs => Some(Arc::new(ChoiceSpecsHandler::new(trainer_side, slot))),
+        Item::ChoiceScarf => Some(Arc::new(ChoiceScarfHandler::new(trainer_side, slot))),
+        _ => None,
+    }
+}
@@ -1,5 +1,8 @@
 pub mod ability;
 pub mod combined_handler;
 pub mod dex_macros;
+pub mod item;
+pub mod pokemon;
 pub mod pokemove;
+pub mod side_condition;
 pub mod status;
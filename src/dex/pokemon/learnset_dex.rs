@@ -0,0 +1,520 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use enum_map::{EnumMap, enum_map};
+
+use crate::core::{
+    pokemon::{learn_source::LearnSource, species::Species},
+    pokemove::move_name::MoveName,
+};
+
+type Learnset = HashMap<MoveName, Vec<LearnSource>>;
+
+// Learnsets use Showdown's codes: generation, then L<level>, M (machine), E (egg) or T (tutor)
+static LEARNSETS: LazyLock<EnumMap<Species, Learnset>> = LazyLock::new(|| {
+    enum_map! {
+        Species::Venusaur => learnset(&[
+            (MoveName::Tackle, "9L1 8L1 7L1"),
+            (MoveName::Growl, "9L1 8L1 7L1"),
+            (MoveName::GigaDrain, "9M 8M 7T"),
+            (MoveName::SolarBeam, "9M 8M 7M"),
+            (MoveName::Synthesis, "9L37 8L37 7L33"),
+            (MoveName::BulletSeed, "9M 8M"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::Earthquake, "9M 8M 7M"),
+            (MoveName::KnockOff, "9M 7T"),
+            (MoveName::HelpingHand, "9M 8M"),
+            (MoveName::GrassKnot, "9M 8M 7M"),
+            (MoveName::SwordsDance, "9M 8M 7M"),
+            (MoveName::Outrage, "9M 8M 7T"),
+            (MoveName::LightScreen, "9M 8M 7M"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Charizard => learnset(&[
+            (MoveName::Ember, "9L1 8L1 7L1"),
+            (MoveName::Growl, "9L1 8L1 7L1"),
+            (MoveName::WillOWisp, "9M 8M"),
+            (MoveName::SolarBeam, "9M 8M 7M"),
+            (MoveName::HeatWave, "9M 8M 7T"),
+            (MoveName::Outrage, "9M 8M 7T"),
+            (MoveName::Earthquake, "9M 8M 7M"),
+            (MoveName::Roost, "9M 7M"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::HelpingHand, "9M 8M"),
+            (MoveName::SwordsDance, "9M 8M 7M"),
+            (MoveName::Counter, "7T"),
+            (MoveName::SeismicToss, "7T"),
+            (MoveName::BrickBreak, "9M 8M 7M"),
+            (MoveName::Acrobatics, "9M 8M 7M"),
+            (MoveName::Reversal, "9M 8M"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Blastoise => learnset(&[
+            (MoveName::Tackle, "9L1 8L1 7L1"),
+            (MoveName::Bite, "9L12 8L12 7L1"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::Earthquake, "9M 8M 7M"),
+            (MoveName::GyroBall, "9M 8M 7M"),
+            (MoveName::Counter, "7T"),
+            (MoveName::SeismicToss, "7T"),
+            (MoveName::BrickBreak, "9M 8M 7M"),
+            (MoveName::IceBall, "7L1"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Pikachu => learnset(&[
+            (MoveName::Growl, "9L1 8L1 7L1"),
+            (MoveName::ElectroBall, "9L12 8L12 7L13"),
+            (MoveName::LightScreen, "9M 8M 7M"),
+            (MoveName::DoubleTeam, "9L8 8L8 7L23"),
+            (MoveName::GrassKnot, "9M 8M 7M"),
+            (MoveName::KnockOff, "9M 7T"),
+            (MoveName::HelpingHand, "9M 8M 7T"),
+            (MoveName::Encore, "9M 8M"),
+            (MoveName::Reversal, "9M 8M 7E"),
+            (MoveName::BrickBreak, "9M 8M 7M"),
+            (MoveName::SeismicToss, "7T"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Alakazam => learnset(&[
+            (MoveName::Recover, "9L1 8L1 7L1"),
+            (MoveName::Reflect, "9M 8M 7M"),
+            (MoveName::LightScreen, "9M 8M 7M"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::GrassKnot, "9M 8M 7M"),
+            (MoveName::Encore, "9M 8M 7T"),
+            (MoveName::KnockOff, "9M 7T"),
+            (MoveName::SeismicToss, "7T"),
+            (MoveName::Counter, "7T"),
+            (MoveName::StoredPower, "9M 8M"),
+            (MoveName::HelpingHand, "9M 8M 7T"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Machamp => learnset(&[
+            (MoveName::LowKick, "9L1 8L1 7L1"),
+            (MoveName::SeismicToss, "9L24 8L24 7L19 7T"),
+            (MoveName::CloseCombat, "9M 8M"),
+            (MoveName::BrickBreak, "9M 8M 7M"),
+            (MoveName::DrainPunch, "9M 8M 7T"),
+            (MoveName::KnockOff, "9M 7T"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::Earthquake, "9M 8M 7M"),
+            (MoveName::RockSlide, "9M 8M 7M"),
+            (MoveName::Reversal, "9M 8M"),
+            (MoveName::HeavySlam, "9M 8M"),
+            (MoveName::Counter, "7T"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Gengar => learnset(&[
+            (MoveName::NightShade, "9M 8L1 7L1"),
+            (MoveName::Hex, "9M 8L20 7L24"),
+            (MoveName::SuckerPunch, "9L1 8L1 7L1"),
+            (MoveName::WillOWisp, "9M 8M 7M"),
+            (MoveName::Encore, "9M 8M 7T"),
+            (MoveName::KnockOff, "9M 7T"),
+            (MoveName::GigaDrain, "9M 8M 7T"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::Counter, "7T"),
+            (MoveName::SeismicToss, "7T"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Starmie => learnset(&[
+            (MoveName::Tackle, "9L1 8L1 7L1"),
+            (MoveName::Recover, "9L1 8L1 7L1"),
+            (MoveName::LightScreen, "9M 8M 7M"),
+            (MoveName::Reflect, "9M 8M 7M"),
+            (MoveName::GyroBall, "9M 8M 7M"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Snorlax => learnset(&[
+            (MoveName::Tackle, "9L1 8L1 7L1"),
+            (MoveName::Rest, "9L28 8L28 7L25 9M 8M 7M"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::Earthquake, "9M 8M 7M"),
+            (MoveName::RockSlide, "9M 8M 7M"),
+            (MoveName::HeavySlam, "9L48 8L48 9M 8M"),
+            (MoveName::Counter, "9E 8E 7E 7T"),
+            (MoveName::SeismicToss, "7T"),
+            (MoveName::BrickBreak, "9M 8M 7M"),
+            (MoveName::DrainPunch, "9M 8M 7T"),
+            (MoveName::SolarBeam, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Dragonite => learnset(&[
+            (MoveName::Outrage, "9L75 8L75 7L61 9M 8M 7T"),
+            (MoveName::Earthquake, "9M 8M 7M"),
+            (MoveName::Roost, "9M 7M"),
+            (MoveName::HeatWave, "9M 8M 7T"),
+            (MoveName::HyperBeam, "9L80 8L80 7L75 9M 8M 7M"),
+            (MoveName::LowKick, "9M 8M 7T"),
+            (MoveName::BrickBreak, "9M 8M 7M"),
+            (MoveName::HeavySlam, "9M 8M"),
+            (MoveName::DragonRage, "7L1"),
+            (MoveName::Acrobatics, "9M 8M 7M"),
+            (MoveName::Counter, "7T"),
+            (MoveName::SeismicToss, "7T"),
+            (MoveName::HelpingHand, "9M 8M"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Tyranitar => learnset(&[
+            (MoveName::Bite, "9L1 8L1 7L1"),
+            (MoveName::SandAttack, "9L1 8L1 7L1"),
+            (MoveName::Outrage, "9M 8M 7T"),
+            (MoveName::Earthquake, "9M 8M 7M"),
+            (MoveName::RockSlide, "9M 8M 7M"),
+            (MoveName::StealthRock, "9M 8M 7T"),
+            (MoveName::KnockOff, "9M 7T"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::Counter, "7T"),
+            (MoveName::SeismicToss, "7T"),
+            (MoveName::LowKick, "9M 8M 7T"),
+            (MoveName::BrickBreak, "9M 8M 7M"),
+            (MoveName::HeavySlam, "9M 8M"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Skarmory => learnset(&[
+            (MoveName::SandAttack, "9L1 8L1 7L1"),
+            (MoveName::Spikes, "9M 8L40 7L39"),
+            (MoveName::StealthRock, "9M 8M 7T"),
+            (MoveName::Roost, "9M 8L1 7M"),
+            (MoveName::SkyAttack, "9L52 8L52 7T"),
+            (MoveName::Acrobatics, "9M 8M 7M"),
+            (MoveName::BrickBreak, "9M 8M 7M"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Scizor => learnset(&[
+            (MoveName::SwordsDance, "9L44 8L44 7L50 9M 8M 7M"),
+            (MoveName::KnockOff, "9M 7T"),
+            (MoveName::Roost, "9M 7M"),
+            (MoveName::BrickBreak, "9M 8M 7M"),
+            (MoveName::CloseCombat, "9M 8M"),
+            (MoveName::Acrobatics, "9M 8M 7M"),
+            (MoveName::DoubleTeam, "9L12 8L12 7L13"),
+            (MoveName::Reversal, "9E 8E 7E"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::LowKick, "9M 8M 7T"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Heatran => learnset(&[
+            (MoveName::HeatWave, "9M 8M 7T"),
+            (MoveName::WillOWisp, "9M 8M 7M"),
+            (MoveName::StealthRock, "9M 8M 7T"),
+            (MoveName::Earthquake, "9M 8M 7M"),
+            (MoveName::SolarBeam, "9M 8M 7M"),
+            (MoveName::HeavySlam, "9M 8M"),
+            (MoveName::RockSlide, "9M 8M 7M"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Garchomp => learnset(&[
+            (MoveName::SandAttack, "9L1 8L1 7L1"),
+            (MoveName::Bite, "9L12 8L12 7L15"),
+            (MoveName::Outrage, "9L0 8L0 7L1 9M 8M 7T"),
+            (MoveName::Earthquake, "9M 8M 7M"),
+            (MoveName::SwordsDance, "9M 8M 7M"),
+            (MoveName::StealthRock, "9M 8M 7T"),
+            (MoveName::RockSlide, "9M 8M 7M"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::BrickBreak, "9M 8M 7M"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Lucario => learnset(&[
+            (MoveName::Counter, "9L1 8L1 7L1 7T"),
+            (MoveName::CloseCombat, "9L60 8L60 7L55 9M 8M"),
+            (MoveName::SwordsDance, "9M 8M 7M"),
+            (MoveName::DrainPunch, "9M 8M 7T"),
+            (MoveName::BrickBreak, "9M 8M 7M"),
+            (MoveName::Reversal, "9L1 8L1 7L1"),
+            (MoveName::HeavySlam, "9M 8M"),
+            (MoveName::LowKick, "9M 8M 7T"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::Earthquake, "9M 8M 7M"),
+            (MoveName::HelpingHand, "9M 8M 7T"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Ferrothorn => learnset(&[
+            (MoveName::Tackle, "9L1 8L1 7L1"),
+            (MoveName::GyroBall, "9L30 8L30 7L21 9M 8M 7M"),
+            (MoveName::Spikes, "9M 8L40 7L46"),
+            (MoveName::StealthRock, "9M 8M 7T"),
+            (MoveName::GigaDrain, "9M 8M 7T"),
+            (MoveName::BulletSeed, "9M 8M 7L1"),
+            (MoveName::HeavySlam, "9M 8M"),
+            (MoveName::KnockOff, "9M 7T"),
+            (MoveName::SolarBeam, "9M 8M 7M"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        Species::Amoonguss => learnset(&[
+            (MoveName::Spore, "9L54 8L54 7L54"),
+            (MoveName::RagePowder, "9L36 8L36 7L35"),
+            (MoveName::GigaDrain, "9L40 8L40 7L39 9M 8M 7T"),
+            (MoveName::Growl, "9L1 8L1 7L1"),
+            (MoveName::Synthesis, "9E 8E 7E"),
+            (MoveName::GrassKnot, "9M 8M 7M"),
+            (MoveName::SolarBeam, "9M 8M 7M"),
+            (MoveName::HyperBeam, "9M 8M 7M"),
+            (MoveName::Rest, "9M 8M 7M"),
+            (MoveName::Facade, "9M 8M 7M"),
+            (MoveName::Return, "7M"),
+            (MoveName::Frustration, "7M"),
+            (MoveName::DoubleTeam, "7M"),
+            (MoveName::SunnyDay, "9M 8M 7M"),
+            (MoveName::RainDance, "9M 8M 7M"),
+        ]),
+        // mega evolutions can't be brought to a battle, so they never need a learnset
+        Species::VenusaurMega => learnset(&[]),
+        Species::ScizorMega => learnset(&[]),
+        Species::GarchompMega => learnset(&[]),
+        Species::LucarioMega => learnset(&[]),
+    }
+});
+
+fn learnset(entries: &[(MoveName, &str)]) -> Learnset {
+    entries
+        .iter()
+        .map(|(move_name, codes)| {
+            let sources = codes
+                .split_whitespace()
+                .map(|code| {
+                    LearnSource::from_code(code)
+                        .unwrap_or_else(|| panic!("Invalid learnset code {code} for {move_name:?}"))
+                })
+                .collect();
+            (*move_name, sources)
+        })
+        .collect()
+}
+
+pub fn get_learn_sources(species: &Species, move_name: &MoveName) -> &'static [LearnSource] {
+    LEARNSETS[*species]
+        .get(move_name)
+        .map_or(&[], |sources| sources.as_slice())
+}
+
+// The learnsets only go back to gen 7
+const OLDEST_LEARNSET_GENERATION: u8 = 7;
+
+// None when there is no learnset data for the generation. Gen 8 cut off transfers from
+//  earlier generations, so with data starting at gen 7 a move has to be learnable in the
+//  generation itself
+pub fn can_learn(species: &Species, move_name: &MoveName, generation: u8) -> Option<bool> {
+    if generation < OLDEST_LEARNSET_GENERATION {
+        return None;
+    }
+
+    Some(
+        get_learn_sources(species, move_name)
+            .iter()
+            .any(|source| source.generation == generation),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::{mechanics::generation::Generation, pokemon::learn_source::LearnMethod},
+        format::{format::Format, format_violation::FormatViolation, team_validator},
+        showdown::text,
+    };
+
+    #[test]
+    fn learnsets_parse_every_source() {
+        // building the learnsets panics on a bad code
+        LazyLock::force(&LEARNSETS);
+        assert_eq!(
+            get_learn_sources(&Species::Venusaur, &MoveName::Synthesis),
+            &[
+                LearnSource {
+                    generation: 9,
+                    method: LearnMethod::LevelUp(37)
+                },
+                LearnSource {
+                    generation: 8,
+                    method: LearnMethod::LevelUp(37)
+                },
+                LearnSource {
+                    generation: 7,
+                    method: LearnMethod::LevelUp(33)
+                },
+            ]
+        );
+        assert!(get_learn_sources(&Species::Venusaur, &MoveName::Spore).is_empty());
+    }
+
+    #[test]
+    fn moves_must_be_learnable_in_the_generation() {
+        // Return was only a gen 7 machine
+        assert_eq!(
+            can_learn(&Species::Snorlax, &MoveName::Return, 7),
+            Some(true)
+        );
+        assert_eq!(
+            can_learn(&Species::Snorlax, &MoveName::Return, 8),
+            Some(false)
+        );
+        assert_eq!(
+            can_learn(&Species::Snorlax, &MoveName::Return, 9),
+            Some(false)
+        );
+
+        // Bullet Seed came to Venusaur in gen 8, so it can't be used in gen 7
+        assert_eq!(
+            can_learn(&Species::Venusaur, &MoveName::BulletSeed, 7),
+            Some(false)
+        );
+        assert_eq!(
+            can_learn(&Species::Venusaur, &MoveName::BulletSeed, 9),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn generations_without_learnsets_have_no_answer() {
+        assert_eq!(can_learn(&Species::Snorlax, &MoveName::Tackle, 4), None);
+        assert_eq!(can_learn(&Species::Snorlax, &MoveName::Tackle, 6), None);
+    }
+
+    #[test]
+    fn unlearnable_moves_are_validation_errors() {
+        let team = text::parse_team(
+            "Snorlax\nAbility: Thick Fat\n- Tackle\n- Return\n\n\
+            Amoonguss\nAbility: Overgrow\n- Spore",
+        )
+        .unwrap();
+        let get_errors = |generation: Generation| {
+            let format = Format::builder().generation(generation).build();
+            team_validator::validate_team(&format, &team)
+        };
+
+        assert_eq!(get_errors(Generation::Gen7), vec![]);
+        assert_eq!(
+            get_errors(Generation::Gen9),
+            vec![team_validator::TeamValidationError::Format(
+                FormatViolation::UnlearnableMove {
+                    idx: 0,
+                    species: Species::Snorlax,
+                    move_name: MoveName::Return
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn gen_4_move_legality_is_reported_as_unknown() {
+        let team = text::parse_team(
+            "Snorlax\nAbility: Thick Fat\n- Return\n- Rest\n- Earthquake\n- Counter\n\n\
+            Garchomp\nAbility: Sand Force\n- Rock Slide\n- Earthquake\n- Outrage\n- Bite",
+        )
+        .unwrap();
+        let format = Format::builder().generation(Generation::Gen4).build();
+
+        let errors = team_validator::validate_team(&format, &team);
+        assert_eq!(errors.len(), 8);
+        assert!(errors.iter().all(|error| matches!(
+            error,
+            team_validator::TeamValidationError::Format(
+                FormatViolation::UnknownMoveLegality { .. }
+            )
+        )));
+    }
+}
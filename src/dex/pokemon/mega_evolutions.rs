@@ -0,0 +1,42 @@
+use crate::core::{ability::ability::Ability, item::item::Item, pokemon::species::Species};
+
+pub struct MegaEvolution {
+    pub species: Species,
+    pub mega_stone: Item,
+    pub mega_species: Species,
+    pub ability: Ability,
+}
+
+const MEGA_EVOLUTIONS: [MegaEvolution; 4] = [
+    MegaEvolution {
+        species: Species::Venusaur,
+        mega_stone: Item::Venusaurite,
+        mega_species: Species::VenusaurMega,
+        ability: Ability::ThickFat,
+    },
+    MegaEvolution {
+        species: Species::Scizor,
+        mega_stone: Item::Scizorite,
+        mega_species: Species::ScizorMega,
+        ability: Ability::Technician,
+    },
+    MegaEvolution {
+        species: Species::Garchomp,
+        mega_stone: Item::Garchompite,
+        mega_species: Species::GarchompMega,
+        ability: Ability::SandForce,
+    },
+    MegaEvolution {
+        species: Species::Lucario,
+        mega_stone: Item::Lucarionite,
+        mega_species: Species::LucarioMega,
+        ability: Ability::Adaptability,
+    },
+];
+
+// The mega evolution a species can reach while holding the item, if any
+pub fn get_mega_evolution(species: &Species, item: &Item) -> Option<&'static MegaEvolution> {
+    MEGA_EVOLUTIONS
+        .iter()
+        .find(|mega| mega.species == *species && mega.mega_stone == *item)
+}
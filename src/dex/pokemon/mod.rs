@@ -0,0 +1,3 @@
+pub mod learnset_dex;
+pub mod mega_evolutions;
+pub mod pokedex;
@@ -0,0 +1,260 @@
+use std::sync::LazyLock;
+
+use enum_map::{EnumMap, enum_map};
+
+use crate::core::{
+    pokemon::{base_pokemon::BasePokemon, species::Species},
+    poketype::{pokemon_typing::PokemonTyping, poketype::PokeType},
+};
+
+static POKEDEX: LazyLock<EnumMap<Species, BasePokemon>> = LazyLock::new(|| {
+    enum_map! {
+        Species::Venusaur => BasePokemon {
+            species: Species::Venusaur,
+            hp: 80,
+            attack: 82,
+            spattack: 100,
+            defense: 83,
+            spdefense: 100,
+            speed: 80,
+            typing: PokemonTyping::DualType(PokeType::Grass, PokeType::Poison),
+            weight: 100.0,
+        },
+        Species::Charizard => BasePokemon {
+            species: Species::Charizard,
+            hp: 78,
+            attack: 84,
+            spattack: 109,
+            defense: 78,
+            spdefense: 85,
+            speed: 100,
+            typing: PokemonTyping::DualType(PokeType::Fire, PokeType::Flying),
+            weight: 90.5,
+        },
+        Species::Blastoise => BasePokemon {
+            species: Species::Blastoise,
+            hp: 79,
+            attack: 83,
+            spattack: 85,
+            defense: 100,
+            spdefense: 105,
+            speed: 78,
+            typing: PokemonTyping::MonoType(PokeType::Water),
+            weight: 85.5,
+        },
+        Species::Pikachu => BasePokemon {
+            species: Species::Pikachu,
+            hp: 35,
+            attack: 55,
+            spattack: 50,
+            defense: 40,
+            spdefense: 50,
+            speed: 90,
+            typing: PokemonTyping::MonoType(PokeType::Electric),
+            weight: 6.0,
+        },
+        Species::Alakazam => BasePokemon {
+            species: Species::Alakazam,
+            hp: 55,
+            attack: 50,
+            spattack: 135,
+            defense: 45,
+            spdefense: 95,
+            speed: 120,
+            typing: PokemonTyping::MonoType(PokeType::Psychic),
+            weight: 48.0,
+        },
+        Species::Machamp => BasePokemon {
+            species: Species::Machamp,
+            hp: 90,
+            attack: 130,
+            spattack: 65,
+            defense: 80,
+            spdefense: 85,
+            speed: 55,
+            typing: PokemonTyping::MonoType(PokeType::Fighting),
+            weight: 130.0,
+        },
+        Species::Gengar => BasePokemon {
+            species: Species::Gengar,
+            hp: 60,
+            attack: 65,
+            spattack: 130,
+            defense: 60,
+            spdefense: 75,
+            speed: 110,
+            typing: PokemonTyping::DualType(PokeType::Ghost, PokeType::Poison),
+            weight: 40.5,
+        },
+        Species::Starmie => BasePokemon {
+            species: Species::Starmie,
+            hp: 60,
+            attack: 75,
+            spattack: 100,
+            defense: 85,
+            spdefense: 85,
+            speed: 115,
+            typing: PokemonTyping::DualType(PokeType::Water, PokeType::Psychic),
+            weight: 80.0,
+        },
+        Species::Snorlax => BasePokemon {
+            species: Species::Snorlax,
+            hp: 160,
+            attack: 110,
+            spattack: 65,
+            defense: 65,
+            spdefense: 110,
+            speed: 30,
+            typing: PokemonTyping::MonoType(PokeType::Normal),
+            weight: 460.0,
+        },
+        Species::Dragonite => BasePokemon {
+            species: Species::Dragonite,
+            hp: 91,
+            attack: 134,
+            spattack: 100,
+            defense: 95,
+            spdefense: 100,
+            speed: 80,
+            typing: PokemonTyping::DualType(PokeType::Dragon, PokeType::Flying),
+            weight: 210.0,
+        },
+        Species::Tyranitar => BasePokemon {
+            species: Species::Tyranitar,
+            hp: 100,
+            attack: 134,
+            spattack: 95,
+            defense: 110,
+            spdefense: 100,
+            speed: 61,
+            typing: PokemonTyping::DualType(PokeType::Rock, PokeType::Dark),
+            weight: 202.0,
+        },
+        Species::Skarmory => BasePokemon {
+            species: Species::Skarmory,
+            hp: 65,
+            attack: 80,
+            spattack: 40,
+            defense: 140,
+            spdefense: 70,
+            speed: 70,
+            typing: PokemonTyping::DualType(PokeType::Steel, PokeType::Flying),
+            weight: 50.5,
+        },
+        Species::Scizor => BasePokemon {
+            species: Species::Scizor,
+            hp: 70,
+            attack: 130,
+            spattack: 55,
+            defense: 100,
+            spdefense: 80,
+            speed: 65,
+            typing: PokemonTyping::DualType(PokeType::Bug, PokeType::Steel),
+            weight: 118.0,
+        },
+        Species::Heatran => BasePokemon {
+            species: Species::Heatran,
+            hp: 91,
+            attack: 90,
+            spattack: 130,
+            defense: 106,
+            spdefense: 106,
+            speed: 77,
+            typing: PokemonTyping::DualType(PokeType::Fire, PokeType::Steel),
+            weight: 430.0,
+        },
+        Species::Garchomp => BasePokemon {
+            species: Species::Garchomp,
+            hp: 108,
+            attack: 130,
+            spattack: 80,
+            defense: 95,
+            spdefense: 85,
+            speed: 102,
+            typing: PokemonTyping::DualType(PokeType::Dragon, PokeType::Ground),
+            weight: 95.0,
+        },
+        Species::Lucario => BasePokemon {
+            species: Species::Lucario,
+            hp: 70,
+            attack: 110,
+            spattack: 115,
+            defense: 70,
+            spdefense: 70,
+            speed: 90,
+            typing: PokemonTyping::DualType(PokeType::Fighting, PokeType::Steel),
+            weight: 54.0,
+        },
+        Species::Ferrothorn => BasePokemon {
+            species: Species::Ferrothorn,
+            hp: 74,
+            attack: 94,
+            spattack: 54,
+            defense: 131,
+            spdefense: 116,
+            speed: 20,
+            typing: PokemonTyping::DualType(PokeType::Grass, PokeType::Steel),
+            weight: 110.0,
+        },
+        Species::Amoonguss => BasePokemon {
+            species: Species::Amoonguss,
+            hp: 114,
+            attack: 85,
+            spattack: 85,
+            defense: 70,
+            spdefense: 80,
+            speed: 30,
+            typing: PokemonTyping::DualType(PokeType::Grass, PokeType::Poison),
+            weight: 10.5,
+        },
+        Species::VenusaurMega => BasePokemon {
+            species: Species::VenusaurMega,
+            hp: 80,
+            attack: 100,
+            spattack: 122,
+            defense: 123,
+            spdefense: 120,
+            speed: 80,
+            typing: PokemonTyping::DualType(PokeType::Grass, PokeType::Poison),
+            weight: 155.5,
+        },
+        Species::ScizorMega => BasePokemon {
+            species: Species::ScizorMega,
+            hp: 70,
+            attack: 150,
+            spattack: 65,
+            defense: 140,
+            spdefense: 100,
+            speed: 75,
+            typing: PokemonTyping::DualType(PokeType::Bug, PokeType::Steel),
+            weight: 125.0,
+        },
+        Species::GarchompMega => BasePokemon {
+            species: Species::GarchompMega,
+            hp: 108,
+            attack: 170,
+            spattack: 120,
+            defense: 115,
+            spdefense: 95,
+            speed: 92,
+            typing: PokemonTyping::DualType(PokeType::Dragon, PokeType::Ground),
+            weight: 95.0,
+        },
+        Species::LucarioMega => BasePokemon {
+            species: Species::LucarioMega,
+            hp: 70,
+            attack: 145,
+            spattack: 140,
+            defense: 88,
+            spdefense: 70,
+            speed: 112,
+            typing: PokemonTyping::DualType(PokeType::Fighting, PokeType::Steel),
+            weight: 57.5,
+        },
+    }
+});
+
+#[inline]
+pub fn get_base_pokemon(species: &Species) -> &'static BasePokemon {
+    &POKEDEX[*species]
+}
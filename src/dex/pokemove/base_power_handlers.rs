@@ -0,0 +1,375 @@
+use std::sync::Arc;
+
+use crate::{
+    battle::state::BattleState,
+    core::{pokemon::stat_enum::StatEnum, pokemove::move_name::MoveName, status::status::Status},
+    dex::combined_handler::CombinedHandler,
+    handler,
+};
+
+// Handlers that compute base power are registered with a higher priority than
+// StaticBattleHandler so that the base power is the first modifier.
+const BASE_POWER_PRIORITY: i32 = 1;
+
+fn has_status(state: &BattleState, trainer: bool, slot: usize) -> bool {
+    state
+        .get_active_pokemon(trainer, slot)
+        .status
+        .is_some_and(|status| status != Status::Faint)
+}
+
+handler!(GyroBallHandler ( s, state ) {
+    queries {
+        OnBasePower( payload ) [priority = BASE_POWER_PRIORITY] => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            let user_speed = state.get_active_pokemon(s.trainer_side, s.slot).get_boosted_stat(StatEnum::Speed);
+            let target_speed = state
+                .get_active_pokemon(payload.context.target_trainer, payload.context.target_slot)
+                .get_boosted_stat(StatEnum::Speed);
+
+            let power = ((25.0 * target_speed / user_speed.max(1.0)).floor() + 1.0).min(150.0);
+            payload.get_vec_f32().push(power);
+        }
+    }
+});
+
+handler!(ElectroBallHandler ( s, state ) {
+    queries {
+        OnBasePower( payload ) [priority = BASE_POWER_PRIORITY] => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            let user_speed = state.get_active_pokemon(s.trainer_side, s.slot).get_boosted_stat(StatEnum::Speed);
+            let target_speed = state
+                .get_active_pokemon(payload.context.target_trainer, payload.context.target_slot)
+                .get_boosted_stat(StatEnum::Speed);
+            let ratio = user_speed / target_speed.max(1.0);
+
+            let power = if ratio >= 4.0 {
+                150.0
+            } else if ratio >= 3.0 {
+                120.0
+            } else if ratio >= 2.0 {
+                80.0
+            } else if ratio >= 1.0 {
+                60.0
+            } else {
+                40.0
+            };
+            payload.get_vec_f32().push(power);
+        }
+    }
+});
+
+// Low Kick and Grass Knot
+handler!(TargetWeightHandler ( s, state ) {
+    queries {
+        OnBasePower( payload ) [priority = BASE_POWER_PRIORITY] => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            let target_weight = state.get_active_pokemon(payload.context.target_trainer, payload.context.target_slot).pokemon.weight;
+
+            let power = if target_weight < 10.0 {
+                20.0
+            } else if target_weight < 25.0 {
+                40.0
+            } else if target_weight < 50.0 {
+                60.0
+            } else if target_weight < 100.0 {
+                80.0
+            } else if target_weight < 200.0 {
+                100.0
+            } else {
+                120.0
+            };
+            payload.get_vec_f32().push(power);
+        }
+    }
+});
+
+handler!(HeavySlamHandler ( s, state ) {
+    queries {
+        OnBasePower( payload ) [priority = BASE_POWER_PRIORITY] => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            let user_weight = state.get_active_pokemon(s.trainer_side, s.slot).pokemon.weight;
+            let target_weight = state.get_active_pokemon(payload.context.target_trainer, payload.context.target_slot).pokemon.weight;
+            let ratio = user_weight / target_weight.max(0.1);
+
+            let power = if ratio >= 5.0 {
+                120.0
+            } else if ratio >= 4.0 {
+                100.0
+            } else if ratio >= 3.0 {
+                80.0
+            } else if ratio >= 2.0 {
+                60.0
+            } else {
+                40.0
+            };
+            payload.get_vec_f32().push(power);
+        }
+    }
+});
+
+// Eruption and Water Spout
+handler!(UserHpHandler ( s, state ) {
+    queries {
+        OnBasePower( payload ) [priority = BASE_POWER_PRIORITY] => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            let user_pokemon = &state.get_active_pokemon(s.trainer_side, s.slot).pokemon;
+            let power = (150 * user_pokemon.hp as u32 / user_pokemon.max_hp as u32).max(1);
+            payload.get_vec_f32().push(power as f32);
+        }
+    }
+});
+
+handler!(FacadeHandler ( s, state ) {
+    queries {
+        OnBasePower( payload ) => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            let is_boosted = matches!(
+                state.get_active_pokemon(s.trainer_side, s.slot).status,
+                Some(Status::Burn | Status::Paralyze | Status::Poison | Status::BadlyPoison)
+            );
+            if is_boosted {
+                payload.get_vec_f32().push(2.0);
+            }
+        }
+    }
+});
+
+handler!(HexHandler ( s, state ) {
+    queries {
+        OnBasePower( payload ) => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            if has_status(state, payload.context.target_trainer, payload.context.target_slot) {
+                payload.get_vec_f32().push(2.0);
+            }
+        }
+    }
+});
+
+handler!(AcrobaticsHandler ( s, state ) {
+    queries {
+        OnBasePower( payload ) => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            if state.get_active_pokemon(s.trainer_side, s.slot).pokemon.item.is_none() {
+                payload.get_vec_f32().push(2.0);
+            }
+        }
+    }
+});
+
+handler!(StoredPowerHandler ( s, state ) {
+    queries {
+        OnBasePower( payload ) [priority = BASE_POWER_PRIORITY] => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            let positive_boosts: u32 = state
+                .get_active_pokemon(s.trainer_side, s.slot)
+                .boosts
+                .values()
+                .filter(|boost| **boost > 0)
+                .map(|boost| *boost as u32)
+                .sum();
+            payload.get_vec_f32().push((20 + 20 * positive_boosts) as f32);
+        }
+    }
+});
+
+handler!(ReversalHandler ( s, state ) {
+    queries {
+        OnBasePower( payload ) [priority = BASE_POWER_PRIORITY] => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            let user_pokemon = &state.get_active_pokemon(s.trainer_side, s.slot).pokemon;
+            let hp_ratio = 48 * user_pokemon.hp as u32 / user_pokemon.max_hp as u32;
+
+            let power = match hp_ratio {
+                0..=1 => 200.0,
+                2..=4 => 150.0,
+                5..=9 => 100.0,
+                10..=16 => 80.0,
+                17..=32 => 40.0,
+                _ => 20.0,
+            };
+            payload.get_vec_f32().push(power);
+        }
+    }
+});
+
+handler!(ReturnHandler ( s, state ) {
+    queries {
+        OnBasePower( payload ) [priority = BASE_POWER_PRIORITY] => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            let happiness = state.get_active_pokemon(s.trainer_side, s.slot).pokemon.happiness as u32;
+            payload.get_vec_f32().push((happiness * 10 / 25).max(1) as f32);
+        }
+    }
+});
+
+handler!(FrustrationHandler ( s, state ) {
+    queries {
+        OnBasePower( payload ) [priority = BASE_POWER_PRIORITY] => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            let happiness = state.get_active_pokemon(s.trainer_side, s.slot).pokemon.happiness as u32;
+            payload.get_vec_f32().push(((255 - happiness) * 10 / 25).max(1) as f32);
+        }
+    }
+});
+
+pub fn get_base_power_handler(
+    move_name: &MoveName,
+    trainer_side: bool,
+    slot: usize,
+) -> Option<Arc<dyn CombinedHandler>> {
+    let handler: Arc<dyn CombinedHandler> = match move_name {
+        MoveName::GyroBall => Arc::new(GyroBallHandler::new(trainer_side, slot)),
+        MoveName::ElectroBall => Arc::new(ElectroBallHandler::new(trainer_side, slot)),
+        MoveName::LowKick | MoveName::GrassKnot => {
+            Arc::new(TargetWeightHandler::new(trainer_side, slot))
+        }
+        MoveName::HeavySlam => Arc::new(HeavySlamHandler::new(trainer_side, slot)),
+        MoveName::Eruption | MoveName::WaterSpout => {
+            Arc::new(UserHpHandler::new(trainer_side, slot))
+        }
+        MoveName::Facade => Arc::new(FacadeHandler::new(trainer_side, slot)),
+        MoveName::Hex => Arc::new(HexHandler::new(trainer_side, slot)),
+        MoveName::Acrobatics => Arc::new(AcrobaticsHandler::new(trainer_side, slot)),
+        MoveName::StoredPower => Arc::new(StoredPowerHandler::new(trainer_side, slot)),
+        MoveName::Reversal => Arc::new(ReversalHandler::new(trainer_side, slot)),
+        MoveName::Return => Arc::new(ReturnHandler::new(trainer_side, slot)),
+        MoveName::Frustration => Arc::new(FrustrationHandler::new(trainer_side, slot)),
+        _ => return None,
+    };
+
+    Some(handler)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        battle::{battle::Battle, test_utils},
+        common::context::MoveContext,
+        core::pokemon::boostable_stat::BoostableStat,
+        dex::pokemove::move_dex,
+        query::{payload::PayloadMoveQuery, query::Query, query_handler::QueryHandler},
+    };
+
+    const FERROTHORN: &str = "Ferrothorn\nAbility: Technician\n- Gyro Ball\n- Hex";
+    const ALAKAZAM: &str = "Alakazam\nAbility: Technician\n- Eruption\n- Reversal";
+
+    // Base power trainer 1's active pokemon would use the move with against trainer 2's
+    fn get_base_power(battle: &mut Battle, move_name: MoveName) -> f32 {
+        let handler = get_base_power_handler(&move_name, true, 0)
+            .expect("move should have a base power handler");
+        let mut query = Query::OnBasePower(PayloadMoveQuery::vec_f32(MoveContext {
+            src_trainer: true,
+            src_slot: 0,
+            target_trainer: false,
+            target_slot: 0,
+            move_name,
+            pokemove: move_dex::get_move_data(&move_name),
+            is_spread: false,
+            base_move: None,
+        }));
+        QueryHandler::handle(&*handler, &mut query, battle.battle_context().battle_state);
+        query.into_payload_move_query().payload.as_vec_f32()[0]
+    }
+
+    #[test]
+    fn gyro_ball_power_from_speed_ratio() {
+        let mut battle = test_utils::get_battle(FERROTHORN, FERROTHORN, 0);
+        assert_eq!(get_base_power(&mut battle, MoveName::GyroBall), 26.0);
+
+        let mut battle = test_utils::get_battle(FERROTHORN, ALAKAZAM, 0);
+        let [user_speed, target_speed] = [true, false].map(|trainer| {
+            battle
+                .battle_state()
+                .get_active_pokemon(trainer, 0)
+                .get_boosted_stat(StatEnum::Speed)
+        });
+        let expected = (25.0 * target_speed / user_speed).floor() + 1.0;
+        assert!(expected < 150.0);
+        assert_eq!(get_base_power(&mut battle, MoveName::GyroBall), expected);
+
+        battle
+            .battle_context()
+            .battle_state
+            .get_active_pokemon_mut(false, 0)
+            .modify_boost(BoostableStat::Stat(StatEnum::Speed), 6);
+        assert_eq!(get_base_power(&mut battle, MoveName::GyroBall), 150.0);
+    }
+
+    fn set_user_hp(battle: &mut Battle, hp: u16) {
+        battle
+            .battle_context()
+            .battle_state
+            .get_active_pokemon_mut(true, 0)
+            .pokemon
+            .hp = hp;
+    }
+
+    #[test]
+    fn user_hp_scales_power() {
+        let mut battle = test_utils::get_battle(ALAKAZAM, FERROTHORN, 0);
+        assert_eq!(get_base_power(&mut battle, MoveName::Eruption), 150.0);
+        assert_eq!(get_base_power(&mut battle, MoveName::Reversal), 20.0);
+
+        let max_hp = battle
+            .battle_state()
+            .get_active_pokemon(true, 0)
+            .pokemon
+            .max_hp;
+        set_user_hp(&mut battle, max_hp / 2);
+        let expected = (150 * (max_hp / 2) as u32 / max_hp as u32) as f32;
+        assert_eq!(get_base_power(&mut battle, MoveName::Eruption), expected);
+
+        set_user_hp(&mut battle, 1);
+        assert_eq!(get_base_power(&mut battle, MoveName::Eruption), 1.0);
+        assert_eq!(get_base_power(&mut battle, MoveName::Reversal), 200.0);
+    }
+
+    #[test]
+    fn hex_doubles_against_a_statused_target() {
+        let mut battle = test_utils::get_battle(FERROTHORN, ALAKAZAM, 0);
+        battle
+            .battle_context()
+            .battle_state
+            .get_active_pokemon_mut(false, 0)
+            .status = Some(Status::Burn);
+        assert_eq!(get_base_power(&mut battle, MoveName::Hex), 2.0);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    core::{
+        pokemove::{
+            fixed_damage::FixedDamage, move_category::MoveCategory, move_name::MoveName,
+            pokemove::PokeMove,
+        },
+        poketype::poketype::PokeType,
+    },
+    dex::pokemove::move_dex,
+};
+
+pub fn get_z_move(move_type: PokeType) -> MoveName {
+    match move_type {
+        PokeType::Fire => MoveName::InfernoOverdrive,
+        PokeType::Water => MoveName::HydroVortex,
+        PokeType::Electric => MoveName::GigavoltHavoc,
+        PokeType::Grass => MoveName::BloomDoom,
+        PokeType::Ice => MoveName::SubzeroSlammer,
+        PokeType::Fighting => MoveName::AllOutPummeling,
+        PokeType::Poison => MoveName::AcidDownpour,
+        PokeType::Ground => MoveName::TectonicRage,
+        PokeType::Flying => MoveName::SupersonicSkystrike,
+        PokeType::Psychic => MoveName::ShatteredPsyche,
+        PokeType::Bug => MoveName::SavageSpinOut,
+        PokeType::Rock => MoveName::ContinentalCrush,
+        PokeType::Ghost => MoveName::NeverEndingNightmare,
+        PokeType::Dragon => MoveName::DevastatingDrake,
+        PokeType::Dark => MoveName::BlackHoleEclipse,
+        PokeType::Steel => MoveName::CorkscrewCrash,
+        PokeType::Fairy => MoveName::TwinkleTackle,
+        PokeType::Normal | PokeType::Typeless | PokeType::Stellar => MoveName::BreakneckBlitz,
+    }
+}
+
+// Status moves become Max Guard, damaging moves the Max Move of their type
+pub fn get_max_move(pokemove: &PokeMove) -> MoveName {
+    if pokemove.category == MoveCategory::Status {
+        return MoveName::MaxGuard;
+    }
+
+    match pokemove.move_type {
+        PokeType::Fire => MoveName::MaxFlare,
+        PokeType::Water => MoveName::MaxGeyser,
+        PokeType::Electric => MoveName::MaxLightning,
+        PokeType::Grass => MoveName::MaxOvergrowth,
+        PokeType::Ice => MoveName::MaxHailstorm,
+        PokeType::Fighting => MoveName::MaxKnuckle,
+        PokeType::Poison => MoveName::MaxOoze,
+        PokeType::Ground => MoveName::MaxQuake,
+        PokeType::Flying => MoveName::MaxAirstream,
+        PokeType::Psychic => MoveName::MaxMindstorm,
+        PokeType::Bug => MoveName::MaxFlutterby,
+        PokeType::Rock => MoveName::MaxRockfall,
+        PokeType::Ghost => MoveName::MaxPhantasm,
+        PokeType::Dragon => MoveName::MaxWyrmwind,
+        PokeType::Dark => MoveName::MaxDarkness,
+        PokeType::Steel => MoveName::MaxSteelspike,
+        PokeType::Fairy => MoveName::MaxStarfall,
+        PokeType::Normal | PokeType::Typeless | PokeType::Stellar => MoveName::MaxStrike,
+    }
+}
+
+pub fn is_z_move(move_name: &MoveName) -> bool {
+    *move_name == get_z_move(move_dex::get_move_data(move_name).move_type)
+}
+
+pub fn is_max_move(move_name: &MoveName) -> bool {
+    let pokemove = move_dex::get_move_data(move_name);
+    *move_name == get_max_move(pokemove)
+}
+
+// Z-Move power scales with the replaced move's power, variable power moves
+//  use the most common value
+pub fn get_z_move_power(base_move: &PokeMove, base_power: Option<u32>) -> u32 {
+    match (base_power, base_move.fixed_damage) {
+        (_, Some(FixedDamage::OneHitKo)) => 180,
+        (_, Some(_)) => 100,
+        (None, None) => 160,
+        (Some(power), None) => match power {
+            140.. => 200,
+            130..=139 => 195,
+            120..=129 => 190,
+            110..=119 => 185,
+            100..=109 => 180,
+            90..=99 => 175,
+            80..=89 => 160,
+            70..=79 => 140,
+            60..=69 => 120,
+            _ => 100,
+        },
+    }
+}
+
+// Fighting and Poison Max Moves are weaker to make up for their stronger boosts
+pub fn get_max_move_power(base_move: &PokeMove, base_power: Option<u32>) -> u32 {
+    let weak_type = matches!(base_move.move_type, PokeType::Fighting | PokeType::Poison);
+    let power = match (base_power, base_move.fixed_damage) {
+        (_, Some(FixedDamage::OneHitKo)) => 130,
+        (_, Some(_)) => 100,
+        (None, None) => 130,
+        (Some(power), None) => match power {
+            150.. => 150,
+            110..=149 => 140,
+            75..=109 => 130,
+            65..=74 => 120,
+            55..=64 => 110,
+            45..=54 => 100,
+            _ => 90,
+        },
+    };
+
+    if weak_type {
+        match power {
+            150 => 100,
+            140 => 95,
+            130 => 90,
+            120 => 85,
+            110 => 80,
+            100 => 75,
+            _ => 70,
+        }
+    } else {
+        power
+    }
+}
@@ -1 +1,5 @@
+pub mod base_power_handlers;
+pub mod gimmick_moves;
 pub mod move_dex;
+pub mod move_handlers;
+pub mod move_revisions;
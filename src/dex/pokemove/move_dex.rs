@@ -1,19 +1,29 @@
-use std::{collections::HashMap, sync::LazyLock};
+use std::sync::LazyLock;
 
 use enum_map::{EnumMap, enum_map};
 
 use crate::core::{
+    field::weather::Weather,
     pokemon::{boostable_stat::BoostableStat, stat_enum::StatEnum},
     pokemove::{
-        move_category::MoveCategory, move_name::MoveName, pokemove::PokeMove,
+        fixed_damage::FixedDamage, move_category::MoveCategory, move_heal::MoveHeal,
+        move_name::MoveName, move_target::MoveTarget, multi_turn::MultiTurn, pokemove::PokeMove,
         secondary_effect::SecondaryEffect,
     },
     poketype::poketype::PokeType,
-    status::status::Status,
+    side_condition::side_condition::SideCondition,
+    status::{status::Status, volatile_status::VolatileStatus},
 };
 
 static MOVES_DB: LazyLock<EnumMap<MoveName, PokeMove>> = LazyLock::new(|| {
     enum_map! {
+        MoveName::Empty =>
+            PokeMove::builder()
+                .name(MoveName::Empty)
+                .category(MoveCategory::Status)
+                .move_type(PokeType::Typeless)
+                .pp(0)
+                .build(),
         MoveName::Struggle =>
             PokeMove::builder()
                 .name(MoveName::Struggle)
@@ -59,7 +69,806 @@ static MOVES_DB: LazyLock<EnumMap<MoveName, PokeMove>> = LazyLock::new(|| {
                 .move_type(PokeType::Normal)
                 .pp(35)
                 .build(),
-        _ => panic!("Move not implemented"),
+        MoveName::BulletSeed =>
+            PokeMove::builder()
+                .name(MoveName::BulletSeed)
+                .category(MoveCategory::Physical)
+                .power(Some(25))
+                .accuracy(Some(100))
+                .is_multi_hit(true)
+                .move_type(PokeType::Grass)
+                .pp(30)
+                .build(),
+        MoveName::SandAttack =>
+            PokeMove::builder()
+                .name(MoveName::SandAttack)
+                .category(MoveCategory::Status)
+                .accuracy(Some(100))
+                .boosts(Some(vec![(BoostableStat::Accuracy, -1)]))
+                .move_type(PokeType::Ground)
+                .pp(15)
+                .build(),
+        MoveName::WillOWisp =>
+            PokeMove::builder()
+                .name(MoveName::WillOWisp)
+                .category(MoveCategory::Status)
+                .accuracy(Some(85))
+                .status(Some(Status::Burn))
+                .move_type(PokeType::Fire)
+                .pp(15)
+                .build(),
+        MoveName::SolarBeam =>
+            PokeMove::builder()
+                .name(MoveName::SolarBeam)
+                .category(MoveCategory::Special)
+                .power(Some(120))
+                .accuracy(Some(100))
+                .multi_turn(Some(MultiTurn::Charge))
+                .move_type(PokeType::Grass)
+                .pp(10)
+                .build(),
+        MoveName::SkyAttack =>
+            PokeMove::builder()
+                .name(MoveName::SkyAttack)
+                .category(MoveCategory::Physical)
+                .power(Some(140))
+                .accuracy(Some(90))
+                .multi_turn(Some(MultiTurn::Charge))
+                .secondary_effects(Some(vec![(30, vec![SecondaryEffect::VolatileStatus(VolatileStatus::Flinch)])]))
+                .move_type(PokeType::Flying)
+                .pp(5)
+                .build(),
+        MoveName::HyperBeam =>
+            PokeMove::builder()
+                .name(MoveName::HyperBeam)
+                .category(MoveCategory::Special)
+                .power(Some(150))
+                .accuracy(Some(90))
+                .multi_turn(Some(MultiTurn::Recharge))
+                .move_type(PokeType::Normal)
+                .pp(5)
+                .build(),
+        MoveName::Outrage =>
+            PokeMove::builder()
+                .name(MoveName::Outrage)
+                .category(MoveCategory::Physical)
+                .power(Some(120))
+                .accuracy(Some(100))
+                .multi_turn(Some(MultiTurn::Rampage))
+                .move_type(PokeType::Dragon)
+                .pp(10)
+                .build(),
+        MoveName::Rollout =>
+            PokeMove::builder()
+                .name(MoveName::Rollout)
+                .category(MoveCategory::Physical)
+                .power(Some(30))
+                .accuracy(Some(90))
+                .multi_turn(Some(MultiTurn::Rollout))
+                .move_type(PokeType::Rock)
+                .pp(20)
+                .build(),
+        MoveName::IceBall =>
+            PokeMove::builder()
+                .name(MoveName::IceBall)
+                .category(MoveCategory::Physical)
+                .power(Some(30))
+                .accuracy(Some(90))
+                .multi_turn(Some(MultiTurn::Rollout))
+                .move_type(PokeType::Ice)
+                .pp(20)
+                .build(),
+        MoveName::Encore =>
+            PokeMove::builder()
+                .name(MoveName::Encore)
+                .category(MoveCategory::Status)
+                .accuracy(Some(100))
+                .volatile_status(Some(VolatileStatus::Encore))
+                .move_type(PokeType::Normal)
+                .pp(5)
+                .build(),
+        MoveName::GigaDrain =>
+            PokeMove::builder()
+                .name(MoveName::GigaDrain)
+                .category(MoveCategory::Special)
+                .power(Some(75))
+                .accuracy(Some(100))
+                .drain(Some(50))
+                .move_type(PokeType::Grass)
+                .pp(10)
+                .build(),
+        MoveName::DrainPunch =>
+            PokeMove::builder()
+                .name(MoveName::DrainPunch)
+                .category(MoveCategory::Physical)
+                .power(Some(75))
+                .accuracy(Some(100))
+                .drain(Some(50))
+                .move_type(PokeType::Fighting)
+                .pp(10)
+                .build(),
+        MoveName::Recover =>
+            PokeMove::builder()
+                .name(MoveName::Recover)
+                .category(MoveCategory::Status)
+                .heal(Some(MoveHeal::Percent(50)))
+                .target(MoveTarget::User)
+                .move_type(PokeType::Normal)
+                .pp(5)
+                .build(),
+        MoveName::Roost =>
+            PokeMove::builder()
+                .name(MoveName::Roost)
+                .category(MoveCategory::Status)
+                .heal(Some(MoveHeal::Percent(50)))
+                .volatile_status(Some(VolatileStatus::Roost))
+                .target(MoveTarget::User)
+                .move_type(PokeType::Flying)
+                .pp(5)
+                .build(),
+        MoveName::Synthesis =>
+            PokeMove::builder()
+                .name(MoveName::Synthesis)
+                .category(MoveCategory::Status)
+                .heal(Some(MoveHeal::Weather))
+                .target(MoveTarget::User)
+                .move_type(PokeType::Grass)
+                .pp(5)
+                .build(),
+        MoveName::SeismicToss =>
+            PokeMove::builder()
+                .name(MoveName::SeismicToss)
+                .category(MoveCategory::Physical)
+                .accuracy(Some(100))
+                .fixed_damage(Some(FixedDamage::Level))
+                .move_type(PokeType::Fighting)
+                .pp(20)
+                .build(),
+        MoveName::NightShade =>
+            PokeMove::builder()
+                .name(MoveName::NightShade)
+                .category(MoveCategory::Special)
+                .accuracy(Some(100))
+                .fixed_damage(Some(FixedDamage::Level))
+                .move_type(PokeType::Ghost)
+                .pp(15)
+                .build(),
+        MoveName::SuperFang =>
+            PokeMove::builder()
+                .name(MoveName::SuperFang)
+                .category(MoveCategory::Physical)
+                .accuracy(Some(90))
+                .fixed_damage(Some(FixedDamage::TargetHpPercent(50)))
+                .move_type(PokeType::Normal)
+                .pp(10)
+                .build(),
+        MoveName::DragonRage =>
+            PokeMove::builder()
+                .name(MoveName::DragonRage)
+                .category(MoveCategory::Special)
+                .accuracy(Some(100))
+                .fixed_damage(Some(FixedDamage::Amount(40)))
+                .move_type(PokeType::Dragon)
+                .pp(10)
+                .build(),
+        MoveName::GyroBall =>
+            PokeMove::builder()
+                .name(MoveName::GyroBall)
+                .category(MoveCategory::Physical)
+                .accuracy(Some(100))
+                .move_type(PokeType::Steel)
+                .pp(5)
+                .build(),
+        MoveName::ElectroBall =>
+            PokeMove::builder()
+                .name(MoveName::ElectroBall)
+                .category(MoveCategory::Special)
+                .accuracy(Some(100))
+                .move_type(PokeType::Electric)
+                .pp(10)
+                .build(),
+        MoveName::LowKick =>
+            PokeMove::builder()
+                .name(MoveName::LowKick)
+                .category(MoveCategory::Physical)
+                .accuracy(Some(100))
+                .move_type(PokeType::Fighting)
+                .pp(20)
+                .build(),
+        MoveName::GrassKnot =>
+            PokeMove::builder()
+                .name(MoveName::GrassKnot)
+                .category(MoveCategory::Special)
+                .accuracy(Some(100))
+                .move_type(PokeType::Grass)
+                .pp(20)
+                .build(),
+        MoveName::HeavySlam =>
+            PokeMove::builder()
+                .name(MoveName::HeavySlam)
+                .category(MoveCategory::Physical)
+                .accuracy(Some(100))
+                .move_type(PokeType::Steel)
+                .pp(10)
+                .build(),
+        MoveName::Eruption =>
+            PokeMove::builder()
+                .name(MoveName::Eruption)
+                .category(MoveCategory::Special)
+                .accuracy(Some(100))
+                .move_type(PokeType::Fire)
+                .pp(5)
+                .build(),
+        MoveName::WaterSpout =>
+            PokeMove::builder()
+                .name(MoveName::WaterSpout)
+                .category(MoveCategory::Special)
+                .accuracy(Some(100))
+                .move_type(PokeType::Water)
+                .pp(5)
+                .build(),
+        MoveName::Facade =>
+            PokeMove::builder()
+                .name(MoveName::Facade)
+                .category(MoveCategory::Physical)
+                .power(Some(70))
+                .accuracy(Some(100))
+                .move_type(PokeType::Normal)
+                .pp(20)
+                .build(),
+        MoveName::Hex =>
+            PokeMove::builder()
+                .name(MoveName::Hex)
+                .category(MoveCategory::Special)
+                .power(Some(65))
+                .accuracy(Some(100))
+                .move_type(PokeType::Ghost)
+                .pp(10)
+                .build(),
+        MoveName::KnockOff =>
+            PokeMove::builder()
+                .name(MoveName::KnockOff)
+                .category(MoveCategory::Physical)
+                .power(Some(65))
+                .accuracy(Some(100))
+                .move_type(PokeType::Dark)
+                .pp(20)
+                .build(),
+        MoveName::Acrobatics =>
+            PokeMove::builder()
+                .name(MoveName::Acrobatics)
+                .category(MoveCategory::Physical)
+                .power(Some(55))
+                .accuracy(Some(100))
+                .move_type(PokeType::Flying)
+                .pp(15)
+                .build(),
+        MoveName::StoredPower =>
+            PokeMove::builder()
+                .name(MoveName::StoredPower)
+                .category(MoveCategory::Special)
+                .accuracy(Some(100))
+                .move_type(PokeType::Psychic)
+                .pp(10)
+                .build(),
+        MoveName::Reversal =>
+            PokeMove::builder()
+                .name(MoveName::Reversal)
+                .category(MoveCategory::Physical)
+                .accuracy(Some(100))
+                .move_type(PokeType::Fighting)
+                .pp(15)
+                .build(),
+        MoveName::Return =>
+            PokeMove::builder()
+                .name(MoveName::Return)
+                .category(MoveCategory::Physical)
+                .accuracy(Some(100))
+                .move_type(PokeType::Normal)
+                .pp(20)
+                .build(),
+        MoveName::Frustration =>
+            PokeMove::builder()
+                .name(MoveName::Frustration)
+                .category(MoveCategory::Physical)
+                .accuracy(Some(100))
+                .move_type(PokeType::Normal)
+                .pp(20)
+                .build(),
+        MoveName::SuckerPunch =>
+            PokeMove::builder()
+                .name(MoveName::SuckerPunch)
+                .category(MoveCategory::Physical)
+                .power(Some(70))
+                .accuracy(Some(100))
+                .priority(1)
+                .move_type(PokeType::Dark)
+                .pp(5)
+                .build(),
+        MoveName::Counter =>
+            PokeMove::builder()
+                .name(MoveName::Counter)
+                .category(MoveCategory::Physical)
+                .accuracy(Some(100))
+                .priority(-5)
+                .fixed_damage(Some(FixedDamage::Retaliation))
+                .move_type(PokeType::Fighting)
+                .pp(20)
+                .build(),
+        MoveName::SwordsDance =>
+            PokeMove::builder()
+                .name(MoveName::SwordsDance)
+                .category(MoveCategory::Status)
+                .boosts(Some(vec![(BoostableStat::Stat(StatEnum::Attack), 2)]))
+                .target(MoveTarget::User)
+                .move_type(PokeType::Normal)
+                .pp(20)
+                .build(),
+        MoveName::CloseCombat =>
+            PokeMove::builder()
+                .name(MoveName::CloseCombat)
+                .category(MoveCategory::Physical)
+                .power(Some(120))
+                .accuracy(Some(100))
+                .self_boosts(Some(vec![
+                    (BoostableStat::Stat(StatEnum::Defense), -1),
+                    (BoostableStat::Stat(StatEnum::SpecialDefense), -1),
+                ]))
+                .move_type(PokeType::Fighting)
+                .pp(5)
+                .build(),
+        MoveName::Rest =>
+            PokeMove::builder()
+                .name(MoveName::Rest)
+                .category(MoveCategory::Status)
+                .heal(Some(MoveHeal::Percent(100)))
+                .status(Some(Status::Sleep))
+                .target(MoveTarget::User)
+                .move_type(PokeType::Psychic)
+                .pp(5)
+                .build(),
+        MoveName::Spikes =>
+            PokeMove::builder()
+                .name(MoveName::Spikes)
+                .category(MoveCategory::Status)
+                .side_condition(Some(SideCondition::Spikes))
+                .target(MoveTarget::OpponentSide)
+                .move_type(PokeType::Ground)
+                .pp(20)
+                .build(),
+        MoveName::StealthRock =>
+            PokeMove::builder()
+                .name(MoveName::StealthRock)
+                .category(MoveCategory::Status)
+                .side_condition(Some(SideCondition::StealthRock))
+                .target(MoveTarget::OpponentSide)
+                .move_type(PokeType::Rock)
+                .pp(20)
+                .build(),
+        MoveName::Reflect =>
+            PokeMove::builder()
+                .name(MoveName::Reflect)
+                .category(MoveCategory::Status)
+                .side_condition(Some(SideCondition::Reflect))
+                .target(MoveTarget::UserSide)
+                .move_type(PokeType::Psychic)
+                .pp(20)
+                .build(),
+        MoveName::LightScreen =>
+            PokeMove::builder()
+                .name(MoveName::LightScreen)
+                .category(MoveCategory::Status)
+                .side_condition(Some(SideCondition::LightScreen))
+                .target(MoveTarget::UserSide)
+                .move_type(PokeType::Psychic)
+                .pp(30)
+                .build(),
+        MoveName::BrickBreak =>
+            PokeMove::builder()
+                .name(MoveName::BrickBreak)
+                .category(MoveCategory::Physical)
+                .power(Some(75))
+                .accuracy(Some(100))
+                .move_type(PokeType::Fighting)
+                .pp(15)
+                .build(),
+        MoveName::SunnyDay =>
+            PokeMove::builder()
+                .name(MoveName::SunnyDay)
+                .category(MoveCategory::Status)
+                .weather(Some(Weather::Sun))
+                .target(MoveTarget::Field)
+                .move_type(PokeType::Fire)
+                .pp(5)
+                .build(),
+        MoveName::RainDance =>
+            PokeMove::builder()
+                .name(MoveName::RainDance)
+                .category(MoveCategory::Status)
+                .weather(Some(Weather::Rain))
+                .target(MoveTarget::Field)
+                .move_type(PokeType::Water)
+                .pp(5)
+                .build(),
+        MoveName::Earthquake =>
+            PokeMove::builder()
+                .name(MoveName::Earthquake)
+                .category(MoveCategory::Physical)
+                .power(Some(100))
+                .accuracy(Some(100))
+                .target(MoveTarget::AllAdjacent)
+                .move_type(PokeType::Ground)
+                .pp(10)
+                .build(),
+        MoveName::RockSlide =>
+            PokeMove::builder()
+                .name(MoveName::RockSlide)
+                .category(MoveCategory::Physical)
+                .power(Some(75))
+                .accuracy(Some(90))
+                .secondary_effects(Some(vec![(30, vec![SecondaryEffect::VolatileStatus(VolatileStatus::Flinch)])]))
+                .target(MoveTarget::AllAdjacentFoes)
+                .move_type(PokeType::Rock)
+                .pp(10)
+                .build(),
+        MoveName::HeatWave =>
+            PokeMove::builder()
+                .name(MoveName::HeatWave)
+                .category(MoveCategory::Special)
+                .power(Some(95))
+                .accuracy(Some(90))
+                .secondary_effects(Some(vec![(10, vec![SecondaryEffect::Status(Status::Burn)])]))
+                .target(MoveTarget::AllAdjacentFoes)
+                .move_type(PokeType::Fire)
+                .pp(10)
+                .build(),
+        MoveName::FollowMe =>
+            PokeMove::builder()
+                .name(MoveName::FollowMe)
+                .category(MoveCategory::Status)
+                .priority(2)
+                .volatile_status(Some(VolatileStatus::FollowMe))
+                .target(MoveTarget::User)
+                .move_type(PokeType::Normal)
+                .pp(20)
+                .build(),
+        MoveName::RagePowder =>
+            PokeMove::builder()
+                .name(MoveName::RagePowder)
+                .category(MoveCategory::Status)
+                .priority(2)
+                .volatile_status(Some(VolatileStatus::RagePowder))
+                .target(MoveTarget::User)
+                .move_type(PokeType::Bug)
+                .pp(20)
+                .build(),
+        MoveName::HelpingHand =>
+            PokeMove::builder()
+                .name(MoveName::HelpingHand)
+                .category(MoveCategory::Status)
+                .priority(5)
+                .volatile_status(Some(VolatileStatus::HelpingHand))
+                .target(MoveTarget::Ally)
+                .move_type(PokeType::Normal)
+                .pp(20)
+                .build(),
+        MoveName::DoubleTeam =>
+            PokeMove::builder()
+                .name(MoveName::DoubleTeam)
+                .category(MoveCategory::Status)
+                .boosts(Some(vec![(BoostableStat::Evasion, 1)]))
+                .target(MoveTarget::User)
+                .move_type(PokeType::Normal)
+                .pp(15)
+                .build(),
+        MoveName::Fissure =>
+            PokeMove::builder()
+                .name(MoveName::Fissure)
+                .category(MoveCategory::Physical)
+                .accuracy(Some(30))
+                .fixed_damage(Some(FixedDamage::OneHitKo))
+                .move_type(PokeType::Ground)
+                .pp(5)
+                .build(),
+        MoveName::SheerCold =>
+            PokeMove::builder()
+                .name(MoveName::SheerCold)
+                .category(MoveCategory::Special)
+                .accuracy(Some(30))
+                .fixed_damage(Some(FixedDamage::OneHitKo))
+                .move_type(PokeType::Ice)
+                .pp(5)
+                .build(),
+        MoveName::Spore =>
+            PokeMove::builder()
+                .name(MoveName::Spore)
+                .category(MoveCategory::Status)
+                .accuracy(Some(100))
+                .status(Some(Status::Sleep))
+                .move_type(PokeType::Grass)
+                .pp(15)
+                .build(),
+        // Z-Moves and Max Moves take their category and power from the move they replace
+        MoveName::BreakneckBlitz =>
+            PokeMove::builder()
+                .name(MoveName::BreakneckBlitz)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Normal)
+                .pp(1)
+                .build(),
+        MoveName::InfernoOverdrive =>
+            PokeMove::builder()
+                .name(MoveName::InfernoOverdrive)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Fire)
+                .pp(1)
+                .build(),
+        MoveName::HydroVortex =>
+            PokeMove::builder()
+                .name(MoveName::HydroVortex)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Water)
+                .pp(1)
+                .build(),
+        MoveName::GigavoltHavoc =>
+            PokeMove::builder()
+                .name(MoveName::GigavoltHavoc)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Electric)
+                .pp(1)
+                .build(),
+        MoveName::BloomDoom =>
+            PokeMove::builder()
+                .name(MoveName::BloomDoom)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Grass)
+                .pp(1)
+                .build(),
+        MoveName::SubzeroSlammer =>
+            PokeMove::builder()
+                .name(MoveName::SubzeroSlammer)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Ice)
+                .pp(1)
+                .build(),
+        MoveName::AllOutPummeling =>
+            PokeMove::builder()
+                .name(MoveName::AllOutPummeling)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Fighting)
+                .pp(1)
+                .build(),
+        MoveName::AcidDownpour =>
+            PokeMove::builder()
+                .name(MoveName::AcidDownpour)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Poison)
+                .pp(1)
+                .build(),
+        MoveName::TectonicRage =>
+            PokeMove::builder()
+                .name(MoveName::TectonicRage)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Ground)
+                .pp(1)
+                .build(),
+        MoveName::SupersonicSkystrike =>
+            PokeMove::builder()
+                .name(MoveName::SupersonicSkystrike)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Flying)
+                .pp(1)
+                .build(),
+        MoveName::ShatteredPsyche =>
+            PokeMove::builder()
+                .name(MoveName::ShatteredPsyche)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Psychic)
+                .pp(1)
+                .build(),
+        MoveName::SavageSpinOut =>
+            PokeMove::builder()
+                .name(MoveName::SavageSpinOut)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Bug)
+                .pp(1)
+                .build(),
+        MoveName::ContinentalCrush =>
+            PokeMove::builder()
+                .name(MoveName::ContinentalCrush)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Rock)
+                .pp(1)
+                .build(),
+        MoveName::NeverEndingNightmare =>
+            PokeMove::builder()
+                .name(MoveName::NeverEndingNightmare)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Ghost)
+                .pp(1)
+                .build(),
+        MoveName::DevastatingDrake =>
+            PokeMove::builder()
+                .name(MoveName::DevastatingDrake)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Dragon)
+                .pp(1)
+                .build(),
+        MoveName::BlackHoleEclipse =>
+            PokeMove::builder()
+                .name(MoveName::BlackHoleEclipse)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Dark)
+                .pp(1)
+                .build(),
+        MoveName::CorkscrewCrash =>
+            PokeMove::builder()
+                .name(MoveName::CorkscrewCrash)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Steel)
+                .pp(1)
+                .build(),
+        MoveName::TwinkleTackle =>
+            PokeMove::builder()
+                .name(MoveName::TwinkleTackle)
+                .category(MoveCategory::Physical)
+                .move_type(PokeType::Fairy)
+                .pp(1)
+                .build(),
+        MoveName::MaxStrike =>
+            PokeMove::builder()
+                .name(MoveName::MaxStrike)
+                .category(MoveCategory::Physical)
+                .secondary_effects(Some(vec![(100, vec![SecondaryEffect::TargetBoost(BoostableStat::Stat(StatEnum::Speed), -1)])]))
+                .move_type(PokeType::Normal)
+                .pp(1)
+                .build(),
+        MoveName::MaxFlare =>
+            PokeMove::builder()
+                .name(MoveName::MaxFlare)
+                .category(MoveCategory::Physical)
+                .weather(Some(Weather::Sun))
+                .move_type(PokeType::Fire)
+                .pp(1)
+                .build(),
+        MoveName::MaxGeyser =>
+            PokeMove::builder()
+                .name(MoveName::MaxGeyser)
+                .category(MoveCategory::Physical)
+                .weather(Some(Weather::Rain))
+                .move_type(PokeType::Water)
+                .pp(1)
+                .build(),
+        MoveName::MaxLightning =>
+            PokeMove::builder()
+                .name(MoveName::MaxLightning)
+                .category(MoveCategory::Physical)
+                // terrains aren't simulated, so this has no field effect
+                .move_type(PokeType::Electric)
+                .pp(1)
+                .build(),
+        MoveName::MaxOvergrowth =>
+            PokeMove::builder()
+                .name(MoveName::MaxOvergrowth)
+                .category(MoveCategory::Physical)
+                // terrains aren't simulated, so this has no field effect
+                .move_type(PokeType::Grass)
+                .pp(1)
+                .build(),
+        MoveName::MaxHailstorm =>
+            PokeMove::builder()
+                .name(MoveName::MaxHailstorm)
+                .category(MoveCategory::Physical)
+                .weather(Some(Weather::Hail))
+                .move_type(PokeType::Ice)
+                .pp(1)
+                .build(),
+        MoveName::MaxKnuckle =>
+            PokeMove::builder()
+                .name(MoveName::MaxKnuckle)
+                .category(MoveCategory::Physical)
+                .self_boosts(Some(vec![(BoostableStat::Stat(StatEnum::Attack), 1)]))
+                .move_type(PokeType::Fighting)
+                .pp(1)
+                .build(),
+        MoveName::MaxOoze =>
+            PokeMove::builder()
+                .name(MoveName::MaxOoze)
+                .category(MoveCategory::Physical)
+                .self_boosts(Some(vec![(BoostableStat::Stat(StatEnum::SpecialAttack), 1)]))
+                .move_type(PokeType::Poison)
+                .pp(1)
+                .build(),
+        MoveName::MaxQuake =>
+            PokeMove::builder()
+                .name(MoveName::MaxQuake)
+                .category(MoveCategory::Physical)
+                .self_boosts(Some(vec![(BoostableStat::Stat(StatEnum::SpecialDefense), 1)]))
+                .move_type(PokeType::Ground)
+                .pp(1)
+                .build(),
+        MoveName::MaxAirstream =>
+            PokeMove::builder()
+                .name(MoveName::MaxAirstream)
+                .category(MoveCategory::Physical)
+                .self_boosts(Some(vec![(BoostableStat::Stat(StatEnum::Speed), 1)]))
+                .move_type(PokeType::Flying)
+                .pp(1)
+                .build(),
+        MoveName::MaxMindstorm =>
+            PokeMove::builder()
+                .name(MoveName::MaxMindstorm)
+                .category(MoveCategory::Physical)
+                // terrains aren't simulated, so this has no field effect
+                .move_type(PokeType::Psychic)
+                .pp(1)
+                .build(),
+        MoveName::MaxFlutterby =>
+            PokeMove::builder()
+                .name(MoveName::MaxFlutterby)
+                .category(MoveCategory::Physical)
+                .secondary_effects(Some(vec![(100, vec![SecondaryEffect::TargetBoost(BoostableStat::Stat(StatEnum::SpecialAttack), -1)])]))
+                .move_type(PokeType::Bug)
+                .pp(1)
+                .build(),
+        MoveName::MaxRockfall =>
+            PokeMove::builder()
+                .name(MoveName::MaxRockfall)
+                .category(MoveCategory::Physical)
+                .weather(Some(Weather::Sand))
+                .move_type(PokeType::Rock)
+                .pp(1)
+                .build(),
+        MoveName::MaxPhantasm =>
+            PokeMove::builder()
+                .name(MoveName::MaxPhantasm)
+                .category(MoveCategory::Physical)
+                .secondary_effects(Some(vec![(100, vec![SecondaryEffect::TargetBoost(BoostableStat::Stat(StatEnum::Defense), -1)])]))
+                .move_type(PokeType::Ghost)
+                .pp(1)
+                .build(),
+        MoveName::MaxWyrmwind =>
+            PokeMove::builder()
+                .name(MoveName::MaxWyrmwind)
+                .category(MoveCategory::Physical)
+                .secondary_effects(Some(vec![(100, vec![SecondaryEffect::TargetBoost(BoostableStat::Stat(StatEnum::Attack), -1)])]))
+                .move_type(PokeType::Dragon)
+                .pp(1)
+                .build(),
+        MoveName::MaxDarkness =>
+            PokeMove::builder()
+                .name(MoveName::MaxDarkness)
+                .category(MoveCategory::Physical)
+                .secondary_effects(Some(vec![(100, vec![SecondaryEffect::TargetBoost(BoostableStat::Stat(StatEnum::SpecialDefense), -1)])]))
+                .move_type(PokeType::Dark)
+                .pp(1)
+                .build(),
+        MoveName::MaxSteelspike =>
+            PokeMove::builder()
+                .name(MoveName::MaxSteelspike)
+                .category(MoveCategory::Physical)
+                .self_boosts(Some(vec![(BoostableStat::Stat(StatEnum::Defense), 1)]))
+                .move_type(PokeType::Steel)
+                .pp(1)
+                .build(),
+        MoveName::MaxStarfall =>
+            PokeMove::builder()
+                .name(MoveName::MaxStarfall)
+                .category(MoveCategory::Physical)
+                // terrains aren't simulated, so this has no field effect
+                .move_type(PokeType::Fairy)
+                .pp(1)
+                .build(),
+        MoveName::MaxGuard =>
+            PokeMove::builder()
+                .name(MoveName::MaxGuard)
+                .category(MoveCategory::Status)
+                .priority(4)
+                .volatile_status(Some(VolatileStatus::MaxGuard))
+                .target(MoveTarget::User)
+                .move_type(PokeType::Normal)
+                .pp(1)
+                .build(),
     }
 });
 
@@ -0,0 +1,373 @@
+use std::sync::Arc;
+
+use crate::{
+    core::{
+        pokemove::{move_category::MoveCategory, move_name::MoveName},
+        side_condition::side_condition::SideCondition,
+        status::status::Status,
+    },
+    dex::{
+        combined_handler::CombinedHandler,
+        pokemove::{base_power_handlers, move_dex},
+    },
+    event::event_handler_effect::EventHandlerEffect,
+    handler,
+    query::payload::Payload,
+};
+
+// Move handlers are only registered while their move is being executed, so
+// they only need to check that the move belongs to their side.
+
+handler!(StruggleHandler ( s, state ) {
+    events {
+        AfterMoveHit(hit_event) => {
+            if !s.is_own_pokemon(hit_event.move_context.src_trainer, hit_event.move_context.src_slot) {
+                return vec![];
+            }
+
+            let recoil_damage = state.get_active_pokemon(s.trainer_side, s.slot).pokemon.max_hp as u32 / 4;
+            vec![EventHandlerEffect::Damage(recoil_damage, s.trainer_side, s.slot)]
+        }
+    },
+    queries {
+        GetDeductPP( payload ) => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            payload.payload = Payload::U8(0);
+        }
+    }
+});
+
+handler!(KnockOffHandler ( s, state ) {
+    events {
+        AfterMoveHit(hit_event) => {
+            if !s.is_own_pokemon(hit_event.move_context.src_trainer, hit_event.move_context.src_slot) {
+                return vec![];
+            }
+
+            let target_trainer = hit_event.move_context.target_trainer;
+            let target_slot = hit_event.move_context.target_slot;
+            let target_pokemon = state.get_active_pokemon(target_trainer, target_slot);
+            if target_pokemon.is_fainted() || target_pokemon.pokemon.item.is_none() {
+                return vec![];
+            }
+
+            vec![EventHandlerEffect::RemoveItem(target_trainer, target_slot)]
+        }
+    },
+    queries {
+        OnBasePower( payload ) => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            if state.get_active_pokemon(payload.context.target_trainer, payload.context.target_slot).pokemon.item.is_some() {
+                payload.get_vec_f32().push(1.5);
+            }
+        }
+    }
+});
+
+handler!(SuckerPunchHandler ( s, state ) {
+    queries {
+        TryUseMove( payload ) => {
+            if !s.is_own_pokemon(payload.move_context.src_trainer, payload.move_context.src_slot) || payload.should_cancel {
+                return;
+            }
+
+            // fails unless the target is about to use a damaging move. The queued move is the
+            //  chosen one, which Z-Moves and Max Moves also take their category from
+            let target_move_category = state
+                .get_active_pokemon(payload.move_context.target_trainer, payload.move_context.target_slot)
+                .queued_move
+                .map(|move_name| state.mechanics.get_move_category(move_dex::get_move_data(&move_name)));
+            payload.should_cancel = target_move_category.is_none_or(|category| category == MoveCategory::Status);
+        }
+    }
+});
+
+handler!(CounterHandler ( s, state ) {
+    queries {
+        TryUseMove( payload ) => {
+            if !s.is_own_pokemon(payload.move_context.src_trainer, payload.move_context.src_slot) || payload.should_cancel {
+                return;
+            }
+
+            // fails unless the user was hit by a physical move this turn
+            let damage_taken = state.get_active_pokemon(s.trainer_side, s.slot).damage_taken;
+            payload.should_cancel = !matches!(damage_taken, Some((_, MoveCategory::Physical)));
+        },
+        OnFixedDamage( payload ) => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            if let Some((damage, _)) = state.get_active_pokemon(s.trainer_side, s.slot).damage_taken {
+                payload.payload = Payload::U32(damage * 2);
+            }
+        }
+    }
+});
+
+handler!(RestHandler ( s, state ) {
+    queries {
+        TryUseMove( payload ) => {
+            if !s.is_own_pokemon(payload.move_context.src_trainer, payload.move_context.src_slot) || payload.should_cancel {
+                return;
+            }
+
+            let pokemon = &state.get_active_pokemon(s.trainer_side, s.slot).pokemon;
+            payload.should_cancel = pokemon.hp == pokemon.max_hp;
+        },
+        CanApplyStatus( payload ) [priority = 1] => {
+            if !s.is_own_pokemon(payload.target, payload.target_slot) || payload.status != Status::Sleep {
+                return;
+            }
+
+            // Rest replaces any existing status, but other immunities still apply
+            payload.replaces_status = true;
+        }
+    }
+});
+
+handler!(BrickBreakHandler ( s, state ) {
+    events {
+        BeforeMoveHit(move_context) => {
+            if !s.is_own_pokemon(move_context.src_trainer, move_context.src_slot) {
+                return vec![];
+            }
+
+            let target_trainer = move_context.target_trainer;
+            [SideCondition::Reflect, SideCondition::LightScreen]
+                .into_iter()
+                .filter(|side_condition| state.get_side(target_trainer).has_side_condition(side_condition))
+                .map(|side_condition| EventHandlerEffect::RemoveSideCondition(side_condition, target_trainer))
+                .collect()
+        }
+    }
+});
+
+pub fn get_move_handler(
+    move_name: &MoveName,
+    trainer_side: bool,
+    slot: usize,
+) -> Option<Arc<dyn CombinedHandler>> {
+    let handler: Arc<dyn CombinedHandler> = match move_name {
+        MoveName::Struggle => Arc::new(StruggleHandler::new(trainer_side, slot)),
+        MoveName::KnockOff => Arc::new(KnockOffHandler::new(trainer_side, slot)),
+        MoveName::SuckerPunch => Arc::new(SuckerPunchHandler::new(trainer_side, slot)),
+        MoveName::Counter => Arc::new(CounterHandler::new(trainer_side, slot)),
+        MoveName::Rest => Arc::new(RestHandler::new(trainer_side, slot)),
+        MoveName::BrickBreak => Arc::new(BrickBreakHandler::new(trainer_side, slot)),
+        _ => return base_power_handlers::get_base_power_handler(move_name, trainer_side, slot),
+    };
+
+    Some(handler)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::RestHandler;
+    use crate::{
+        battle::{
+            actions::{Action, MoveModifier, MoveSlot},
+            battle::Battle,
+            battle_engine::BattleEngine,
+            static_battle_handler::StaticBattleHandler,
+            test_utils,
+        },
+        common::context::MoveContext,
+        core::{pokemove::move_name::MoveName, status::status::Status},
+        dex::pokemove::move_dex,
+        handler,
+        query::{
+            query::{CanApplyStatusQuery, Query},
+            query_bus::QueryBus,
+        },
+    };
+
+    // Blocks sleep the way an ability like Insomnia would
+    handler!(SleepImmunityHandler ( s, _state ) {
+        queries {
+            CanApplyStatus( payload ) => {
+                if s.is_own_pokemon(payload.target, payload.target_slot) && payload.status == Status::Sleep {
+                    payload.can_apply = false;
+                }
+            }
+        }
+    });
+
+    const SNORLAX: &str = "Snorlax @ Choice Band\nAbility: Thick Fat\n- Swords Dance";
+
+    fn play_turn(battle: &mut Battle) {
+        let action = Action::Move(MoveSlot::Slot0, MoveModifier::None);
+        let input = test_utils::get_input(battle, action.clone(), action);
+        battle.process_input(input);
+    }
+
+    fn get_hp_lost(battle: &Battle, trainer: bool) -> u16 {
+        let pokemon = &battle.battle_state().get_active_pokemon(trainer, 0).pokemon;
+        pokemon.max_hp - pokemon.hp
+    }
+
+    #[test]
+    fn knock_off_removes_the_target_item() {
+        let mut battle =
+            test_utils::get_battle("Tyranitar\nAbility: Sand Force\n- Knock Off", SNORLAX, 0);
+        play_turn(&mut battle);
+
+        assert!(get_hp_lost(&battle, false) > 0);
+        assert_eq!(
+            battle
+                .battle_state()
+                .get_active_pokemon(false, 0)
+                .pokemon
+                .item,
+            None
+        );
+    }
+
+    #[test]
+    fn sucker_punch_fails_against_a_status_move() {
+        let mut battle =
+            test_utils::get_battle("Tyranitar\nAbility: Sand Force\n- Sucker Punch", SNORLAX, 0);
+        play_turn(&mut battle);
+        assert_eq!(get_hp_lost(&battle, false), 0);
+
+        let mut battle = test_utils::get_battle(
+            "Tyranitar\nAbility: Sand Force\n- Sucker Punch",
+            "Snorlax\nAbility: Thick Fat\n- Tackle",
+            0,
+        );
+        play_turn(&mut battle);
+        assert!(get_hp_lost(&battle, false) > 0);
+    }
+
+    #[test]
+    fn counter_returns_double_physical_damage() {
+        let mut battle = test_utils::get_battle(
+            "Snorlax\nAbility: Thick Fat\n- Counter",
+            "Machamp\nAbility: Technician\n- Tackle",
+            0,
+        );
+        play_turn(&mut battle);
+        assert_eq!(get_hp_lost(&battle, false), 2 * get_hp_lost(&battle, true));
+    }
+
+    const RESTER: &str = "Snorlax\nAbility: Thick Fat\n- Rest";
+
+    fn set_status(battle: &mut Battle, status: Status) {
+        let mut battle_context = battle.battle_context();
+        BattleEngine::apply_status(&mut battle_context, true, 0, status);
+        battle_context
+            .battle_state
+            .get_active_pokemon_mut(true, 0)
+            .pokemon
+            .hp = 1;
+    }
+
+    #[test]
+    fn rest_replaces_an_existing_status() {
+        let mut battle = test_utils::get_battle(RESTER, SNORLAX, 0);
+        set_status(&mut battle, Status::Burn);
+        play_turn(&mut battle);
+
+        let snorlax = battle.battle_state().get_active_pokemon(true, 0);
+        assert_eq!(snorlax.status, Some(Status::Sleep));
+        assert_eq!(snorlax.pokemon.hp, snorlax.pokemon.max_hp);
+    }
+
+    #[test]
+    fn rest_sleeps_for_two_turns_without_a_roll() {
+        let mut battle = test_utils::get_battle(RESTER, SNORLAX, 0);
+        set_status(&mut battle, Status::Burn);
+        let action = Action::Move(MoveSlot::Slot0, MoveModifier::None);
+        let input = test_utils::get_input(&battle, action.clone(), action);
+        let outcomes = battle.get_outcomes(&input);
+
+        assert_eq!(outcomes.len(), 1);
+        let (_, battle, _) = &outcomes[0];
+        assert_eq!(
+            battle
+                .battle_state()
+                .get_active_pokemon(true, 0)
+                .sleep_turns,
+            3
+        );
+    }
+
+    #[test]
+    fn rest_fails_at_full_hp() {
+        let mut battle = test_utils::get_battle(RESTER, SNORLAX, 0);
+        play_turn(&mut battle);
+        assert_eq!(
+            battle.battle_state().get_active_pokemon(true, 0).status,
+            None
+        );
+    }
+
+    #[test]
+    fn rest_does_not_heal_a_sleep_immune_user() {
+        let mut battle = test_utils::get_battle(RESTER, SNORLAX, 0);
+        set_status(&mut battle, Status::Burn);
+        let battle_context = battle.battle_context();
+        let pokemon = &mut battle_context
+            .battle_state
+            .get_active_pokemon_mut(true, 0)
+            .pokemon;
+        pokemon.hp = pokemon.max_hp / 2;
+        battle_context
+            .query_bus
+            .registry
+            .add_handler(Arc::new(SleepImmunityHandler::new(true, 0)));
+        play_turn(&mut battle);
+
+        let snorlax = battle.battle_state().get_active_pokemon(true, 0);
+        assert_eq!(snorlax.status, Some(Status::Burn));
+        assert!(snorlax.pokemon.hp < snorlax.pokemon.max_hp / 2);
+    }
+
+    #[test]
+    fn rest_does_not_override_sleep_immunity() {
+        let mut battle = test_utils::get_battle(RESTER, SNORLAX, 0);
+        set_status(&mut battle, Status::Burn);
+
+        let mut query_bus = QueryBus::new();
+        query_bus
+            .registry
+            .add_handler(Arc::new(StaticBattleHandler));
+        query_bus
+            .registry
+            .add_handler(Arc::new(RestHandler::new(true, 0)));
+        let move_context = MoveContext {
+            src_trainer: true,
+            src_slot: 0,
+            target_trainer: true,
+            target_slot: 0,
+            move_name: MoveName::Rest,
+            pokemove: move_dex::get_move_data(&MoveName::Rest),
+            is_spread: false,
+            base_move: None,
+        };
+        let get_can_apply = |query_bus: &QueryBus, battle: &mut Battle| {
+            let mut query = Query::CanApplyStatus(CanApplyStatusQuery::new(
+                move_context,
+                true,
+                0,
+                Status::Sleep,
+            ));
+            query_bus.query(&mut query, battle.battle_context().battle_state);
+            query.into_can_apply_status_query().can_apply
+        };
+        assert!(get_can_apply(&query_bus, &mut battle));
+
+        query_bus
+            .registry
+            .add_handler(Arc::new(SleepImmunityHandler::new(true, 0)));
+        assert!(!get_can_apply(&query_bus, &mut battle));
+    }
+}
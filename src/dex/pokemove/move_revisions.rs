@@ -0,0 +1,68 @@
+use crate::{
+    core::{mechanics::generation::Generation, pokemove::move_name::MoveName},
+    dex::pokemove::gimmick_moves,
+};
+
+// Overrides for move data in generations where it differs from the move dex
+pub struct MoveRevision {
+    pub power: Option<u32>,
+    pub accuracy: Option<u8>,
+}
+
+const fn power(power: u32) -> Option<MoveRevision> {
+    Some(MoveRevision {
+        power: Some(power),
+        accuracy: None,
+    })
+}
+
+const fn accuracy(accuracy: u8) -> Option<MoveRevision> {
+    Some(MoveRevision {
+        power: None,
+        accuracy: Some(accuracy),
+    })
+}
+
+// Moves newer than Gen 4 have no data for earlier generations
+pub fn introduced_in(move_name: &MoveName) -> Generation {
+    match move_name {
+        MoveName::ElectroBall
+        | MoveName::HeavySlam
+        | MoveName::Hex
+        | MoveName::Acrobatics
+        | MoveName::StoredPower
+        | MoveName::RagePowder => Generation::Gen5,
+        _ if gimmick_moves::is_z_move(move_name) => Generation::Gen7,
+        MoveName::MaxGuard => Generation::Gen8,
+        _ if gimmick_moves::is_max_move(move_name) => Generation::Gen8,
+        _ => Generation::Gen4,
+    }
+}
+
+pub fn get_move_revision(move_name: &MoveName, generation: Generation) -> Option<MoveRevision> {
+    use Generation::*;
+
+    match (move_name, generation) {
+        (MoveName::Tackle, Gen4) => Some(MoveRevision {
+            power: Some(35),
+            accuracy: Some(95),
+        }),
+        (MoveName::Tackle, Gen5 | Gen6) => Some(MoveRevision {
+            power: Some(50),
+            accuracy: Some(100),
+        }),
+        (MoveName::Tackle, Gen7 | Gen8 | Gen9) => Some(MoveRevision {
+            power: Some(40),
+            accuracy: Some(100),
+        }),
+        (MoveName::BulletSeed, Gen4) => power(10),
+        (MoveName::GigaDrain, Gen4) => power(60),
+        (MoveName::DrainPunch, Gen4) => power(60),
+        (MoveName::HeatWave, Gen4 | Gen5) => power(100),
+        (MoveName::Hex, Gen5) => power(50),
+        (MoveName::KnockOff, Gen4 | Gen5) => power(20),
+        (MoveName::SuckerPunch, Gen4 | Gen5 | Gen6) => power(80),
+        (MoveName::WillOWisp, Gen4 | Gen5) => accuracy(75),
+        _ => None,
+    }
+}
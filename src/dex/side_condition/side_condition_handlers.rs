@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use crate::{
+    battle::state::BattleState,
+    core::{
+        pokemove::move_category::MoveCategory,
+        poketype::{effectiveness, pokemon_typing::PokemonTyping, poketype::PokeType},
+        side_condition::side_condition::SideCondition,
+    },
+    dex::combined_handler::CombinedHandler,
+    event::event_handler_effect::EventHandlerEffect,
+    handler,
+};
+
+handler!(SpikesHandler ( s, state ) {
+    events {
+        Switch(switch_event) => {
+            if switch_event.trainer_side != s.trainer_side {
+                return vec![];
+            }
+
+            let pokemon_battle_instance = state.get_active_pokemon(s.trainer_side, switch_event.slot);
+            if pokemon_battle_instance.get_typing().has_type(PokeType::Flying) {
+                return vec![];
+            }
+
+            let max_hp = pokemon_battle_instance.pokemon.max_hp as u32;
+            let spikes_damage = match state.get_side(s.trainer_side).spikes_layers {
+                1 => max_hp / 8,
+                2 => max_hp / 6,
+                _ => max_hp / 4,
+            };
+
+            vec![EventHandlerEffect::Damage(spikes_damage, s.trainer_side, switch_event.slot)]
+        }
+    }
+});
+
+handler!(StealthRockHandler ( s, state ) {
+    events {
+        Switch(switch_event) => {
+            if switch_event.trainer_side != s.trainer_side {
+                return vec![];
+            }
+
+            let generation = state.mechanics.generation;
+            let pokemon_battle_instance = state.get_active_pokemon(s.trainer_side, switch_event.slot);
+            let type_mult = match pokemon_battle_instance.get_typing() {
+                PokemonTyping::MonoType(t) => effectiveness::type_effectiveness(PokeType::Rock, t, generation),
+                PokemonTyping::DualType(t1, t2) => {
+                    effectiveness::type_effectiveness(PokeType::Rock, t1, generation)
+                        * effectiveness::type_effectiveness(PokeType::Rock, t2, generation)
+                }
+            };
+
+            let max_hp = pokemon_battle_instance.pokemon.max_hp as f32;
+            let stealth_rock_damage = (max_hp * type_mult / 8.0).floor() as u32;
+
+            vec![EventHandlerEffect::Damage(stealth_rock_damage, s.trainer_side, switch_event.slot)]
+        }
+    }
+});
+
+// Screens halve damage, or take a third off when more than one pokemon is active
+fn screen_damage(state: &BattleState, trainer_side: bool, damage: u32) -> u32 {
+    if state.get_side(trainer_side).num_active_slots() > 1 {
+        damage * 2 / 3
+    } else {
+        damage / 2
+    }
+}
+
+handler!(ReflectHandler ( s, state ) {
+    events {
+        OnSideTurnEnd(trainer) => {
+            if *trainer != s.trainer_side {
+                return vec![];
+            }
+
+            let side = state.get_side_mut(s.trainer_side);
+            side.reflect_turns = side.reflect_turns.saturating_sub(1);
+            if side.reflect_turns == 0 {
+                vec![EventHandlerEffect::RemoveSideCondition(SideCondition::Reflect, s.trainer_side)]
+            } else {
+                vec![]
+            }
+        }
+    },
+    queries {
+        FinalDamage( payload ) => {
+            let Some(move_context) = payload.move_context else {
+                return;
+            };
+            if move_context.target_trainer != s.trainer_side
+                || state.mechanics.get_move_category(move_context.get_category_move()) != MoveCategory::Physical
+                || move_context.pokemove.fixed_damage.is_some()
+            {
+                return;
+            }
+
+            payload.damage = screen_damage(state, s.trainer_side, payload.damage);
+        }
+    }
+});
+
+handler!(LightScreenHandler ( s, state ) {
+    events {
+        OnSideTurnEnd(trainer) => {
+            if *trainer != s.trainer_side {
+                return vec![];
+            }
+
+            let side = state.get_side_mut(s.trainer_side);
+            side.light_screen_turns = side.light_screen_turns.saturating_sub(1);
+            if side.light_screen_turns == 0 {
+                vec![EventHandlerEffect::RemoveSideCondition(SideCondition::LightScreen, s.trainer_side)]
+            } else {
+                vec![]
+            }
+        }
+    },
+    queries {
+        FinalDamage( payload ) => {
+            let Some(move_context) = payload.move_context else {
+                return;
+            };
+            if move_context.target_trainer != s.trainer_side
+                || state.mechanics.get_move_category(move_context.get_category_move()) != MoveCategory::Special
+                || move_context.pokemove.fixed_damage.is_some()
+            {
+                return;
+            }
+
+            payload.damage = screen_damage(state, s.trainer_side, payload.damage);
+        }
+    }
+});
+
+pub fn get_side_condition_handler(
+    side_condition: SideCondition,
+    trainer: bool,
+) -> Arc<dyn CombinedHandler> {
+    // side conditions cover every slot on their side, so the slot is unused
+    match side_condition {
+        SideCondition::Spikes => Arc::new(SpikesHandler::new(trainer, 0)),
+        SideCondition::StealthRock => Arc::new(StealthRockHandler::new(trainer, 0)),
+        SideCondition::Reflect => Arc::new(ReflectHandler::new(trainer, 0)),
+        SideCondition::LightScreen => Arc::new(LightScreenHandler::new(trainer, 0)),
+    }
+}
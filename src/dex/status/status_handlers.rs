@@ -11,24 +11,25 @@ use crate::{
 
 handler!(BurnHandler ( s, state ) {
     events {
-        OnTurnEnd(trainer) => {
-            if *trainer != s.trainer_side {
+        OnTurnEnd(trainer, slot) => {
+            if !s.is_own_pokemon(*trainer, *slot) {
                 return vec![];
             }
 
-            let target_trainer = s.trainer_side;
-            let target_pokemon = &state.get_active_pokemon(target_trainer).pokemon;
+            let target_pokemon = &state.get_active_pokemon(s.trainer_side, s.slot).pokemon;
 
-            let burn_damage = target_pokemon.max_hp as u32 / 16;
+            let burn_damage = target_pokemon.max_hp as u32 / state.mechanics.burn_damage_divisor;
 
-            vec![EventHandlerEffect::Damage(burn_damage, s.trainer_side)]
+            vec![EventHandlerEffect::Damage(burn_damage, s.trainer_side, s.slot)]
         }
     },
     queries {
         OnMod1( payload ) [priority=1] => {
-            let src_trainer = payload.context.src_trainer;
-            let src_pokemon = &state.get_active_pokemon(src_trainer).pokemon;
-            let move_category = payload.context.pokemove.category;
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            let move_category = state.mechanics.get_move_category(payload.context.get_category_move());
             // TODO: Check GUTS ability when implemented
             if move_category == MoveCategory::Physical {
                 payload.get_vec_f32().push(0.5);
@@ -40,8 +41,8 @@ handler!(BurnHandler ( s, state ) {
 handler!(ParalyzeHandler ( s, state ) {
     queries {
         TryUseMove( payload ) [priority=5] => {
-            let src_trainer = payload.move_context.src_trainer;
-            if src_trainer != s.trainer_side {
+            let move_context = payload.move_context;
+            if !s.is_own_pokemon(move_context.src_trainer, move_context.src_slot) {
                 return;
             }
             if payload.should_cancel {
@@ -52,12 +53,11 @@ handler!(ParalyzeHandler ( s, state ) {
             payload.should_cancel = should_cancel;
         },
         OnStat( payload ) [priority=1] => {
-            let src_trainer = payload.trainer;
-            if src_trainer != s.trainer_side || payload.stat != StatEnum::Speed {
+            if !s.is_own_pokemon(payload.trainer, payload.slot) || payload.stat != StatEnum::Speed {
                 return;
             }
 
-            payload.mults.push(0.5);
+            payload.mults.push(state.mechanics.paralysis_speed_mult);
         }
     }
 });
@@ -65,60 +65,57 @@ handler!(ParalyzeHandler ( s, state ) {
 handler!(SleepHandler ( s, state ) {
     queries {
         TryUseMove( payload ) [priority=0] => {
-            let src_trainer = payload.move_context.src_trainer;
-            if src_trainer != s.trainer_side {
+            let move_context = payload.move_context;
+            if !s.is_own_pokemon(move_context.src_trainer, move_context.src_slot) {
                 return;
             }
             if payload.should_cancel {
                 return;
             }
 
-            let new_sleep_turns = state.get_active_pokemon(src_trainer).sleep_turns - 1;
+            let new_sleep_turns = state.get_active_pokemon(s.trainer_side, s.slot).sleep_turns - 1;
             if new_sleep_turns > 0 {
                 payload.should_cancel = true;
             } else if new_sleep_turns == 0 {
                 payload.wake_sleep = true;
             }
 
-            state.get_active_pokemon_mut(src_trainer).sleep_turns = new_sleep_turns;
+            state.get_active_pokemon_mut(s.trainer_side, s.slot).sleep_turns = new_sleep_turns;
         }
     }
 });
 
 handler!(PoisonHandler ( s, state ) {
     events {
-        OnTurnEnd(trainer) => {
-            if *trainer != s.trainer_side {
+        OnTurnEnd(trainer, slot) => {
+            if !s.is_own_pokemon(*trainer, *slot) {
                 return vec![];
             }
 
-            let target_trainer = s.trainer_side;
-            let target_pokemon = &state.get_active_pokemon(target_trainer).pokemon;
+            let target_pokemon = &state.get_active_pokemon(s.trainer_side, s.slot).pokemon;
 
             let poison_damage = target_pokemon.max_hp as u32 / 8;
 
-            vec![EventHandlerEffect::Damage(poison_damage, s.trainer_side)]
+            vec![EventHandlerEffect::Damage(poison_damage, s.trainer_side, s.slot)]
         }
     }
 });
 
 handler!(BadlyPoisonHandler ( s, state ) {
     events {
-        OnTurnEnd(trainer) => {
-            if *trainer != s.trainer_side {
+        OnTurnEnd(trainer, slot) => {
+            if !s.is_own_pokemon(*trainer, *slot) {
                 return vec![];
             }
 
-            let target_trainer = s.trainer_side;
-            let target_max_hp = state.get_active_pokemon(target_trainer).pokemon.max_hp;
-            let curr_badly_poison_turns = state.get_active_pokemon(target_trainer).badly_poison_turns;
-            let new_badly_poison_turns = std::cmp::min(curr_badly_poison_turns + 1, 15);
-
-            state.get_active_pokemon_mut(target_trainer).badly_poison_turns = new_badly_poison_turns;
+            let target_pokemon = state.get_active_pokemon_mut(s.trainer_side, s.slot);
+            let target_max_hp = target_pokemon.pokemon.max_hp;
+            let new_badly_poison_turns = std::cmp::min(target_pokemon.badly_poison_turns + 1, 15);
+            target_pokemon.badly_poison_turns = new_badly_poison_turns;
 
             let poison_damage = (target_max_hp * new_badly_poison_turns as u16) as u32 / 8;
 
-            vec![EventHandlerEffect::Damage(poison_damage, s.trainer_side)]
+            vec![EventHandlerEffect::Damage(poison_damage, s.trainer_side, s.slot)]
         }
     }
 });
@@ -126,8 +123,8 @@ handler!(BadlyPoisonHandler ( s, state ) {
 handler!(FrozenHandler ( s, state ) {
     queries {
         TryUseMove( payload ) [priority=1] => {
-            let src_trainer = payload.move_context.src_trainer;
-            if src_trainer != s.trainer_side {
+            let move_context = payload.move_context;
+            if !s.is_own_pokemon(move_context.src_trainer, move_context.src_slot) {
                 return;
             }
             if payload.should_cancel {
@@ -144,26 +141,14 @@ handler!(FrozenHandler ( s, state ) {
     }
 });
 
-pub fn get_status_handler(status: Status, trainer: bool) -> Arc<dyn CombinedHandler> {
+pub fn get_status_handler(status: Status, trainer: bool, slot: usize) -> Arc<dyn CombinedHandler> {
     match status {
-        Status::Burn => Arc::new(BurnHandler {
-            trainer_side: trainer,
-        }),
-        Status::Paralyze => Arc::new(ParalyzeHandler {
-            trainer_side: trainer,
-        }),
-        Status::Sleep => Arc::new(SleepHandler {
-            trainer_side: trainer,
-        }),
-        Status::Poison => Arc::new(PoisonHandler {
-            trainer_side: trainer,
-        }),
-        Status::BadlyPoison => Arc::new(BadlyPoisonHandler {
-            trainer_side: trainer,
-        }),
-        Status::Frozen => Arc::new(FrozenHandler {
-            trainer_side: trainer,
-        }),
+        Status::Burn => Arc::new(BurnHandler::new(trainer, slot)),
+        Status::Paralyze => Arc::new(ParalyzeHandler::new(trainer, slot)),
+        Status::Sleep => Arc::new(SleepHandler::new(trainer, slot)),
+        Status::Poison => Arc::new(PoisonHandler::new(trainer, slot)),
+        Status::BadlyPoison => Arc::new(BadlyPoisonHandler::new(trainer, slot)),
+        Status::Frozen => Arc::new(FrozenHandler::new(trainer, slot)),
         Status::Faint => panic!("Faint status should not have a handler"),
     }
 }
@@ -1,22 +1,26 @@
 use std::sync::Arc;
 
 use crate::{
-    core::status::volatile_status::VolatileStatus, dex::combined_handler::CombinedHandler,
-    event::event_handler_effect::EventHandlerEffect, handler,
+    battle::state::BattleState,
+    core::{poketype::poketype::PokeType, status::volatile_status::VolatileStatus},
+    dex::combined_handler::CombinedHandler,
+    event::event_handler_effect::EventHandlerEffect,
+    handler,
+    query::{payload::Payload, query::RedirectTargetQuery},
 };
 
 handler!(ConfusionHandler ( s, state ) {
     queries {
         TryUseMove( payload ) [priority=4] => {
-            let src_trainer = payload.move_context.src_trainer;
-            if src_trainer != s.trainer_side {
+            let move_context = payload.move_context;
+            if !s.is_own_pokemon(move_context.src_trainer, move_context.src_slot) {
                 return;
             }
             if payload.should_cancel {
                 return;
             }
 
-            let new_confusion_turns = state.get_active_pokemon(src_trainer).confusion_turns - 1;
+            let new_confusion_turns = state.get_active_pokemon(s.trainer_side, s.slot).confusion_turns - 1;
             if new_confusion_turns > 0 {
                 if state.get_random_check(1, 2) {
                     payload.should_cancel = true;
@@ -26,7 +30,7 @@ handler!(ConfusionHandler ( s, state ) {
                 payload.unconfuse = true;
             }
 
-            state.get_active_pokemon_mut(src_trainer).confusion_turns = new_confusion_turns;
+            state.get_active_pokemon_mut(s.trainer_side, s.slot).confusion_turns = new_confusion_turns;
         },
     }
 });
@@ -34,8 +38,8 @@ handler!(ConfusionHandler ( s, state ) {
 handler!(InfatuationHandler ( s, state ) {
     queries {
         TryUseMove( payload ) [priority=6] => {
-            let src_trainer = payload.move_context.src_trainer;
-            if src_trainer != s.trainer_side {
+            let move_context = payload.move_context;
+            if !s.is_own_pokemon(move_context.src_trainer, move_context.src_slot) {
                 return;
             }
             if payload.should_cancel {
@@ -50,25 +54,41 @@ handler!(InfatuationHandler ( s, state ) {
 
 handler!(LeechSeedHandler ( s, state ) {
     events {
-        OnTurnEnd(trainer) => {
-            if *trainer != s.trainer_side {
+        OnTurnEnd(trainer, slot) => {
+            if !s.is_own_pokemon(*trainer, *slot) {
                 return vec![];
             }
-            let target_trainer = s.trainer_side;
-            let target_pokemon = &state.get_active_pokemon(target_trainer).pokemon;
+            let target_pokemon = state.get_active_pokemon(s.trainer_side, s.slot);
 
-            let leech_seed_damage = target_pokemon.max_hp as u32 / 8;
+            let leech_seed_damage = target_pokemon.pokemon.max_hp as u32 / 8;
 
-            vec![EventHandlerEffect::DamageAndHeal(leech_seed_damage, target_trainer, !target_trainer)]
+            // heals whichever opposing pokemon is in the slot that planted the seed
+            vec![EventHandlerEffect::DamageAndHeal(
+                leech_seed_damage,
+                s.trainer_side,
+                s.slot,
+                !s.trainer_side,
+                target_pokemon.leech_seed_slot,
+            )]
         }
     }
 });
 
+// Flinching only lasts for the turn it was caused
 handler!(FlinchHandler ( s, state ) {
+    events {
+        OnTurnEnd(trainer, slot) => {
+            if !s.is_own_pokemon(*trainer, *slot) {
+                return vec![];
+            }
+
+            vec![EventHandlerEffect::RemoveVolatileStatus(VolatileStatus::Flinch, s.trainer_side, s.slot)]
+        }
+    },
     queries {
         TryUseMove( payload ) [priority=3] => {
-            let src_trainer = payload.move_context.src_trainer;
-            if src_trainer != s.trainer_side {
+            let move_context = payload.move_context;
+            if !s.is_own_pokemon(move_context.src_trainer, move_context.src_slot) {
                 return;
             }
             if payload.should_cancel {
@@ -80,22 +100,160 @@ handler!(FlinchHandler ( s, state ) {
     }
 });
 
+handler!(EncoreHandler ( s, state ) {
+    events {
+        OnTurnEnd(trainer, slot) => {
+            if !s.is_own_pokemon(*trainer, *slot) {
+                return vec![];
+            }
+
+            let pokemon_battle_instance = state.get_active_pokemon_mut(s.trainer_side, s.slot);
+            pokemon_battle_instance.encore_turns = pokemon_battle_instance.encore_turns.saturating_sub(1);
+
+            let out_of_pp = pokemon_battle_instance.encore_move.is_none_or(|move_name| {
+                let idx = pokemon_battle_instance.pokemon.get_idx_for_move_name(&move_name);
+                pokemon_battle_instance.pp[idx] == 0
+            });
+
+            if pokemon_battle_instance.encore_turns == 0 || out_of_pp {
+                vec![EventHandlerEffect::RemoveVolatileStatus(VolatileStatus::Encore, s.trainer_side, s.slot)]
+            } else {
+                vec![]
+            }
+        }
+    }
+});
+
+handler!(RoostHandler ( s, state ) {
+    events {
+        OnTurnEnd(trainer, slot) => {
+            if !s.is_own_pokemon(*trainer, *slot) {
+                return vec![];
+            }
+
+            vec![EventHandlerEffect::RemoveVolatileStatus(VolatileStatus::Roost, s.trainer_side, s.slot)]
+        }
+    }
+});
+
+// Follow Me and Rage Powder draw single target moves from the opposing side
+fn should_redirect(
+    state: &BattleState,
+    payload: &RedirectTargetQuery,
+    trainer_side: bool,
+    ignore_grass: bool,
+) -> bool {
+    let move_context = payload.move_context;
+    if move_context.src_trainer == trainer_side || move_context.target_trainer != trainer_side {
+        return false;
+    }
+
+    // Rage Powder is a powder move, so grass types are unaffected
+    let src_typing = state
+        .get_active_pokemon(move_context.src_trainer, move_context.src_slot)
+        .get_typing();
+    !(ignore_grass && src_typing.has_type(PokeType::Grass))
+}
+
+handler!(FollowMeHandler ( s, state ) {
+    events {
+        OnTurnEnd(trainer, slot) => {
+            if !s.is_own_pokemon(*trainer, *slot) {
+                return vec![];
+            }
+
+            vec![EventHandlerEffect::RemoveVolatileStatus(VolatileStatus::FollowMe, s.trainer_side, s.slot)]
+        }
+    },
+    queries {
+        RedirectTarget( payload ) => {
+            if should_redirect(state, payload, s.trainer_side, false) {
+                payload.target_slot = s.slot;
+            }
+        }
+    }
+});
+
+handler!(RagePowderHandler ( s, state ) {
+    events {
+        OnTurnEnd(trainer, slot) => {
+            if !s.is_own_pokemon(*trainer, *slot) {
+                return vec![];
+            }
+
+            vec![EventHandlerEffect::RemoveVolatileStatus(VolatileStatus::RagePowder, s.trainer_side, s.slot)]
+        }
+    },
+    queries {
+        RedirectTarget( payload ) => {
+            if should_redirect(state, payload, s.trainer_side, true) {
+                payload.target_slot = s.slot;
+            }
+        }
+    }
+});
+
+handler!(HelpingHandHandler ( s, state ) {
+    events {
+        OnTurnEnd(trainer, slot) => {
+            if !s.is_own_pokemon(*trainer, *slot) {
+                return vec![];
+            }
+
+            vec![EventHandlerEffect::RemoveVolatileStatus(VolatileStatus::HelpingHand, s.trainer_side, s.slot)]
+        }
+    },
+    queries {
+        OnBasePower( payload ) => {
+            if !s.is_own_pokemon(payload.context.src_trainer, payload.context.src_slot) {
+                return;
+            }
+
+            payload.get_vec_f32().push(1.5);
+        }
+    }
+});
+
+// Blocks every move from other pokemon for the rest of the turn, Max Moves included
+handler!(MaxGuardHandler ( s, state ) {
+    events {
+        OnTurnEnd(trainer, slot) => {
+            if !s.is_own_pokemon(*trainer, *slot) {
+                return vec![];
+            }
+
+            vec![EventHandlerEffect::RemoveVolatileStatus(VolatileStatus::MaxGuard, s.trainer_side, s.slot)]
+        }
+    },
+    queries {
+        CheckInvulnerability( payload ) => {
+            let move_context = payload.context;
+            if !s.is_own_pokemon(move_context.target_trainer, move_context.target_slot)
+                || s.is_own_pokemon(move_context.src_trainer, move_context.src_slot)
+            {
+                return;
+            }
+
+            payload.payload = Payload::Bool(false);
+        }
+    }
+});
+
 pub fn get_volatile_status_handler(
     status: VolatileStatus,
     trainer: bool,
+    slot: usize,
 ) -> Arc<dyn CombinedHandler> {
     match status {
-        VolatileStatus::Confusion => Arc::new(ConfusionHandler {
-            trainer_side: trainer,
-        }),
-        VolatileStatus::Infatuation => Arc::new(InfatuationHandler {
-            trainer_side: trainer,
-        }),
-        VolatileStatus::LeechSeed => Arc::new(LeechSeedHandler {
-            trainer_side: trainer,
-        }),
-        VolatileStatus::Flinch => Arc::new(FlinchHandler {
-            trainer_side: trainer,
-        }),
+        VolatileStatus::Confusion => Arc::new(ConfusionHandler::new(trainer, slot)),
+        VolatileStatus::Infatuation => Arc::new(InfatuationHandler::new(trainer, slot)),
+        VolatileStatus::LeechSeed => Arc::new(LeechSeedHandler::new(trainer, slot)),
+        VolatileStatus::Flinch => Arc::new(FlinchHandler::new(trainer, slot)),
+        VolatileStatus::Encore => Arc::new(EncoreHandler::new(trainer, slot)),
+        VolatileStatus::Roost => Arc::new(RoostHandler::new(trainer, slot)),
+        VolatileStatus::FollowMe => Arc::new(FollowMeHandler::new(trainer, slot)),
+        VolatileStatus::RagePowder => Arc::new(RagePowderHandler::new(trainer, slot)),
+        VolatileStatus::HelpingHand => Arc::new(HelpingHandHandler::new(trainer, slot)),
+        VolatileStatus::MaxGuard => Arc::new(MaxGuardHandler::new(trainer, slot)),
     }
 }
@@ -0,0 +1,86 @@
+use crate::battle::{
+    actions::{Action, MoveModifier, MoveSlot, SwitchSlot},
+    battle_input::SingleInput,
+    battle_request::SingleBattleRequest,
+    valid_actions::ValidMoves,
+};
+
+// Flat action space for a singles slot
+//  0..4    move in slot i
+//  4..8    move in slot i, mega evolving first
+//  8..12   move in slot i as a Z-Move
+//  12..16  move in slot i, dynamaxing first
+//  16..20  move in slot i, terastallizing first
+//  20..26  switch to party index i, or send it in after a faint
+//  Struggle and continuing a locked move are both action 0
+pub const NUM_MOVES: usize = 4;
+pub const NUM_SWITCHES: usize = 6;
+pub const ACTION_SPACE: usize = NUM_MOVES * MOVE_MODIFIERS.len() + NUM_SWITCHES;
+const SWITCH_OFFSET: usize = NUM_MOVES * MOVE_MODIFIERS.len();
+const MOVE_MODIFIERS: [MoveModifier; 5] = [
+    MoveModifier::None,
+    MoveModifier::MegaEvolve,
+    MoveModifier::ZMove,
+    MoveModifier::Dynamax,
+    MoveModifier::Terastallize,
+];
+
+pub fn get_action_mask(request: &SingleBattleRequest) -> [bool; ACTION_SPACE] {
+    let mut mask = [false; ACTION_SPACE];
+    match request {
+        SingleBattleRequest::SwitchInRequest(valid_switches) => {
+            for idx in &valid_switches.switches {
+                mask[SWITCH_OFFSET + idx] = true;
+            }
+        }
+        SingleBattleRequest::ActionRequest(valid_actions) => {
+            match &valid_actions.valid_moves {
+                ValidMoves::Struggle | ValidMoves::Locked => mask[0] = true,
+                ValidMoves::Moves(move_idxs) => {
+                    for idx in move_idxs {
+                        let modifiers = [
+                            true,
+                            valid_actions.can_mega_evolve,
+                            valid_actions.z_moves.contains(idx),
+                            valid_actions.can_dynamax,
+                            valid_actions.can_terastallize,
+                        ];
+                        for (modifier_idx, available) in modifiers.into_iter().enumerate() {
+                            mask[modifier_idx * NUM_MOVES + idx] = available;
+                        }
+                    }
+                }
+            }
+            if let Some(valid_switches) = &valid_actions.valid_switches {
+                for idx in &valid_switches.switches {
+                    mask[SWITCH_OFFSET + idx] = true;
+                }
+            }
+        }
+    }
+    mask
+}
+
+// Turns an action the mask allows back into an input for the request
+pub fn to_input(action: usize, request: &SingleBattleRequest) -> SingleInput {
+    match request {
+        SingleBattleRequest::SwitchInRequest(_) => {
+            SingleInput::SwitchInInput(SwitchSlot::from_index(action - SWITCH_OFFSET))
+        }
+        SingleBattleRequest::ActionRequest(_) if action >= SWITCH_OFFSET => {
+            SingleInput::StandardInput(Action::Switch(SwitchSlot::from_index(
+                action - SWITCH_OFFSET,
+            )))
+        }
+        SingleBattleRequest::ActionRequest(valid_actions) => {
+            SingleInput::StandardInput(match valid_actions.valid_moves {
+                ValidMoves::Struggle => Action::Struggle,
+                ValidMoves::Locked => Action::Locked,
+                ValidMoves::Moves(_) => Action::Move(
+                    MoveSlot::from_index(action % NUM_MOVES),
+                    MOVE_MODIFIERS[action / NUM_MOVES],
+                ),
+            })
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod action_space;
+pub mod observation_encoder;
+pub mod reward;
+pub mod vec_env;
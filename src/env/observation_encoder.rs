@@ -0,0 +1,337 @@
+use enum_map::Enum;
+use strum::EnumCount;
+
+use crate::{
+    battle::side_observation::{PokemonView, SideObservation, SideView},
+    core::{
+        ability::ability::Ability,
+        field::weather::Weather,
+        item::item::Item,
+        pokemon::{boostable_stat::BoostableStat, species::Species},
+        pokemove::move_name::MoveName,
+        poketype::{pokemon_typing::PokemonTyping, poketype::PokeType},
+        status::{status::Status, volatile_status::VolatileStatus},
+    },
+    dex::pokemove::move_dex,
+};
+
+// Bumped whenever the layout below changes, so saved models can check what they were
+//  trained on
+pub const ENCODER_VERSION: u32 = 2;
+
+pub const PARTY_SIZE: usize = 6;
+const NUM_MOVES: usize = 4;
+const MAX_BOOST: f32 = 6.0;
+const MAX_SPIKES_LAYERS: f32 = 3.0;
+const MAX_SCREEN_TURNS: f32 = 8.0;
+const MAX_WEATHER_TURNS: f32 = 8.0;
+const MAX_DYNAMAX_TURNS: f32 = 3.0;
+const TURN_SCALE: f32 = 100.0; // turns past this are encoded as 1
+
+// Feature groups of one party slot, as (name, size). Enum valued groups are one-hot over
+//  the enum's variants in declaration order, left at 0 when there is no value or the
+//  opponent hasn't revealed it yet
+const POKEMON_LAYOUT: [(&str, usize); 16] = [
+    ("present", 1),
+    ("active", 1),
+    ("species", Species::COUNT),
+    ("types", PokeType::COUNT),     // current types, after tera and Roost
+    ("tera_type", PokeType::COUNT), // revealed by terastallizing
+    ("terastallized", 1),
+    ("status", Status::COUNT),
+    ("volatile_statuses", VolatileStatus::COUNT),
+    ("item_known", 1), // set with an empty item one-hot when known to hold nothing
+    ("item", Item::COUNT),
+    ("ability", Ability::COUNT),
+    ("hp", 1),                              // health bar percentage divided by 100
+    ("boosts", BoostableStat::LENGTH),      // stages divided by 6
+    ("moves", NUM_MOVES * MoveName::COUNT), // one-hot per move slot
+    ("pp", NUM_MOVES),                      // fraction of max pp per move slot
+    ("dynamax_turns", 1),
+];
+
+// Feature groups of one side's field state
+const SIDE_LAYOUT: [(&str, usize); 10] = [
+    ("team_preview", Species::COUNT), // multi-hot of the species shown at team preview
+    ("spikes_layers", 1),
+    ("stealth_rock", 1),
+    ("reflect_turns", 1),
+    ("light_screen_turns", 1),
+    ("mega_used", 1),
+    ("z_move_used", 1),
+    ("dynamax_used", 1),
+    ("tera_used", 1),
+    ("usable_pokemon", 1), // fraction of the party not known to have fainted
+];
+
+// Feature groups shared by both sides
+const FIELD_LAYOUT: [(&str, usize); 3] = [
+    ("weather", Weather::COUNT),
+    ("weather_turns", 1),
+    ("turn", 1),
+];
+
+const fn get_layout_size(layout: &[(&str, usize)]) -> usize {
+    let mut size = 0;
+    let mut idx = 0;
+    while idx < layout.len() {
+        size += layout[idx].1;
+        idx += 1;
+    }
+    size
+}
+
+pub const POKEMON_FEATURES: usize = get_layout_size(&POKEMON_LAYOUT);
+pub const SIDE_FEATURES: usize = get_layout_size(&SIDE_LAYOUT);
+pub const FIELD_FEATURES: usize = get_layout_size(&FIELD_LAYOUT);
+
+// The observing side's party in party order, then the opponent's pokemon in the order
+//  they were first seen, each zero-padded to six slots, then the observing side's field
+//  state, the opponent's and the field
+pub const OBSERVATION_SIZE: usize =
+    2 * PARTY_SIZE * POKEMON_FEATURES + 2 * SIDE_FEATURES + FIELD_FEATURES;
+
+// Every feature group in the observation as (name, offset, size), names are prefixed
+//  with "self_pokemon_<slot>_", "opponent_pokemon_<slot>_", "self_side_", "opponent_side_"
+//  or "field_"
+pub fn get_feature_layout() -> Vec<(String, usize, usize)> {
+    let mut groups: Vec<(String, &[(&str, usize)])> = vec![];
+    for side in ["self", "opponent"] {
+        for slot in 0..PARTY_SIZE {
+            groups.push((format!("{side}_pokemon_{slot}_"), &POKEMON_LAYOUT));
+        }
+    }
+    groups.push(("self_side_".to_string(), &SIDE_LAYOUT));
+    groups.push(("opponent_side_".to_string(), &SIDE_LAYOUT));
+    groups.push(("field_".to_string(), &FIELD_LAYOUT));
+
+    let mut offset = 0;
+    let mut feature_layout = vec![];
+    for (prefix, layout) in groups {
+        for (name, size) in layout {
+            feature_layout.push((format!("{prefix}{name}"), offset, *size));
+            offset += size;
+        }
+    }
+    feature_layout
+}
+
+// Writes features one after another, in layout order
+struct FeatureWriter<'a> {
+    features: &'a mut [f32],
+    offset: usize,
+}
+
+impl FeatureWriter<'_> {
+    fn push(&mut self, value: f32) {
+        self.features[self.offset] = value;
+        self.offset += 1;
+    }
+
+    fn push_flag(&mut self, flag: bool) {
+        self.push(if flag { 1.0 } else { 0.0 });
+    }
+
+    fn push_one_hot(&mut self, idx: Option<usize>, size: usize) {
+        self.push_multi_hot(idx, size);
+    }
+
+    fn push_multi_hot(&mut self, idxs: impl IntoIterator<Item = usize>, size: usize) {
+        self.features[self.offset..self.offset + size].fill(0.0);
+        for idx in idxs {
+            self.features[self.offset + idx] = 1.0;
+        }
+        self.offset += size;
+    }
+
+    fn skip(&mut self, size: usize) {
+        self.features[self.offset..self.offset + size].fill(0.0);
+        self.offset += size;
+    }
+}
+
+// One side's view of the battle as a fixed length vector, see the layout above
+pub fn encode_observation(observation: &SideObservation) -> Vec<f32> {
+    let mut features = vec![0.0; OBSERVATION_SIZE];
+    encode_observation_into(observation, &mut features);
+    features
+}
+
+// Same as encode_observation, writing into a slice of OBSERVATION_SIZE features
+pub fn encode_observation_into(observation: &SideObservation, features: &mut [f32]) {
+    if features.len() != OBSERVATION_SIZE {
+        panic!(
+            "Observation needs {OBSERVATION_SIZE} features, got a slice of {}",
+            features.len()
+        );
+    }
+
+    let mut writer = FeatureWriter {
+        features,
+        offset: 0,
+    };
+    let sides = [&observation.own_side, &observation.opponent_side];
+    for side in sides {
+        for idx in 0..PARTY_SIZE {
+            match side.pokemon.get(idx) {
+                Some(pokemon) => encode_pokemon(&mut writer, pokemon),
+                None => writer.skip(POKEMON_FEATURES),
+            }
+        }
+    }
+    for side in sides {
+        encode_side(&mut writer, side);
+    }
+    encode_field(&mut writer, observation);
+}
+
+fn encode_pokemon(writer: &mut FeatureWriter, pokemon: &PokemonView) {
+    writer.push_flag(true);
+    writer.push_flag(pokemon.is_active);
+    writer.push_one_hot(Some(pokemon.species as usize), Species::COUNT);
+    let types = match pokemon.typing {
+        PokemonTyping::MonoType(poke_type) => vec![poke_type as usize],
+        PokemonTyping::DualType(type_1, type_2) => vec![type_1 as usize, type_2 as usize],
+    };
+    writer.push_multi_hot(types, PokeType::COUNT);
+    writer.push_one_hot(
+        pokemon.tera_type.map(|tera_type| tera_type as usize),
+        PokeType::COUNT,
+    );
+    writer.push_flag(pokemon.terastallized);
+    writer.push_one_hot(pokemon.status.map(|status| status as usize), Status::COUNT);
+    writer.push_multi_hot(
+        pokemon
+            .volatile_statuses
+            .iter()
+            .map(|volatile_status| *volatile_status as usize),
+        VolatileStatus::COUNT,
+    );
+    writer.push_flag(pokemon.item.is_some());
+    writer.push_one_hot(
+        pokemon.item.flatten().map(|item| item as usize),
+        Item::COUNT,
+    );
+    writer.push_one_hot(
+        pokemon.ability.map(|ability| ability as usize),
+        Ability::COUNT,
+    );
+    writer.push(pokemon.hp_percent as f32 / 100.0);
+    for boost in pokemon.boosts {
+        writer.push(boost as f32 / MAX_BOOST);
+    }
+    for move_name in pokemon.moves {
+        writer.push_one_hot(
+            move_name.map(|move_name| move_name as usize),
+            MoveName::COUNT,
+        );
+    }
+    for (move_name, pp) in pokemon.moves.iter().zip(pokemon.pp) {
+        let max_pp = move_name.map_or(0, |move_name| move_dex::get_move_pp(&move_name));
+        writer.push(match pp {
+            Some(pp) if max_pp > 0 => pp as f32 / max_pp as f32,
+            _ => 0.0,
+        });
+    }
+    writer.push(pokemon.dynamax_turns as f32 / MAX_DYNAMAX_TURNS);
+}
+
+fn encode_side(writer: &mut FeatureWriter, side: &SideView) {
+    writer.push_multi_hot(
+        side.team_preview.iter().map(|species| *species as usize),
+        Species::COUNT,
+    );
+    writer.push(side.spikes_layers as f32 / MAX_SPIKES_LAYERS);
+    writer.push_flag(side.stealth_rock);
+    writer.push(side.reflect_turns as f32 / MAX_SCREEN_TURNS);
+    writer.push(side.light_screen_turns as f32 / MAX_SCREEN_TURNS);
+    writer.push_flag(side.mega_used);
+    writer.push_flag(side.z_move_used);
+    writer.push_flag(side.dynamax_used);
+    writer.push_flag(side.tera_used);
+    let fainted = side
+        .pokemon
+        .iter()
+        .filter(|pokemon| pokemon.hp_percent == 0)
+        .count();
+    writer.push((side.party_size - fainted) as f32 / side.party_size.max(1) as f32);
+}
+
+fn encode_field(writer: &mut FeatureWriter, observation: &SideObservation) {
+    writer.push_one_hot(
+        observation.weather.map(|weather| weather as usize),
+        Weather::COUNT,
+    );
+    writer.push(observation.weather_turns as f32 / MAX_WEATHER_TURNS);
+    writer.push((observation.turn as f32 / TURN_SCALE).min(1.0));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::test_utils;
+
+    const TEAM: &str = "Garchomp @ Choice Band\nAbility: Sand Force\n- Earthquake\n\n\
+        Snorlax\nAbility: Thick Fat\n- Tackle";
+
+    fn get_features<'a>(features: &'a [f32], name: &str) -> &'a [f32] {
+        let (_, offset, size) = get_feature_layout()
+            .into_iter()
+            .find(|(group, _, _)| group == name)
+            .expect("Feature group should exist");
+        &features[offset..offset + size]
+    }
+
+    #[test]
+    fn layout_covers_the_observation() {
+        let mut next_offset = 0;
+        for (_, offset, size) in get_feature_layout() {
+            assert_eq!(offset, next_offset);
+            next_offset += size;
+        }
+        assert_eq!(next_offset, OBSERVATION_SIZE);
+    }
+
+    #[test]
+    fn encodes_own_party_and_only_the_seen_opponents() {
+        let battle = test_utils::get_battle(TEAM, TEAM, 0);
+        let features = encode_observation(&SideObservation::new(battle.battle_state(), true));
+
+        assert_eq!(get_features(&features, "self_pokemon_0_active"), [1.0]);
+        assert_eq!(
+            get_features(&features, "self_pokemon_0_species")[Species::Garchomp as usize],
+            1.0
+        );
+        assert_eq!(get_features(&features, "self_pokemon_0_hp"), [1.0]);
+        assert_eq!(get_features(&features, "self_pokemon_1_present"), [1.0]);
+        assert_eq!(get_features(&features, "self_pokemon_1_active"), [0.0]);
+        assert_eq!(get_features(&features, "self_pokemon_0_item_known"), [1.0]);
+
+        // the opponent's lead is seen, but not its item or its benched pokemon
+        assert_eq!(get_features(&features, "opponent_pokemon_0_present"), [1.0]);
+        assert_eq!(
+            get_features(&features, "opponent_pokemon_0_item_known"),
+            [0.0]
+        );
+        assert!(
+            get_features(&features, "opponent_pokemon_1_species")
+                .iter()
+                .all(|feature| *feature == 0.0)
+        );
+        assert_eq!(
+            get_features(&features, "opponent_side_usable_pokemon"),
+            [1.0]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Observation needs")]
+    fn rejects_slices_of_the_wrong_size() {
+        let battle = test_utils::get_battle(TEAM, TEAM, 0);
+        let mut features = vec![0.0; OBSERVATION_SIZE - 1];
+        encode_observation_into(
+            &SideObservation::new(battle.battle_state(), true),
+            &mut features,
+        );
+    }
+}
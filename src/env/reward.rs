@@ -0,0 +1,286 @@
+use typed_builder::TypedBuilder;
+
+use crate::{
+    battle::{battle_request::Winner, state::BattleState, state::SingleSideState},
+    core::{side_condition::side_condition::SideCondition, status::status::Status},
+};
+
+// Weight of each reward component, from trainer 1's side. Every component counts
+//  something happening, so costs like hp_taken need a negative weight. Only the
+//  terminal rewards are set by default
+#[derive(Clone, Debug, TypedBuilder)]
+pub struct RewardWeights {
+    #[builder(default = 1.0)]
+    pub win: f32,
+    #[builder(default = -1.0)]
+    pub loss: f32,
+    // battles cut off by the turn limit only draw when both sides have the same pokemon
+    //  and team hp left, otherwise they are a win or a loss
+    #[builder(default)]
+    pub draw: f32,
+    #[builder(default)]
+    pub hp_dealt: f32, // per opposing max hp lost, net of healing
+    #[builder(default)]
+    pub hp_taken: f32,
+    #[builder(default)]
+    pub faints_dealt: f32, // per pokemon
+    #[builder(default)]
+    pub faints_taken: f32,
+    #[builder(default)]
+    pub status_inflicted: f32, // per new non-volatile status
+    #[builder(default)]
+    pub status_taken: f32,
+    #[builder(default)]
+    pub hazards_set: f32, // per spikes layer or stealth rock added
+    #[builder(default)]
+    pub hazards_taken: f32,
+    #[builder(default)]
+    pub boosts_gained: f32, // per net stage on the active pokemon, lost again on switching
+    #[builder(default)]
+    pub opponent_boosts_gained: f32,
+}
+
+impl Default for RewardWeights {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl RewardWeights {
+    // Every weight as (name, weight), named like the fields
+    pub fn get_weights(&self) -> Vec<(&'static str, f32)> {
+        vec![
+            ("win", self.win),
+            ("loss", self.loss),
+            ("draw", self.draw),
+            ("hp_dealt", self.hp_dealt),
+            ("hp_taken", self.hp_taken),
+            ("faints_dealt", self.faints_dealt),
+            ("faints_taken", self.faints_taken),
+            ("status_inflicted", self.status_inflicted),
+            ("status_taken", self.status_taken),
+            ("hazards_set", self.hazards_set),
+            ("hazards_taken", self.hazards_taken),
+            ("boosts_gained", self.boosts_gained),
+            ("opponent_boosts_gained", self.opponent_boosts_gained),
+        ]
+    }
+
+    pub fn set_weight(&mut self, name: &str, weight: f32) -> Result<(), String> {
+        let field = match name {
+            "win" => &mut self.win,
+            "loss" => &mut self.loss,
+            "draw" => &mut self.draw,
+            "hp_dealt" => &mut self.hp_dealt,
+            "hp_taken" => &mut self.hp_taken,
+            "faints_dealt" => &mut self.faints_dealt,
+            "faints_taken" => &mut self.faints_taken,
+            "status_inflicted" => &mut self.status_inflicted,
+            "status_taken" => &mut self.status_taken,
+            "hazards_set" => &mut self.hazards_set,
+            "hazards_taken" => &mut self.hazards_taken,
+            "boosts_gained" => &mut self.boosts_gained,
+            "opponent_boosts_gained" => &mut self.opponent_boosts_gained,
+            _ => return Err(format!("unknown reward component {name}")),
+        };
+        *field = weight;
+        Ok(())
+    }
+}
+
+// What the shaping rewards are worked out from, taken before and after each step
+#[derive(Clone)]
+pub struct RewardSnapshot {
+    sides: [SideSnapshot; 2], // trainer 1, then trainer 2
+}
+
+#[derive(Clone)]
+struct SideSnapshot {
+    hp: Vec<f32>, // fraction of max hp per party slot
+    statuses: Vec<Option<Status>>,
+    hazards: u32,
+    boosts: i32, // total stages over the active pokemon
+}
+
+// Changes on one side between two snapshots
+struct SideDelta {
+    hp_lost: f32,
+    faints: u32,
+    statuses: u32,
+    hazards: u32,
+    boosts: i32,
+}
+
+impl RewardSnapshot {
+    pub fn new(battle_state: &BattleState) -> Self {
+        Self {
+            sides: [
+                SideSnapshot::new(battle_state.get_side(true)),
+                SideSnapshot::new(battle_state.get_side(false)),
+            ],
+        }
+    }
+}
+
+impl SideSnapshot {
+    fn new(side: &SingleSideState) -> Self {
+        let party = (0..side.num_pokemon()).map(|idx| side.get_pokemon(idx));
+        Self {
+            hp: party
+                .clone()
+                .map(|pokemon| pokemon.pokemon.hp as f32 / pokemon.pokemon.max_hp.max(1) as f32)
+                .collect(),
+            statuses: party.map(|pokemon| pokemon.status).collect(),
+            hazards: side.spikes_layers as u32
+                + side.has_side_condition(&SideCondition::StealthRock) as u32,
+            boosts: (0..side.num_active_slots())
+                .map(|slot| {
+                    side.get_active_pokemon(slot)
+                        .boosts
+                        .values()
+                        .map(|boost| *boost as i32)
+                        .sum::<i32>()
+                })
+                .sum(),
+        }
+    }
+
+    fn get_delta(&self, next: &SideSnapshot) -> SideDelta {
+        let statuses = self.statuses.iter().zip(next.statuses.iter());
+        SideDelta {
+            hp_lost: self.hp.iter().sum::<f32>() - next.hp.iter().sum::<f32>(),
+            faints: statuses
+                .clone()
+                .filter(|(prev, next)| {
+                    **prev != Some(Status::Faint) && **next == Some(Status::Faint)
+                })
+                .count() as u32,
+            statuses: statuses
+                .filter(|(prev, next)| {
+                    prev != next && next.is_some_and(|status| status != Status::Faint)
+                })
+                .count() as u32,
+            hazards: next.hazards.saturating_sub(self.hazards),
+            boosts: next.boosts - self.boosts,
+        }
+    }
+}
+
+// Reward for trainer 1 for a step from prev to next, with the terminal reward added once
+//  the battle has ended
+pub fn get_step_reward(
+    weights: &RewardWeights,
+    prev: &RewardSnapshot,
+    next: &RewardSnapshot,
+    winner: Option<Winner>,
+) -> f32 {
+    let own = prev.sides[0].get_delta(&next.sides[0]);
+    let opponent = prev.sides[1].get_delta(&next.sides[1]);
+    let terminal = match winner {
+        Some(Winner::Trainer1) => weights.win,
+        Some(Winner::Trainer2) => weights.loss,
+        Some(Winner::Draw) => weights.draw,
+        None => 0.0,
+    };
+
+    terminal
+        + weights.hp_dealt * opponent.hp_lost
+        + weights.hp_taken * own.hp_lost
+        + weights.faints_dealt * opponent.faints as f32
+        + weights.faints_taken * own.faints as f32
+        + weights.status_inflicted * opponent.statuses as f32
+        + weights.status_taken * own.statuses as f32
+        + weights.hazards_set * opponent.hazards as f32
+        + weights.hazards_taken * own.hazards as f32
+        + weights.boosts_gained * own.boosts as f32
+        + weights.opponent_boosts_gained * opponent.boosts as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        battle::{battle::Battle, battle_engine::BattleEngine, test_utils},
+        core::pokemon::{boostable_stat::BoostableStat, stat_enum::StatEnum},
+    };
+
+    const TEAM: &str = "Snorlax\nAbility: Thick Fat\n- Tackle\n\n\
+        Garchomp\nAbility: Sand Force\n- Earthquake";
+
+    fn get_reward(weights: &RewardWeights, update: impl FnOnce(&mut Battle)) -> f32 {
+        let mut battle = test_utils::get_battle(TEAM, TEAM, 0);
+        let prev = RewardSnapshot::new(battle.battle_state());
+        update(&mut battle);
+        get_step_reward(
+            weights,
+            &prev,
+            &RewardSnapshot::new(battle.battle_state()),
+            None,
+        )
+    }
+
+    #[test]
+    fn terminal_rewards_are_given_once_the_battle_ends() {
+        let weights = RewardWeights::builder().draw(0.25).build();
+        let battle = test_utils::get_battle(TEAM, TEAM, 0);
+        let snapshot = RewardSnapshot::new(battle.battle_state());
+        for (winner, reward) in [
+            (Some(Winner::Trainer1), 1.0),
+            (Some(Winner::Trainer2), -1.0),
+            (Some(Winner::Draw), 0.25),
+            (None, 0.0),
+        ] {
+            assert_eq!(
+                get_step_reward(&weights, &snapshot, &snapshot, winner),
+                reward
+            );
+        }
+    }
+
+    #[test]
+    fn hp_is_counted_in_max_hps() {
+        let weights = RewardWeights::builder()
+            .hp_dealt(1.0)
+            .hp_taken(-2.0)
+            .build();
+        let reward = get_reward(&weights, |battle| {
+            for trainer in [true, false] {
+                let pokemon = &mut battle
+                    .battle_context()
+                    .battle_state
+                    .get_active_pokemon_mut(trainer, 0)
+                    .pokemon;
+                pokemon.hp = pokemon.max_hp / 2;
+            }
+        });
+        assert!((reward - (0.5 - 2.0 * 0.5)).abs() < 0.01);
+    }
+
+    #[test]
+    fn statuses_hazards_and_boosts_are_counted_per_side() {
+        let weights = RewardWeights::builder()
+            .status_inflicted(1.0)
+            .hazards_taken(-1.0)
+            .boosts_gained(0.5)
+            .build();
+        let reward = get_reward(&weights, |battle| {
+            let mut battle_context = battle.battle_context();
+            BattleEngine::apply_status(&mut battle_context, false, 0, Status::Burn);
+            BattleEngine::set_side_condition(&mut battle_context, true, SideCondition::StealthRock);
+            battle_context
+                .battle_state
+                .get_active_pokemon_mut(true, 0)
+                .modify_boost(BoostableStat::Stat(StatEnum::Attack), 2);
+        });
+        assert_eq!(reward, 1.0 - 1.0 + 0.5 * 2.0);
+    }
+
+    #[test]
+    fn weights_are_set_by_name() {
+        let mut weights = RewardWeights::default();
+        weights.set_weight("faints_dealt", 0.3).unwrap();
+        assert_eq!(weights.faints_dealt, 0.3);
+        assert!(weights.get_weights().contains(&("faints_dealt", 0.3)));
+        assert!(weights.set_weight("faints", 1.0).is_err());
+    }
+}
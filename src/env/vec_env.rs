@@ -0,0 +1,372 @@
+use std::thread;
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use typed_builder::TypedBuilder;
+
+use crate::{
+    agent::{agent::Agent, runner},
+    battle::{
+        battle::Battle,
+        battle_request::{BattleRequest, SingleBattleRequest, Winner},
+        side_observation::SideObservation,
+    },
+    core::pokemon::pokemon::Pokemon,
+    env::{
+        action_space::{self, ACTION_SPACE},
+        observation_encoder::{self, OBSERVATION_SIZE},
+        reward::{self, RewardSnapshot, RewardWeights},
+    },
+};
+
+// Builds the opponent for an episode from that episode's seed
+pub type OpponentFactory = Box<dyn Fn(u64) -> Box<dyn Agent + Send + Sync> + Send + Sync>;
+
+#[derive(TypedBuilder)]
+pub struct VecEnvConfig {
+    pub num_envs: usize,
+    #[builder(default)]
+    pub seed: u64,
+    #[builder(default = thread::available_parallelism().map_or(1, |n| n.get()))]
+    pub threads: usize,
+    // battles that reach it go to the side with more pokemon left, then more team hp
+    #[builder(default = Some(1000))]
+    pub turn_limit: Option<u32>,
+    #[builder(default)]
+    pub reward_weights: RewardWeights,
+}
+
+// One step of every env, with each env's values one after another
+pub struct StepResult {
+    pub observations: Vec<f32>, // num_envs * OBSERVATION_SIZE
+    pub rewards: Vec<f32>,
+    pub dones: Vec<bool>,
+    pub action_masks: Vec<bool>, // num_envs * ACTION_SPACE
+}
+
+impl StepResult {
+    fn new(num_envs: usize) -> Self {
+        Self {
+            observations: vec![0.0; num_envs * OBSERVATION_SIZE],
+            rewards: vec![0.0; num_envs],
+            dones: vec![false; num_envs],
+            action_masks: vec![false; num_envs * ACTION_SPACE],
+        }
+    }
+}
+
+struct EnvSettings {
+    config: VecEnvConfig,
+    teams: Vec<Vec<Pokemon>>,
+    opponent_factory: OpponentFactory,
+}
+
+// A singles battle played as trainer 1 against a Rust agent
+struct SingleEnv {
+    env_idx: usize,
+    episode: u64,
+    battle: Battle,
+    opponent: Box<dyn Agent + Send + Sync>,
+    request: BattleRequest, // asks trainer 1 for an input, unless the battle ended
+}
+
+impl SingleEnv {
+    fn new(settings: &EnvSettings, env_idx: usize, episode: u64) -> Self {
+        let seed = Self::get_episode_seed(settings.config.seed, env_idx, episode);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let teams = (
+            settings.teams[rng.gen_range(0..settings.teams.len())].clone(),
+            settings.teams[rng.gen_range(0..settings.teams.len())].clone(),
+        );
+
+        let mut battle = Battle::new(teams.0, teams.1);
+        battle.set_seed(seed);
+        battle.set_turn_limit(settings.config.turn_limit);
+        let mut env = Self {
+            env_idx,
+            episode,
+            battle,
+            opponent: (settings.opponent_factory)(seed.wrapping_add(1)),
+            request: BattleRequest::BattleEnded(Winner::Draw),
+        };
+        let request = env.battle.start();
+        env.request = env.play_opponent_inputs(request);
+        env
+    }
+
+    // Seeds are spread out from the env seed, so an episode can be replayed alone
+    fn get_episode_seed(seed: u64, env_idx: usize, episode: u64) -> u64 {
+        StdRng::seed_from_u64(
+            seed ^ (env_idx as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+                ^ episode.wrapping_mul(0xBF58_476D_1CE4_E5B9),
+        )
+        .r#gen()
+    }
+
+    fn get_request(&self) -> &SingleBattleRequest {
+        match &self.request {
+            BattleRequest::Request(side_1_request, _) => side_1_request[0]
+                .as_ref()
+                .expect("Env is waiting without a request for trainer 1"),
+            _ => panic!("Env is waiting without a move or switch request"),
+        }
+    }
+
+    // Answers requests that only the opponent has to act on, e.g. its switch-in after a faint
+    fn play_opponent_inputs(&mut self, mut request: BattleRequest) -> BattleRequest {
+        loop {
+            let BattleRequest::Request(side_1_request, side_2_request) = &request else {
+                return request;
+            };
+            if side_1_request.iter().any(Option::is_some) {
+                return request;
+            }
+            let input = (
+                side_1_request.iter().map(|_| None).collect(),
+                runner::get_side_input(&self.battle, self.opponent.as_mut(), false, side_2_request),
+            );
+            request = self.battle.process_input(input);
+        }
+    }
+
+    // Reward and done for the step, a finished battle is replaced by the next episode
+    fn step(&mut self, settings: &EnvSettings, action: usize) -> (f32, bool) {
+        let prev_snapshot = RewardSnapshot::new(self.battle.battle_state());
+        let BattleRequest::Request(_, side_2_request) = &self.request else {
+            panic!("Env is waiting without a move or switch request");
+        };
+        let input = (
+            vec![Some(action_space::to_input(action, self.get_request()))],
+            runner::get_side_input(&self.battle, self.opponent.as_mut(), false, side_2_request),
+        );
+        let request = self.battle.process_input(input);
+        self.request = self.play_opponent_inputs(request);
+
+        let winner = match self.request {
+            BattleRequest::BattleEnded(winner) => Some(winner),
+            _ => None,
+        };
+        let reward = reward::get_step_reward(
+            &settings.config.reward_weights,
+            &prev_snapshot,
+            &RewardSnapshot::new(self.battle.battle_state()),
+            winner,
+        );
+        if winner.is_none() {
+            return (reward, false);
+        }
+        *self = Self::new(settings, self.env_idx, self.episode + 1);
+        (reward, true)
+    }
+
+    fn write_observation(&self, observation: &mut [f32]) {
+        observation_encoder::encode_observation_into(
+            &SideObservation::new(self.battle.battle_state(), true),
+            observation,
+        );
+    }
+}
+
+// Steps many singles battles at once, split across worker threads. The caller plays
+//  trainer 1 in every battle against an opponent built by the factory, and a battle
+//  that ends is replaced by a new episode straight away, so the observation returned
+//  with done set is already the first one of the next episode
+pub struct VecEnv {
+    settings: EnvSettings,
+    envs: Vec<SingleEnv>,
+}
+
+impl VecEnv {
+    pub fn new(
+        config: VecEnvConfig,
+        teams: Vec<Vec<Pokemon>>,
+        opponent_factory: OpponentFactory,
+    ) -> Self {
+        if teams.is_empty() {
+            panic!("VecEnv needs at least one team");
+        }
+        let settings = EnvSettings {
+            config,
+            teams,
+            opponent_factory,
+        };
+        let envs = (0..settings.config.num_envs)
+            .map(|env_idx| SingleEnv::new(&settings, env_idx, 0))
+            .collect();
+        Self { settings, envs }
+    }
+
+    pub fn num_envs(&self) -> usize {
+        self.envs.len()
+    }
+
+    pub fn reward_weights(&self) -> &RewardWeights {
+        &self.settings.config.reward_weights
+    }
+
+    // Takes effect from the next step, without resetting the envs
+    pub fn set_reward_weights(&mut self, reward_weights: RewardWeights) {
+        self.settings.config.reward_weights = reward_weights;
+    }
+
+    // Starts a new episode in every env, from the first episode again if a seed is given
+    pub fn reset(&mut self, seed: Option<u64>) -> StepResult {
+        if let Some(seed) = seed {
+            self.settings.config.seed = seed;
+        }
+        let actions = vec![0; self.envs.len()];
+        self.run_envs(&actions, |env, settings, _| {
+            let episode = seed.map_or(env.episode + 1, |_| 0);
+            *env = SingleEnv::new(settings, env.env_idx, episode);
+            (0.0, false)
+        })
+    }
+
+    // Takes one action per env, indexed as in action_space
+    pub fn step(&mut self, actions: &[usize]) -> StepResult {
+        if let Err(message) = self.check_actions(actions) {
+            panic!("Illegal input: {message}");
+        }
+        self.run_envs(actions, |env, settings, action| env.step(settings, action))
+    }
+
+    pub fn check_actions(&self, actions: &[usize]) -> Result<(), String> {
+        if actions.len() != self.envs.len() {
+            return Err(format!(
+                "expected {} actions, got {}",
+                self.envs.len(),
+                actions.len()
+            ));
+        }
+        for (env_idx, (env, action)) in self.envs.iter().zip(actions).enumerate() {
+            if *action >= ACTION_SPACE || !action_space::get_action_mask(env.get_request())[*action]
+            {
+                return Err(format!("action {action} is not available in env {env_idx}"));
+            }
+        }
+        Ok(())
+    }
+
+    fn run_envs(
+        &mut self,
+        actions: &[usize],
+        env_op: impl Fn(&mut SingleEnv, &EnvSettings, usize) -> (f32, bool) + Sync,
+    ) -> StepResult {
+        let mut result = StepResult::new(self.envs.len());
+        let chunk_size = self
+            .envs
+            .len()
+            .div_ceil(self.settings.config.threads.max(1))
+            .max(1);
+        let settings = &self.settings;
+        let env_op = &env_op;
+
+        let outputs = result
+            .observations
+            .chunks_mut(chunk_size * OBSERVATION_SIZE)
+            .zip(result.action_masks.chunks_mut(chunk_size * ACTION_SPACE))
+            .zip(
+                result
+                    .rewards
+                    .chunks_mut(chunk_size)
+                    .zip(result.dones.chunks_mut(chunk_size)),
+            );
+        thread::scope(|scope| {
+            for ((envs, actions), ((observations, action_masks), (rewards, dones))) in self
+                .envs
+                .chunks_mut(chunk_size)
+                .zip(actions.chunks(chunk_size))
+                .zip(outputs)
+            {
+                scope.spawn(move || {
+                    for (idx, (env, action)) in envs.iter_mut().zip(actions).enumerate() {
+                        (rewards[idx], dones[idx]) = env_op(env, settings, *action);
+                        env.write_observation(
+                            &mut observations[idx * OBSERVATION_SIZE..(idx + 1) * OBSERVATION_SIZE],
+                        );
+                        action_masks[idx * ACTION_SPACE..(idx + 1) * ACTION_SPACE]
+                            .copy_from_slice(&action_space::get_action_mask(env.get_request()));
+                    }
+                });
+            }
+        });
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{agent::random_agent::RandomAgent, showdown::text};
+
+    fn get_env(num_envs: usize, turn_limit: Option<u32>) -> VecEnv {
+        let team = text::parse_team(
+            "Garchomp\nAbility: Sand Force\n- Earthquake\n- Tackle\n\n\
+            Snorlax\nAbility: Thick Fat\n- Tackle",
+        )
+        .unwrap();
+        let config = VecEnvConfig::builder()
+            .num_envs(num_envs)
+            .seed(3)
+            .threads(2)
+            .turn_limit(turn_limit)
+            .build();
+        VecEnv::new(
+            config,
+            vec![team],
+            Box::new(|seed| Box::new(RandomAgent::new(seed))),
+        )
+    }
+
+    fn get_first_actions(result: &StepResult) -> Vec<usize> {
+        result
+            .action_masks
+            .chunks(ACTION_SPACE)
+            .map(|mask| mask.iter().position(|valid| *valid).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn steps_fill_every_env() {
+        let mut env = get_env(3, Some(1000));
+        let result = env.reset(None);
+        assert_eq!(result.observations.len(), 3 * OBSERVATION_SIZE);
+        assert_eq!(result.action_masks.len(), 3 * ACTION_SPACE);
+
+        let result = env.step(&get_first_actions(&result));
+        assert_eq!(result.rewards.len(), 3);
+        assert_eq!(result.dones.len(), 3);
+    }
+
+    #[test]
+    fn reset_with_a_seed_replays_the_same_episodes() {
+        let mut env = get_env(2, Some(1000));
+        let first = env.reset(Some(9));
+        let first = env.step(&get_first_actions(&first));
+        let second = env.reset(Some(9));
+        let second = env.step(&get_first_actions(&second));
+        assert_eq!(first.observations, second.observations);
+        assert_eq!(first.rewards, second.rewards);
+    }
+
+    #[test]
+    fn battles_at_the_turn_limit_end_with_a_terminal_reward() {
+        let mut env = get_env(2, Some(1));
+        let result = env.reset(None);
+        let result = env.step(&get_first_actions(&result));
+        assert!(result.dones.iter().all(|done| *done));
+        assert!(
+            result
+                .rewards
+                .iter()
+                .all(|reward| [1.0, -1.0, 0.0].contains(reward))
+        );
+    }
+
+    #[test]
+    fn unavailable_actions_are_rejected() {
+        let env = get_env(2, Some(1000));
+        assert!(env.check_actions(&[0]).is_err());
+        assert!(env.check_actions(&[0, ACTION_SPACE]).is_err());
+        assert!(env.check_actions(&[0, 0]).is_ok());
+    }
+}
@@ -1,4 +1,12 @@
+use crate::core::{
+    side_condition::side_condition::SideCondition, status::volatile_status::VolatileStatus,
+};
+
+// Pokemon targets are given as a trainer side followed by an active slot
 pub enum EventHandlerEffect {
-    Damage(u32, bool),              // damage target
-    DamageAndHeal(u32, bool, bool), // amount, damage target, heal target
+    Damage(u32, bool, usize),                          // damage, target
+    DamageAndHeal(u32, bool, usize, bool, usize),      // amount, damage target, heal target
+    RemoveVolatileStatus(VolatileStatus, bool, usize), // volatile status, target
+    RemoveItem(bool, usize),                           // target
+    RemoveSideCondition(SideCondition, bool),          // side condition, target side
 }
@@ -20,8 +20,8 @@ macro_rules! on_event {
 #[macro_export]
 macro_rules! impl_event_handler {
     ($name:ident, $( $event:expr ),* ) => {
-        impl crate::event::event_handler::EventHandler for $name {
-            fn handle(&self, event: &crate::event::event_type::Event, state: &crate::battle::state::BattleState) -> Vec<EventHandlerEffect> {
+        impl $crate::event::event_handler::EventHandler for $name {
+            fn handle(&self, event: &$crate::event::event_type::Event, state: &$crate::battle::state::BattleState) -> Vec<EventHandlerEffect> {
                 match event {
                     $(
                         {
@@ -34,8 +34,8 @@ macro_rules! impl_event_handler {
             }
         }
 
-        impl Subscriber<crate::event::event_type::Event> for $name {
-            fn subscriptions(&self) -> &'static [crate::event::event_type::EventKind] {
+        impl Subscriber<$crate::event::event_type::Event> for $name {
+            fn subscriptions(&self) -> &'static [$crate::event::event_type::EventKind] {
                 &[
                     $(
                         {
@@ -46,7 +46,7 @@ macro_rules! impl_event_handler {
                 ]
             }
 
-            fn priority(&self, kind: &crate::event::event_type::EventKind) -> i32 {
+            fn priority(&self, kind: &$crate::event::event_type::EventKind) -> i32 {
                 match kind {
                     $(
                         {
@@ -64,22 +64,22 @@ macro_rules! impl_event_handler {
 #[macro_export]
 macro_rules! impl_empty_event_handler {
     ($name:ident) => {
-        impl crate::event::event_handler::EventHandler for $name {
+        impl $crate::event::event_handler::EventHandler for $name {
             fn handle(
                 &self,
-                _: &crate::event::event_type::Event,
-                _: &mut crate::battle::state::BattleState,
-            ) -> Vec<crate::event::event_handler_effect::EventHandlerEffect> {
+                _: &$crate::event::event_type::Event,
+                _: &mut $crate::battle::state::BattleState,
+            ) -> Vec<$crate::event::event_handler_effect::EventHandlerEffect> {
                 panic!("Event not handled")
             }
         }
 
-        impl crate::common::subscriber::Subscriber<crate::event::event_type::Event> for $name {
-            fn subscriptions(&self) -> &'static [crate::event::event_type::EventKind] {
+        impl $crate::common::subscriber::Subscriber<$crate::event::event_type::Event> for $name {
+            fn subscriptions(&self) -> &'static [$crate::event::event_type::EventKind] {
                 &[]
             }
 
-            fn priority(&self, _: &crate::event::event_type::EventKind) -> i32 {
+            fn priority(&self, _: &$crate::event::event_type::EventKind) -> i32 {
                 panic!("Event priority for unhandled event")
             }
         }
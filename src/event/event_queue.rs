@@ -2,6 +2,7 @@ use std::collections::VecDeque;
 
 use crate::event::event_type::Event;
 
+#[derive(Clone, Default)]
 pub struct EventQueue {
     events: VecDeque<Event>,
 }
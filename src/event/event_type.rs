@@ -10,14 +10,21 @@ pub enum EventKind {
     BeginTurn,
     Faint,
     OnTurnEnd,
+    OnSideTurnEnd,
+    BeforeMoveHit,
+    AfterMoveHit,
 }
 
+#[derive(Clone)]
 pub enum Event {
     Damage(DamageEvent),
     Switch(SwitchEvent),
     Faint(FaintEvent),
     BeginTurn,
-    OnTurnEnd(bool),
+    OnTurnEnd(bool, usize), // trainer and slot of each active pokemon, in speed order
+    OnSideTurnEnd(bool),    // after every pokemon's end of turn effects
+    BeforeMoveHit(MoveContext),
+    AfterMoveHit(MoveHitEvent),
 }
 
 impl HasKind for Event {
@@ -29,7 +36,10 @@ impl HasKind for Event {
             Event::Switch(_) => EventKind::Switch,
             Event::BeginTurn => EventKind::BeginTurn,
             Event::Faint(_) => EventKind::Faint,
-            Event::OnTurnEnd(_) => EventKind::OnTurnEnd,
+            Event::OnTurnEnd(_, _) => EventKind::OnTurnEnd,
+            Event::OnSideTurnEnd(_) => EventKind::OnSideTurnEnd,
+            Event::BeforeMoveHit(_) => EventKind::BeforeMoveHit,
+            Event::AfterMoveHit(_) => EventKind::AfterMoveHit,
         }
     }
 }
@@ -38,20 +48,33 @@ impl Event {}
 
 // Event Payloads with data outside of BattleState
 
+#[derive(Clone)]
 pub struct DamageEvent {
     pub target_trainer_1_side: bool,
+    pub target_slot: usize,
     pub damage: u32,
     pub caused_faint: bool,
 }
 
+#[derive(Clone)]
 pub struct SwitchEvent {
+    pub trainer_side: bool,
+    pub slot: usize,
     pub orig_pokemon_idx: usize,
     pub new_pokemon_idx: usize,
 }
 
+#[derive(Clone)]
 pub struct FaintEvent {
     pub move_context: Option<MoveContext>,
     pub trainer_side: bool,
+    pub slot: usize,
+}
+
+#[derive(Clone)]
+pub struct MoveHitEvent {
+    pub move_context: MoveContext,
+    pub damage_dealt: u32,
 }
 
 pub struct OnPriorityEvent {
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Clause {
+    Sleep,   // only one opposing pokemon can be put to sleep at a time
+    Species, // no two pokemon of the same species on a team
+    Evasion, // no moves that raise the user's evasion
+    Ohko,    // no one-hit KO moves
+}
@@ -0,0 +1,106 @@
+use crate::{
+    battle::state::BattleState,
+    common::{has_kind::HasKind, subscriber::Subscriber},
+    core::status::status::Status,
+    query::{
+        query::{Query, QueryKind},
+        query_handler::QueryHandler,
+    },
+};
+
+// Blocks putting a pokemon to sleep while the opponent already put another one
+//  on that side to sleep. Sleep from the pokemon's own move, e.g. Rest, doesn't count
+pub struct SleepClauseHandler;
+
+impl Subscriber<Query> for SleepClauseHandler {
+    fn subscriptions(&self) -> &'static [<Query as HasKind>::Kind] {
+        &[QueryKind::CanApplyStatus]
+    }
+
+    fn priority(&self, kind: &<Query as HasKind>::Kind) -> i32 {
+        match kind {
+            // runs after any handler that allows the status
+            QueryKind::CanApplyStatus => -10,
+            _ => panic!("Query priority in sleep clause handler for unhandled query"),
+        }
+    }
+}
+
+impl QueryHandler for SleepClauseHandler {
+    fn handle(&self, query: &mut Query, battle_state: &mut BattleState) {
+        match query {
+            Query::CanApplyStatus(payload) => {
+                let context = payload.move_context;
+                if payload.status != Status::Sleep
+                    || (payload.target, payload.target_slot)
+                        == (context.src_trainer, context.src_slot)
+                {
+                    return;
+                }
+
+                let target_side = battle_state.get_side(payload.target);
+                let target_idx = target_side.get_active_pokemon_idx(payload.target_slot);
+                let other_asleep = (0..target_side.num_pokemon())
+                    .filter(|idx| *idx != target_idx)
+                    .map(|idx| target_side.get_pokemon(idx))
+                    .any(|pokemon| {
+                        pokemon.status == Some(Status::Sleep) && !pokemon.self_inflicted_sleep
+                    });
+                if other_asleep {
+                    payload.can_apply = false;
+                }
+            }
+            _ => panic!("unhandled query for sleep clause handler"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{
+        battle::{
+            actions::{Action, MoveModifier, MoveSlot, SwitchSlot},
+            battle::Battle,
+            test_utils,
+        },
+        core::status::status::Status,
+        format::{clause::Clause, format::Format},
+        showdown::text,
+    };
+
+    const SPORE: &str = "Amoonguss\nAbility: Overgrow\n- Spore";
+    const SNORLAXES: &str = "Snorlax\nAbility: Thick Fat\n- Tackle\n\n\
+        Snorlax\nAbility: Thick Fat\n- Tackle";
+
+    // Spores the first Snorlax, then the second one after it switches in
+    fn get_second_status(clauses: HashSet<Clause>) -> Option<Status> {
+        let format = Format::builder().clauses(clauses).build();
+        let (mut battle, _) = Battle::init_with_format(
+            text::parse_team(SPORE).unwrap(),
+            text::parse_team(SNORLAXES).unwrap(),
+            &format,
+        )
+        .expect("Teams should be legal");
+        battle.set_seed(0);
+
+        let spore = Action::Move(MoveSlot::Slot0, MoveModifier::None);
+        let input = test_utils::get_input(&battle, spore.clone(), spore.clone());
+        battle.process_input(input);
+        assert_eq!(
+            battle.battle_state().get_active_pokemon(false, 0).status,
+            Some(Status::Sleep)
+        );
+
+        let input = test_utils::get_input(&battle, spore, Action::Switch(SwitchSlot::Slot1));
+        battle.process_input(input);
+        battle.battle_state().get_active_pokemon(false, 0).status
+    }
+
+    #[test]
+    fn sleep_clause_blocks_a_second_sleep() {
+        assert_eq!(get_second_status(HashSet::from([Clause::Sleep])), None);
+        assert_eq!(get_second_status(HashSet::new()), Some(Status::Sleep));
+    }
+}
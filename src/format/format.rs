@@ -0,0 +1,274 @@
+use std::collections::HashSet;
+
+use typed_builder::TypedBuilder;
+
+use crate::{
+    battle::game_type::GameType,
+    core::{
+        ability::ability::Ability, item::item::Item, mechanics::generation::Generation,
+        pokemon::pokemon::Pokemon, pokemon::species::Species, pokemove::move_name::MoveName,
+    },
+    dex::{
+        pokemon::learnset_dex,
+        pokemove::{move_dex, move_revisions},
+    },
+    format::{clause::Clause, format_violation::FormatViolation},
+};
+
+#[derive(Clone, TypedBuilder)]
+pub struct Format {
+    #[builder(default = GameType::Singles)]
+    pub game_type: GameType,
+    #[builder(default = Generation::Gen9)]
+    pub generation: Generation, // battle mechanics and learnset legality
+    #[builder(default = 1)]
+    pub min_team_size: usize,
+    #[builder(default = 6)]
+    pub max_team_size: usize,
+    #[builder(default)]
+    pub team_preview: Option<usize>, // number of pokemon brought to the battle, if there is a team preview
+    #[builder(default = 100)]
+    pub level_cap: u8,
+    #[builder(default)]
+    pub banned_species: HashSet<Species>,
+    #[builder(default)]
+    pub banned_moves: HashSet<MoveName>,
+    #[builder(default)]
+    pub banned_abilities: HashSet<Ability>,
+    #[builder(default)]
+    pub banned_items: HashSet<Item>,
+    #[builder(default)]
+    pub clauses: HashSet<Clause>,
+    #[builder(default)]
+    pub turn_limit: Option<u32>,
+}
+
+impl Format {
+    // Smogon style singles with the usual clauses
+    pub fn standard_singles() -> Self {
+        Format::builder()
+            .team_preview(Some(6))
+            .clauses(HashSet::from([
+                Clause::Sleep,
+                Clause::Species,
+                Clause::Evasion,
+                Clause::Ohko,
+            ]))
+            .turn_limit(Some(1000))
+            .build()
+    }
+
+    // VGC style doubles, bring 6 and pick 4
+    pub fn standard_doubles() -> Self {
+        Format::builder()
+            .game_type(GameType::Doubles)
+            .min_team_size(4)
+            .team_preview(Some(4))
+            .level_cap(50)
+            .clauses(HashSet::from([Clause::Species]))
+            .turn_limit(Some(1000))
+            .build()
+    }
+
+    pub fn has_clause(&self, clause: Clause) -> bool {
+        self.clauses.contains(&clause)
+    }
+
+    // Returns every rule the team breaks, empty if the team is legal
+    pub fn validate_team(&self, team: &[Pokemon]) -> Vec<FormatViolation> {
+        let mut violations = vec![];
+
+        let min_size = self
+            .min_team_size
+            .max(self.team_preview.unwrap_or(0))
+            .max(self.game_type.active_slots());
+        if team.len() < min_size {
+            violations.push(FormatViolation::TeamTooSmall {
+                size: team.len(),
+                min: min_size,
+            });
+        }
+        if team.len() > self.max_team_size {
+            violations.push(FormatViolation::TeamTooLarge {
+                size: team.len(),
+                max: self.max_team_size,
+            });
+        }
+
+        let mut seen_species = HashSet::new();
+        for (idx, pokemon) in team.iter().enumerate() {
+            if pokemon.level > self.level_cap {
+                violations.push(FormatViolation::LevelAboveCap {
+                    idx,
+                    level: pokemon.level,
+                    cap: self.level_cap,
+                });
+            }
+
+            if self.banned_species.contains(&pokemon.species) {
+                violations.push(FormatViolation::BannedSpecies {
+                    idx,
+                    species: pokemon.species,
+                });
+            }
+
+            if self.has_clause(Clause::Species) && !seen_species.insert(pokemon.species) {
+                violations.push(FormatViolation::DuplicateSpecies {
+                    idx,
+                    species: pokemon.species,
+                });
+            }
+
+            if self.banned_abilities.contains(&pokemon.ability) {
+                violations.push(FormatViolation::BannedAbility {
+                    idx,
+                    ability: pokemon.ability,
+                });
+            }
+
+            if let Some(item) = pokemon.item.filter(|item| self.banned_items.contains(item)) {
+                violations.push(FormatViolation::BannedItem { idx, item });
+            }
+
+            for move_name in pokemon.moves.iter().filter(|m| **m != MoveName::Empty) {
+                violations.extend(self.validate_move(idx, pokemon.species, *move_name));
+            }
+        }
+
+        violations
+    }
+
+    fn validate_move(
+        &self,
+        idx: usize,
+        species: Species,
+        move_name: MoveName,
+    ) -> Vec<FormatViolation> {
+        let mut violations = vec![];
+        let pokemove = move_dex::get_move_data(&move_name);
+
+        if move_revisions::introduced_in(&move_name) > self.generation {
+            violations.push(FormatViolation::MoveNotInGeneration { idx, move_name });
+        }
+        match learnset_dex::can_learn(&species, &move_name, self.generation.number()) {
+            Some(true) => {}
+            Some(false) => violations.push(FormatViolation::UnlearnableMove {
+                idx,
+                species,
+                move_name,
+            }),
+            None => violations.push(FormatViolation::UnknownMoveLegality {
+                idx,
+                species,
+                move_name,
+            }),
+        }
+        if self.banned_moves.contains(&move_name) {
+            violations.push(FormatViolation::BannedMove { idx, move_name });
+        }
+        if self.has_clause(Clause::Evasion) && pokemove.raises_user_evasion() {
+            violations.push(FormatViolation::EvasionMove { idx, move_name });
+        }
+        if self.has_clause(Clause::Ohko) && pokemove.is_ohko() {
+            violations.push(FormatViolation::OhkoMove { idx, move_name });
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::showdown::text;
+
+    const TEAM: &str = "Venusaur\nAbility: Overgrow\n- Tackle\n\n\
+        Charizard\nAbility: Blaze\n- Ember\n\n\
+        Blastoise\nAbility: Torrent\n- Tackle\n\n\
+        Pikachu\nAbility: Technician\n- Growl\n\n\
+        Snorlax\nAbility: Thick Fat\n- Tackle\n\n\
+        Garchomp\nAbility: Sand Force\n- Earthquake";
+
+    fn get_violations(format: &Format, team: &str) -> Vec<FormatViolation> {
+        format.validate_team(&text::parse_team(team).unwrap())
+    }
+
+    fn with_clause(clause: Clause) -> Format {
+        Format::builder().clauses(HashSet::from([clause])).build()
+    }
+
+    #[test]
+    fn standard_singles_allows_a_legal_team() {
+        assert_eq!(get_violations(&Format::standard_singles(), TEAM), vec![]);
+    }
+
+    #[test]
+    fn species_clause_rejects_duplicate_species() {
+        let team = "Snorlax\nAbility: Thick Fat\n- Tackle\n\n\
+            Snorlax\nAbility: Thick Fat\n- Rest";
+        assert_eq!(
+            get_violations(&with_clause(Clause::Species), team),
+            vec![FormatViolation::DuplicateSpecies {
+                idx: 1,
+                species: Species::Snorlax
+            }]
+        );
+        assert_eq!(get_violations(&Format::builder().build(), team), vec![]);
+    }
+
+    #[test]
+    fn evasion_and_ohko_clauses_reject_their_moves() {
+        let evasion_team = "Pikachu\nAbility: Technician\n- Double Team";
+        assert_eq!(
+            get_violations(&with_clause(Clause::Evasion), evasion_team),
+            vec![FormatViolation::EvasionMove {
+                idx: 0,
+                move_name: MoveName::DoubleTeam
+            }]
+        );
+        assert_eq!(
+            get_violations(&with_clause(Clause::Ohko), evasion_team),
+            vec![]
+        );
+
+        let ohko_team = "Snorlax\nAbility: Thick Fat\n- Fissure";
+        let ohko_violation = FormatViolation::OhkoMove {
+            idx: 0,
+            move_name: MoveName::Fissure,
+        };
+        assert!(get_violations(&with_clause(Clause::Ohko), ohko_team).contains(&ohko_violation));
+        assert!(
+            !get_violations(&with_clause(Clause::Evasion), ohko_team).contains(&ohko_violation)
+        );
+    }
+
+    #[test]
+    fn level_cap_rejects_higher_levels() {
+        let format = Format::builder().level_cap(50).build();
+        assert_eq!(
+            get_violations(&format, "Snorlax\nLevel: 51\nAbility: Thick Fat\n- Tackle"),
+            vec![FormatViolation::LevelAboveCap {
+                idx: 0,
+                level: 51,
+                cap: 50
+            }]
+        );
+        assert_eq!(
+            get_violations(&format, "Snorlax\nLevel: 50\nAbility: Thick Fat\n- Tackle"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn moves_newer_than_the_generation_are_rejected() {
+        let team = "Gengar\nAbility: Technician\n- Hex";
+        let violation = FormatViolation::MoveNotInGeneration {
+            idx: 0,
+            move_name: MoveName::Hex,
+        };
+        let gen_4 = Format::builder().generation(Generation::Gen4).build();
+        assert!(get_violations(&gen_4, team).contains(&violation));
+        let gen_5 = Format::builder().generation(Generation::Gen5).build();
+        assert!(!get_violations(&gen_5, team).contains(&violation));
+    }
+}
@@ -0,0 +1,66 @@
+use crate::core::{
+    ability::ability::Ability, item::item::Item, pokemon::species::Species,
+    pokemove::move_name::MoveName,
+};
+
+// Reasons a team is not allowed in a format, pokemon are referred to by party index
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FormatViolation {
+    TeamTooSmall {
+        size: usize,
+        min: usize,
+    },
+    TeamTooLarge {
+        size: usize,
+        max: usize,
+    },
+    LevelAboveCap {
+        idx: usize,
+        level: u8,
+        cap: u8,
+    },
+    BannedSpecies {
+        idx: usize,
+        species: Species,
+    },
+    BannedMove {
+        idx: usize,
+        move_name: MoveName,
+    },
+    // the move does not exist yet in the format's generation
+    MoveNotInGeneration {
+        idx: usize,
+        move_name: MoveName,
+    },
+    UnlearnableMove {
+        idx: usize,
+        species: Species,
+        move_name: MoveName,
+    },
+    // there is no learnset data for the format's generation to check the move against
+    UnknownMoveLegality {
+        idx: usize,
+        species: Species,
+        move_name: MoveName,
+    },
+    BannedAbility {
+        idx: usize,
+        ability: Ability,
+    },
+    BannedItem {
+        idx: usize,
+        item: Item,
+    },
+    DuplicateSpecies {
+        idx: usize,
+        species: Species,
+    },
+    EvasionMove {
+        idx: usize,
+        move_name: MoveName,
+    },
+    OhkoMove {
+        idx: usize,
+        move_name: MoveName,
+    },
+}
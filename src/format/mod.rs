@@ -0,0 +1,5 @@
+pub mod clause;
+pub mod clause_handlers;
+pub mod format;
+pub mod format_violation;
+pub mod team_validator;
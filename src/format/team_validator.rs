@@ -0,0 +1,144 @@
+use crate::{
+    core::{
+        pokemon::{pokemon::Pokemon, pokemon_validation_error::PokemonValidationError},
+        pokemove::move_name::MoveName,
+    },
+    format::{format::Format, format_violation::FormatViolation},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TeamValidationError {
+    Pokemon {
+        idx: usize, // party index
+        error: PokemonValidationError,
+    },
+    Format(FormatViolation),
+}
+
+// Collects every problem with the team, empty if it can be used in the format.
+//  Pokemon fields are public, so the checks from Pokemon::new are repeated here
+pub fn validate_team(format: &Format, team: &[Pokemon]) -> Vec<TeamValidationError> {
+    let mut errors: Vec<TeamValidationError> = team
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, pokemon)| {
+            validate_pokemon(pokemon)
+                .into_iter()
+                .map(move |error| TeamValidationError::Pokemon { idx, error })
+        })
+        .collect();
+
+    errors.extend(
+        format
+            .validate_team(team)
+            .into_iter()
+            .map(TeamValidationError::Format),
+    );
+
+    errors
+}
+
+pub fn validate_pokemon(pokemon: &Pokemon) -> Vec<PokemonValidationError> {
+    let moves: Vec<MoveName> = pokemon
+        .moves
+        .iter()
+        .copied()
+        .filter(|move_name| *move_name != MoveName::Empty)
+        .collect();
+
+    Pokemon::validate(pokemon.level, &moves, &pokemon.ivs, &pokemon.evs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use strum::IntoEnumIterator;
+
+    use super::*;
+    use crate::{
+        core::{
+            ability::ability::Ability,
+            pokemon::{nature::Nature, species::Species, stat_enum::StatEnum},
+        },
+        dex::pokemon::pokedex,
+        showdown::text,
+    };
+
+    fn get_team() -> Vec<Pokemon> {
+        text::parse_team(
+            "Snorlax\nAbility: Thick Fat\n- Tackle\n\nSnorlax\nAbility: Thick Fat\n- Tackle",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn pokemon_validation_collects_every_error() {
+        let ivs: HashMap<StatEnum, u8> = StatEnum::iter()
+            .filter(|stat| *stat != StatEnum::Speed)
+            .map(|stat| (stat, 32))
+            .collect();
+        let evs: HashMap<StatEnum, u8> = StatEnum::iter().map(|stat| (stat, 255)).collect();
+        let errors = Pokemon::validate(
+            0,
+            &[MoveName::Tackle, MoveName::Tackle, MoveName::Empty],
+            &ivs,
+            &evs,
+        );
+
+        for error in [
+            PokemonValidationError::InvalidLevel(0),
+            PokemonValidationError::DuplicateMove(MoveName::Tackle),
+            PokemonValidationError::EmptyMoveSlot,
+            PokemonValidationError::MissingIv(StatEnum::Speed),
+            PokemonValidationError::IvAboveMax(StatEnum::HP, 32),
+            PokemonValidationError::EvAboveMax(StatEnum::Attack, 255),
+            PokemonValidationError::EvTotalAboveMax(255 * 6),
+        ] {
+            assert!(errors.contains(&error), "missing {error:?}");
+        }
+    }
+
+    #[test]
+    fn invalid_pokemon_is_an_error_not_a_panic() {
+        let ivs: HashMap<StatEnum, u8> = StatEnum::iter().map(|stat| (stat, 31)).collect();
+        let evs: HashMap<StatEnum, u8> = StatEnum::iter().map(|stat| (stat, 0)).collect();
+        let result = Pokemon::new(
+            pokedex::get_base_pokemon(&Species::Snorlax).clone(),
+            50,
+            vec![],
+            Ability::ThickFat,
+            None,
+            ivs,
+            evs,
+            Nature::Hardy,
+        );
+        assert_eq!(result.err(), Some(vec![PokemonValidationError::NoMoves]));
+    }
+
+    #[test]
+    fn team_validation_reports_party_indices() {
+        let mut team = get_team();
+        team[1].level = 0;
+
+        let errors = validate_team(&Format::standard_singles(), &team);
+        for error in [
+            TeamValidationError::Pokemon {
+                idx: 1,
+                error: PokemonValidationError::InvalidLevel(0),
+            },
+            TeamValidationError::Format(FormatViolation::TeamTooSmall { size: 2, min: 6 }),
+            TeamValidationError::Format(FormatViolation::DuplicateSpecies {
+                idx: 1,
+                species: Species::Snorlax,
+            }),
+        ] {
+            assert!(errors.contains(&error), "missing {error:?}");
+        }
+        assert!(
+            !errors
+                .iter()
+                .any(|error| matches!(error, TeamValidationError::Pokemon { idx: 0, .. }))
+        );
+    }
+}
@@ -1,15 +1,42 @@
+// modules are named after the type they define, e.g. battle::battle::Battle
+#![allow(clippy::module_inception)]
+
+use std::collections::HashMap;
+
+use numpy::{PyArray1, PyArray2, PyArrayMethods, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
-mod battle;
-mod common;
-mod core;
-mod dex;
-mod event;
-mod query;
+pub mod agent;
+pub mod battle;
+pub mod common;
+pub mod core;
+pub mod dex;
+pub mod env;
+pub mod event;
+pub mod format;
+pub mod query;
+pub mod showdown;
+pub mod tournament;
 
-use battle::battle::Battle;
+use crate::core::{field::weather::Weather, mechanics::generation::Generation};
+use agent::{max_damage_agent::MaxDamageAgent, random_agent::RandomAgent};
+use battle::{
+    battle::Battle,
+    damage_calculator::{self, CalcField},
+    side_observation::SideObservation,
+    state::BattleState,
+};
+use env::{
+    action_space::ACTION_SPACE,
+    observation_encoder::{self, ENCODER_VERSION, OBSERVATION_SIZE},
+    reward::RewardWeights,
+    vec_env::{OpponentFactory, StepResult, VecEnv, VecEnvConfig},
+};
+use showdown::{names, packed, showdown_set, text};
 
+/// One battle between two packed Showdown teams, observed from either side
 #[pyclass]
 struct Env {
     sim: Battle,
@@ -18,19 +45,309 @@ struct Env {
 #[pymethods]
 impl Env {
     #[new]
-    fn new() -> Self {
-        Self { sim: Battle::new() }
+    #[pyo3(signature = (team_1, team_2, seed = 0))]
+    fn new(team_1: &str, team_2: &str, seed: u64) -> PyResult<Self> {
+        Ok(Self {
+            sim: start_battle(team_1, team_2, seed).map_err(PyValueError::new_err)?,
+        })
+    }
+
+    /// One side's encoded view of the battle
+    fn observe(&self, py: Python<'_>, trainer_1: bool) -> PyResult<Py<PyAny>> {
+        serialize_obs(py, self.sim.battle_state(), trainer_1)
     }
 }
 
+// Parses both packed teams and starts the battle, so there's always one to observe
+fn start_battle(team_1: &str, team_2: &str, seed: u64) -> Result<Battle, String> {
+    let parse_team = |trainer: u8, team: &str| match packed::parse_packed_team(team) {
+        Ok(team) if team.is_empty() => Err(format!("Team {trainer} is empty")),
+        Ok(team) => Ok(team),
+        Err(errors) => Err(format!("Invalid team {trainer}: {errors:?}")),
+    };
+    let mut battle = Battle::new(parse_team(1, team_1)?, parse_team(2, team_2)?);
+    battle.set_seed(seed);
+    battle.start();
+    Ok(battle)
+}
+
+type StepArrays<'py> = (
+    Bound<'py, PyArray2<f32>>,
+    Bound<'py, PyArray1<f32>>,
+    Bound<'py, PyArray1<bool>>,
+    Bound<'py, PyArray2<bool>>,
+);
+
+type ResetArrays<'py> = (Bound<'py, PyArray2<f32>>, Bound<'py, PyArray2<bool>>);
+
+/// Steps many battles per call, see env::vec_env::VecEnv
+///
+/// Teams are packed Showdown teams, the opponent is "random" or "max-damage".
+/// Reward weights are a dict of component name to weight, see env::reward::RewardWeights,
+/// where components left out keep their defaults
+#[pyclass(name = "VecEnv")]
+struct PyVecEnv {
+    env: VecEnv,
+}
+
+#[pymethods]
+impl PyVecEnv {
+    #[new]
+    #[pyo3(signature = (teams, num_envs, opponent = "random", seed = 0, threads = None, turn_limit = Some(1000), reward_weights = None))]
+    fn new(
+        teams: Vec<String>,
+        num_envs: usize,
+        opponent: &str,
+        seed: u64,
+        threads: Option<usize>,
+        turn_limit: Option<u32>,
+        reward_weights: Option<HashMap<String, f32>>,
+    ) -> PyResult<Self> {
+        let teams = teams
+            .iter()
+            .enumerate()
+            .map(|(team_idx, team)| {
+                packed::parse_packed_team(team).map_err(|errors| {
+                    PyValueError::new_err(format!("Invalid team {team_idx}: {errors:?}"))
+                })
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        if teams.is_empty() {
+            return Err(PyValueError::new_err("At least one team is needed"));
+        }
+        let opponent_factory: OpponentFactory = match opponent {
+            "random" => Box::new(|seed| Box::new(RandomAgent::new(seed))),
+            "max-damage" => Box::new(|_| Box::new(MaxDamageAgent)),
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown opponent: {opponent}"
+                )));
+            }
+        };
+
+        let mut weights = RewardWeights::default();
+        update_reward_weights(&mut weights, reward_weights.unwrap_or_default())?;
+
+        let config = VecEnvConfig::builder()
+            .num_envs(num_envs)
+            .seed(seed)
+            .turn_limit(turn_limit)
+            .reward_weights(weights);
+        let config = match threads {
+            Some(threads) => config.threads(threads).build(),
+            None => config.build(),
+        };
+        Ok(Self {
+            env: VecEnv::new(config, teams, opponent_factory),
+        })
+    }
+
+    #[getter]
+    fn num_envs(&self) -> usize {
+        self.env.num_envs()
+    }
+
+    #[getter]
+    fn observation_size(&self) -> usize {
+        OBSERVATION_SIZE
+    }
+
+    #[getter]
+    fn action_space(&self) -> usize {
+        ACTION_SPACE
+    }
+
+    #[getter]
+    fn reward_weights(&self) -> HashMap<&'static str, f32> {
+        self.env
+            .reward_weights()
+            .get_weights()
+            .into_iter()
+            .collect()
+    }
+
+    /// Changes the given reward components from the next step on, keeping the rest
+    fn set_reward_weights(&mut self, reward_weights: HashMap<String, f32>) -> PyResult<()> {
+        let mut weights = self.env.reward_weights().clone();
+        update_reward_weights(&mut weights, reward_weights)?;
+        self.env.set_reward_weights(weights);
+        Ok(())
+    }
+
+    /// Returns (observations, action_masks) for the first step of new episodes
+    #[pyo3(signature = (seed = None))]
+    fn reset<'py>(&mut self, py: Python<'py>, seed: Option<u64>) -> PyResult<ResetArrays<'py>> {
+        let result = py.detach(|| self.env.reset(seed));
+        let (observations, _, _, action_masks) = to_step_arrays(py, result)?;
+        Ok((observations, action_masks))
+    }
+
+    /// Returns (observations, rewards, dones, action_masks), finished battles are reset
+    fn step<'py>(
+        &mut self,
+        py: Python<'py>,
+        actions: PyReadonlyArray1<'py, i64>,
+    ) -> PyResult<StepArrays<'py>> {
+        let actions = actions
+            .as_array()
+            .iter()
+            .map(|action| {
+                usize::try_from(*action)
+                    .map_err(|_| PyValueError::new_err(format!("Invalid action {action}")))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        self.env
+            .check_actions(&actions)
+            .map_err(PyValueError::new_err)?;
+        let result = py.detach(|| self.env.step(&actions));
+        to_step_arrays(py, result)
+    }
+}
+
+fn update_reward_weights(
+    weights: &mut RewardWeights,
+    updates: HashMap<String, f32>,
+) -> PyResult<()> {
+    for (name, weight) in updates {
+        weights
+            .set_weight(&name, weight)
+            .map_err(PyValueError::new_err)?;
+    }
+    Ok(())
+}
+
+fn to_step_arrays(py: Python<'_>, result: StepResult) -> PyResult<StepArrays<'_>> {
+    let num_envs = result.rewards.len();
+    Ok((
+        PyArray1::from_vec(py, result.observations).reshape([num_envs, OBSERVATION_SIZE])?,
+        PyArray1::from_vec(py, result.rewards),
+        PyArray1::from_vec(py, result.dones),
+        PyArray1::from_vec(py, result.action_masks).reshape([num_envs, ACTION_SPACE])?,
+    ))
+}
+
 /// Helper: convert observation Rust struct → Python dict
-fn serialize_obs(py: Python<'_>) -> PyObject {
+fn serialize_obs(
+    py: Python<'_>,
+    battle_state: &BattleState,
+    trainer_1: bool,
+) -> PyResult<Py<PyAny>> {
     let dict = PyDict::new(py);
-    dict.into()
+    dict.set_item("version", ENCODER_VERSION)?;
+    dict.set_item(
+        "observation",
+        PyArray1::from_vec(
+            py,
+            observation_encoder::encode_observation(&SideObservation::new(battle_state, trainer_1)),
+        ),
+    )?;
+    Ok(dict.into_any().unbind())
+}
+
+/// Every feature group of an encoded observation as (name, offset, size)
+#[pyfunction]
+fn observation_layout() -> Vec<(String, usize, usize)> {
+    observation_encoder::get_feature_layout()
+}
+
+/// Damage rolls and KO chances of the attacker's move against the defender, both given
+/// as Showdown sets. None for status moves
+#[pyfunction]
+#[pyo3(signature = (attacker, defender, move_name, weather = None, generation = None, max_uses = 4))]
+fn calculate_damage(
+    py: Python<'_>,
+    attacker: &str,
+    defender: &str,
+    move_name: &str,
+    weather: Option<&str>,
+    generation: Option<u8>,
+    max_uses: usize,
+) -> PyResult<Option<Py<PyAny>>> {
+    let parse_set = |set: &str| {
+        text::parse_team(set)
+            .map_err(|errors| PyValueError::new_err(format!("Invalid set: {errors:?}")))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| PyValueError::new_err("Empty set"))
+    };
+    let move_name = showdown_set::parse_move(move_name)
+        .map_err(|error| PyValueError::new_err(format!("Invalid move: {error:?}")))?;
+    let weather = weather
+        .map(|name| {
+            names::find_by_name::<Weather>(name)
+                .ok_or_else(|| PyValueError::new_err(format!("Unknown weather: {name}")))
+        })
+        .transpose()?;
+    let generation = generation
+        .map(|number| {
+            Generation::from_number(number)
+                .ok_or_else(|| PyValueError::new_err(format!("Unknown generation: {number}")))
+        })
+        .transpose()?;
+
+    let field = CalcField::builder()
+        .weather(weather)
+        .generation(generation)
+        .max_uses(max_uses)
+        .build();
+    let Some(calc) = damage_calculator::calculate(
+        &parse_set(attacker)?,
+        &parse_set(defender)?,
+        move_name,
+        &field,
+    ) else {
+        return Ok(None);
+    };
+
+    let dict = PyDict::new(py);
+    dict.set_item("rolls", calc.damage_rolls.rolls.to_vec())?;
+    dict.set_item("crit_rolls", calc.damage_rolls.crit_rolls.to_vec())?;
+    dict.set_item("crit_chance", calc.damage_rolls.crit_chance)?;
+    dict.set_item("hit_chances", calc.damage_rolls.hit_chances)?;
+    dict.set_item("defender_hp", calc.defender_hp)?;
+    dict.set_item("defender_max_hp", calc.defender_max_hp)?;
+    dict.set_item("percent_range", calc.percent_range)?;
+    dict.set_item("crit_percent_range", calc.crit_percent_range)?;
+    dict.set_item("recoil_range", calc.recoil_range)?;
+    dict.set_item("drain_range", calc.drain_range)?;
+    dict.set_item("attacker_residuals", calc.attacker_residuals)?;
+    dict.set_item("defender_residuals", calc.defender_residuals)?;
+    dict.set_item("ko_chances", calc.ko_chances)?;
+    Ok(Some(dict.into_any().unbind()))
 }
 
 #[pymodule]
-fn pokemon_env(_py: Python, m: &PyModule) -> PyResult<()> {
+fn pokemon_env(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Env>()?;
+    m.add_class::<PyVecEnv>()?;
+    m.add_function(wrap_pyfunction!(observation_layout, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_damage, m)?)?;
+    m.add("OBSERVATION_VERSION", ENCODER_VERSION)?;
+    m.add("OBSERVATION_SIZE", OBSERVATION_SIZE)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEAM: &str = "Lax|snorlax||thickfat|return,earthquake|Adamant|||||50|]";
+
+    #[test]
+    fn env_battles_start_between_their_teams() {
+        let battle = start_battle(TEAM, TEAM, 0).expect("teams should parse");
+        for trainer in [true, false] {
+            let active = battle.battle_state().get_active_pokemon(trainer, 0);
+            assert!(active.pokemon.hp > 0);
+        }
+    }
+
+    #[test]
+    fn env_battles_need_two_valid_teams() {
+        assert_eq!(
+            start_battle(TEAM, "", 0).err(),
+            Some("Team 2 is empty".to_string())
+        );
+        assert!(start_battle("Lax|notapokemon|||tackle|||||||]", TEAM, 0).is_err());
+    }
+}
@@ -1,11 +1,4 @@
-use crate::{
-    common::context::MoveContext,
-    core::{
-        pokemon::pokemon::Pokemon,
-        pokemove::{move_name::MoveName, pokemove::PokeMove},
-        util::damage_utils,
-    },
-};
+use crate::{common::context::MoveContext, core::util::damage_utils};
 
 pub enum Payload {
     U8(u8),
@@ -345,6 +338,11 @@ impl PayloadMoveQuery {
         damage_utils::rounded_damage_from_modifiers(self.payload.as_vec_f32())
     }
 
+    // Product of multipliers that scale a value rather than produce one
+    pub fn as_modifier_product(&self) -> f32 {
+        self.payload.as_vec_f32().iter().product()
+    }
+
     pub fn as_combined_modifier_default(&self, default: u32) -> u32 {
         damage_utils::rounded_damage_from_modifiers_with_default(
             self.payload.as_vec_f32(),
@@ -30,6 +30,8 @@ pub enum QueryKind {
     MultiHitRange,
     MultiHitHits,
     OnSecondaryEffectChance,
+    OnFixedDamage,
+    RedirectTarget,
 }
 
 pub enum Query {
@@ -54,6 +56,8 @@ pub enum Query {
     MultiHitRange(MultiHitRangeQuery),
     MultiHitHits(MultiHitHitsQuery),
     OnSecondaryEffectChance(PayloadMoveQuery),
+    OnFixedDamage(PayloadMoveQuery),
+    RedirectTarget(RedirectTargetQuery),
 }
 
 impl HasKind for Query {
@@ -82,6 +86,8 @@ impl HasKind for Query {
             Query::MultiHitRange(_) => QueryKind::MultiHitRange,
             Query::MultiHitHits(_) => QueryKind::MultiHitHits,
             Query::OnSecondaryEffectChance(_) => QueryKind::OnSecondaryEffectChance,
+            Query::OnFixedDamage(_) => QueryKind::OnFixedDamage,
+            Query::RedirectTarget(_) => QueryKind::RedirectTarget,
         }
     }
 }
@@ -103,6 +109,7 @@ impl Query {
             Query::CheckImmunity(e) => e,
             Query::GetMoveHitChance(e) => e,
             Query::OnSecondaryEffectChance(e) => e,
+            Query::OnFixedDamage(e) => e,
             _ => panic!("Query is not a PayloadMoveQuery"),
         }
     }
@@ -149,6 +156,13 @@ impl Query {
         }
     }
 
+    pub fn into_redirect_target_query(self) -> RedirectTargetQuery {
+        match self {
+            Query::RedirectTarget(e) => e,
+            _ => panic!("Query is not a RedirectTargetQuery"),
+        }
+    }
+
     pub fn into_final_damage_query(self) -> FinalDamageQuery {
         match self {
             Query::FinalDamage(e) => e,
@@ -181,6 +195,7 @@ impl TryUseMoveQuery {
 
 pub struct OnStatQuery {
     pub trainer: bool,
+    pub slot: usize,
     pub stat: StatEnum,
     pub mults: Vec<f32>,
 }
@@ -188,17 +203,26 @@ pub struct OnStatQuery {
 pub struct CanApplyStatusQuery {
     pub move_context: MoveContext,
     pub target: bool,
+    pub target_slot: usize,
     pub status: Status,
     pub can_apply: bool,
+    pub replaces_status: bool, // set by moves like Rest that overwrite the current status
 }
 
 impl CanApplyStatusQuery {
-    pub fn new(move_context: MoveContext, target: bool, status: Status) -> Self {
+    pub fn new(
+        move_context: MoveContext,
+        target: bool,
+        target_slot: usize,
+        status: Status,
+    ) -> Self {
         Self {
             move_context,
             target,
+            target_slot,
             status,
             can_apply: true,
+            replaces_status: false,
         }
     }
 }
@@ -206,15 +230,22 @@ impl CanApplyStatusQuery {
 pub struct CanApplyVolatileStatusQuery {
     pub move_context: MoveContext,
     pub target: bool,
+    pub target_slot: usize,
     pub volatile_status: VolatileStatus,
     pub can_apply: bool,
 }
 
 impl CanApplyVolatileStatusQuery {
-    pub fn new(move_context: MoveContext, target: bool, volatile_status: VolatileStatus) -> Self {
+    pub fn new(
+        move_context: MoveContext,
+        target: bool,
+        target_slot: usize,
+        volatile_status: VolatileStatus,
+    ) -> Self {
         Self {
             move_context,
             target,
+            target_slot,
             volatile_status,
             can_apply: true,
         }
@@ -237,7 +268,7 @@ pub struct MultiHitHitsQuery {
 pub struct FinalDamageQuery {
     pub damage: u32,
     pub move_context: Option<MoveContext>,
-    pub target_trainer: Option<bool>,
+    pub target: Option<(bool, usize)>, // trainer and slot, for damage not from a move
 }
 
 impl FinalDamageQuery {
@@ -245,15 +276,30 @@ impl FinalDamageQuery {
         Self {
             damage,
             move_context: Some(move_context),
-            target_trainer: None,
+            target: None,
         }
     }
 
-    pub fn from_non_move(damage: u32, target_trainer: bool) -> Self {
+    pub fn from_non_move(damage: u32, target_trainer: bool, target_slot: usize) -> Self {
         Self {
             damage,
             move_context: None,
-            target_trainer: Some(target_trainer),
+            target: Some((target_trainer, target_slot)),
+        }
+    }
+}
+
+// Lets effects like Follow Me pull a single target move to a different slot
+pub struct RedirectTargetQuery {
+    pub move_context: MoveContext,
+    pub target_slot: usize,
+}
+
+impl RedirectTargetQuery {
+    pub fn new(move_context: MoveContext) -> Self {
+        Self {
+            move_context,
+            target_slot: move_context.target_slot,
         }
     }
 }
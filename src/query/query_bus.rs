@@ -1,15 +1,10 @@
 use crate::{
     battle::state::BattleState,
     common::{has_kind::HasKind, registry::Registry},
-    event::{
-        event_handler::EventHandler,
-        event_handler_effect::EventHandlerEffect,
-        event_queue::EventQueue,
-        event_type::{Event, EventKind},
-    },
     query::{query::Query, query_handler::QueryHandler},
 };
 
+#[derive(Clone, Default)]
 pub struct QueryBus {
     pub registry: Registry<Query, dyn QueryHandler>,
 }
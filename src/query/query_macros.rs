@@ -2,8 +2,8 @@
 macro_rules! on_query {
     ($variant:ident { $($pat:tt)* } @ $prio:expr => $handler:expr) => {
         (
-            crate::query::query::Query::$variant { $($pat)* },
-            crate::query::query::QueryKind::$variant,
+            $crate::query::query::Query::$variant { $($pat)* },
+            $crate::query::query::QueryKind::$variant,
             $prio,
             $handler
         )
@@ -15,8 +15,8 @@ macro_rules! on_query {
 
     ($variant:ident ( $($pat:tt)* ) @ $prio:expr => $handler:expr) => {
         (
-            crate::query::query::Query::$variant,
-            crate::query::query::QueryKind::$variant,
+            $crate::query::query::Query::$variant,
+            $crate::query::query::QueryKind::$variant,
             $prio,
             $handler
         )
@@ -30,8 +30,8 @@ macro_rules! on_query {
 #[macro_export]
 macro_rules! impl_query_handler {
     ($name:ident, $( $query:expr ),* ) => {
-        impl crate::query::query_handler::QueryHandler for $name {
-            fn handle(&self, query: &crate::query::query::Query, state: &crate::battle::state::BattleState) {
+        impl $crate::query::query_handler::QueryHandler for $name {
+            fn handle(&self, query: &$crate::query::query::Query, state: &$crate::battle::state::BattleState) {
                 // match query {
                 //     $(
                 //         $(query[0]) => $query[3](query, state),
@@ -58,8 +58,8 @@ macro_rules! impl_query_handler {
             }
         }
 
-        impl crate::common::subscriber::Subscriber<crate::query::query::Query> for $name {
-            fn subscriptions(&self) -> &'static [crate::query::query::QueryKind] {
+        impl $crate::common::subscriber::Subscriber<$crate::query::query::Query> for $name {
+            fn subscriptions(&self) -> &'static [$crate::query::query::QueryKind] {
                 &[
                     $(
                         {
@@ -70,7 +70,7 @@ macro_rules! impl_query_handler {
                 ]
             }
 
-            fn priority(&self, kind: &crate::query::query::QueryKind) -> i32 {
+            fn priority(&self, kind: &$crate::query::query::QueryKind) -> i32 {
                 $(
                     let (_, k, prio, _) = $query;
 
@@ -98,22 +98,22 @@ macro_rules! impl_query_handler {
 #[macro_export]
 macro_rules! impl_empty_query_handler {
     ($name:ident) => {
-        impl crate::query::query_handler::QueryHandler for $name {
+        impl $crate::query::query_handler::QueryHandler for $name {
             fn handle(
                 &self,
-                _: &mut crate::query::query::Query,
-                _: &mut crate::battle::state::BattleState,
+                _: &mut $crate::query::query::Query,
+                _: &mut $crate::battle::state::BattleState,
             ) {
                 panic!("Query not handled")
             }
         }
 
-        impl crate::common::subscriber::Subscriber<crate::query::query::Query> for $name {
-            fn subscriptions(&self) -> &'static [crate::query::query::QueryKind] {
+        impl $crate::common::subscriber::Subscriber<$crate::query::query::Query> for $name {
+            fn subscriptions(&self) -> &'static [$crate::query::query::QueryKind] {
                 &[]
             }
 
-            fn priority(&self, _: &crate::query::query::QueryKind) -> i32 {
+            fn priority(&self, _: &$crate::query::query::QueryKind) -> i32 {
                 panic!("Query priority for unhandled query")
             }
         }
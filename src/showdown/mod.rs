@@ -0,0 +1,6 @@
+pub mod names;
+pub mod packed;
+pub mod parse_error;
+pub mod showdown_set;
+pub mod text;
+pub mod usage_stats;
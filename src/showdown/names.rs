@@ -0,0 +1,37 @@
+use std::fmt::Debug;
+
+use strum::IntoEnumIterator;
+
+// Showdown ids are lowercase with everything but letters and digits removed,
+//  e.g. "Swords Dance" -> "swordsdance"
+pub fn to_id(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+// Spaces out an enum variant's name, e.g. SwordsDance -> "Swords Dance"
+pub fn display_name<T: Debug>(value: &T) -> String {
+    let debug_name = format!("{value:?}");
+    let mut name = String::with_capacity(debug_name.len() + 4);
+    let mut prev: Option<char> = None;
+    for c in debug_name.chars() {
+        if c.is_ascii_uppercase() && prev.is_some_and(|p| p.is_ascii_lowercase()) {
+            name.push(' ');
+        }
+        name.push(c);
+        prev = Some(c);
+    }
+    name
+}
+
+pub fn id<T: Debug>(value: &T) -> String {
+    to_id(&format!("{value:?}"))
+}
+
+// Finds the variant whose name matches, ignoring case, spaces and punctuation
+pub fn find_by_name<T: Debug + IntoEnumIterator>(name: &str) -> Option<T> {
+    let target = to_id(name);
+    T::iter().find(|value| id(value) == target)
+}
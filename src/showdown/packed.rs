@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    core::pokemon::{pokemon::Pokemon, pokemon_validation_error::MAX_IV, stat_enum::StatEnum},
+    showdown::{
+        names,
+        parse_error::{ShowdownParseError, ShowdownParseErrorKind},
+        showdown_set::{self, DEFAULT_NATURE, ShowdownSet},
+    },
+};
+
+// Fields of a packed set, separated by '|', sets are separated by ']'
+//  NICKNAME|SPECIES|ITEM|ABILITY|MOVES|NATURE|EVS|GENDER|IVS|SHINY|LEVEL|HAPPINESS,POKEBALL,
+//  HIDDENPOWERTYPE,GIGANTAMAX,DYNAMAXLEVEL,TERATYPE
+const NICKNAME: usize = 0;
+const SPECIES: usize = 1;
+const ITEM: usize = 2;
+const ABILITY: usize = 3;
+const MOVES: usize = 4;
+const NATURE: usize = 5;
+const EVS: usize = 6;
+const IVS: usize = 8;
+const LEVEL: usize = 10;
+const MIN_FIELDS: usize = 11;
+const MISC: usize = 11;
+const MISC_TERA_TYPE: usize = 5;
+
+pub fn parse_packed_team(packed: &str) -> Result<Vec<Pokemon>, Vec<ShowdownParseError>> {
+    let mut team = vec![];
+    let mut errors = vec![];
+
+    for (pokemon_idx, packed_set) in packed
+        .trim()
+        .split(']')
+        .filter(|packed_set| !packed_set.is_empty())
+        .enumerate()
+    {
+        match parse_packed_set(packed_set).and_then(ShowdownSet::into_pokemon) {
+            Ok(pokemon) => team.push(pokemon),
+            Err(kinds) => errors.extend(
+                kinds
+                    .into_iter()
+                    .map(|kind| ShowdownParseError { pokemon_idx, kind }),
+            ),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(team)
+    } else {
+        Err(errors)
+    }
+}
+
+fn parse_packed_set(packed_set: &str) -> Result<ShowdownSet, Vec<ShowdownParseErrorKind>> {
+    let fields: Vec<&str> = packed_set.split('|').collect();
+    if fields.len() < MIN_FIELDS {
+        return Err(vec![ShowdownParseErrorKind::MalformedLine(
+            packed_set.to_string(),
+        )]);
+    }
+
+    let mut errors = vec![];
+    let mut record = |result: Result<(), ShowdownParseErrorKind>| {
+        if let Err(err) = result {
+            errors.push(err);
+        }
+    };
+
+    // the species is left blank when it's the same as the nickname
+    let species_name = if fields[SPECIES].is_empty() {
+        fields[NICKNAME]
+    } else {
+        fields[SPECIES]
+    };
+    let mut species = None;
+    record(showdown_set::parse_species(species_name).map(|parsed| species = Some(parsed)));
+
+    let mut item = None;
+    if !fields[ITEM].is_empty() {
+        record(showdown_set::parse_item(fields[ITEM]).map(|parsed| item = Some(parsed)));
+    }
+
+    let mut ability = None;
+    if fields[ABILITY].is_empty() {
+        record(Err(ShowdownParseErrorKind::MissingAbility));
+    } else {
+        record(showdown_set::parse_ability(fields[ABILITY]).map(|parsed| ability = Some(parsed)));
+    }
+
+    let mut moves = vec![];
+    for move_name in fields[MOVES].split(',').filter(|m| !m.is_empty()) {
+        record(showdown_set::parse_move(move_name).map(|parsed| moves.push(parsed)));
+    }
+
+    let mut nature = DEFAULT_NATURE;
+    if !fields[NATURE].is_empty() {
+        record(showdown_set::parse_nature(fields[NATURE]).map(|parsed| nature = parsed));
+    }
+
+    let mut evs = showdown_set::default_evs();
+    record(parse_packed_spread(fields[EVS], &mut evs));
+    let mut ivs = showdown_set::default_ivs();
+    record(parse_packed_spread(fields[IVS], &mut ivs));
+
+    let mut level = 100;
+    if !fields[LEVEL].is_empty() {
+        record(showdown_set::parse_stat_value(fields[LEVEL]).map(|parsed| level = parsed));
+    }
+
+    let mut tera_type = None;
+    if let Some(tera_name) = fields
+        .get(MISC)
+        .and_then(|misc| misc.split(',').nth(MISC_TERA_TYPE))
+        .filter(|tera_name| !tera_name.is_empty())
+    {
+        record(showdown_set::parse_type(tera_name).map(|parsed| tera_type = Some(parsed)));
+    }
+
+    match (species, ability) {
+        (Some(species), Some(ability)) if errors.is_empty() => Ok(ShowdownSet {
+            species,
+            item,
+            ability,
+            level,
+            moves,
+            evs,
+            ivs,
+            nature,
+            tera_type,
+        }),
+        _ => Err(errors),
+    }
+}
+
+// Comma separated values in stat order, blank entries keep their default
+fn parse_packed_spread(
+    value: &str,
+    spread: &mut HashMap<StatEnum, u8>,
+) -> Result<(), ShowdownParseErrorKind> {
+    if value.is_empty() {
+        return Ok(());
+    }
+
+    let values: Vec<&str> = value.split(',').collect();
+    if values.len() != StatEnum::iter().count() {
+        return Err(ShowdownParseErrorKind::MalformedLine(value.to_string()));
+    }
+    for (stat, value) in StatEnum::iter().zip(values) {
+        if !value.is_empty() {
+            spread.insert(stat, showdown_set::parse_stat_value(value)?);
+        }
+    }
+    Ok(())
+}
+
+pub fn export_packed_team(team: &[Pokemon]) -> String {
+    team.iter()
+        .map(export_packed_pokemon)
+        .collect::<Vec<String>>()
+        .join("]")
+}
+
+pub fn export_packed_pokemon(pokemon: &Pokemon) -> String {
+    let set = ShowdownSet::from_pokemon(pokemon);
+    let mut fields = vec![String::new(); MIN_FIELDS];
+
+    fields[NICKNAME] = names::display_name(&set.species);
+    fields[ITEM] = set.item.map(|item| names::id(&item)).unwrap_or_default();
+    fields[ABILITY] = names::id(&set.ability);
+    fields[MOVES] = set
+        .moves
+        .iter()
+        .map(names::id)
+        .collect::<Vec<String>>()
+        .join(",");
+    fields[NATURE] = format!("{:?}", set.nature);
+    fields[EVS] = export_packed_spread(&set.evs, 0);
+    fields[IVS] = export_packed_spread(&set.ivs, MAX_IV);
+    if set.level != 100 {
+        fields[LEVEL] = set.level.to_string();
+    }
+    if let Some(tera_type) = set.tera_type {
+        fields.push(format!(",,,,,{tera_type:?}"));
+    }
+
+    fields.join("|")
+}
+
+fn export_packed_spread(spread: &HashMap<StatEnum, u8>, default: u8) -> String {
+    if StatEnum::iter().all(|stat| spread[&stat] == default) {
+        return String::new();
+    }
+
+    StatEnum::iter()
+        .map(|stat| {
+            if spread[&stat] == default {
+                String::new()
+            } else {
+                spread[&stat].to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::{pokemon::species::Species, poketype::poketype::PokeType},
+        showdown::text,
+    };
+
+    const PACKED: &str = "Lax|snorlax|choiceband|thickfat|return,earthquake|Adamant|252,252,,,4,||,,,,,0||50|,,,,,ghost]|starmie||technician|recover|Timid||||||";
+
+    #[test]
+    fn export_round_trips() {
+        let team = parse_packed_team(PACKED).unwrap();
+        let exported = export_packed_team(&team);
+        let reparsed = parse_packed_team(&exported).unwrap();
+        assert_eq!(text::export_team(&reparsed), text::export_team(&team));
+    }
+
+    #[test]
+    fn matches_the_text_format() {
+        let team = parse_packed_team(PACKED).unwrap();
+        assert_eq!(team[0].species, Species::Snorlax);
+        assert_eq!(team[0].level, 50);
+        assert_eq!(team[0].ivs[&StatEnum::Speed], 0);
+        assert_eq!(team[0].tera_type, PokeType::Ghost);
+
+        let from_text = text::parse_team(&text::export_team(&team)).unwrap();
+        assert_eq!(export_packed_team(&from_text), export_packed_team(&team));
+    }
+
+    #[test]
+    fn short_sets_are_malformed() {
+        let errors = parse_packed_team("snorlax||thickfat|return")
+            .err()
+            .expect("Team should fail to parse");
+        assert!(matches!(
+            errors[0].kind,
+            ShowdownParseErrorKind::MalformedLine(_)
+        ));
+    }
+}
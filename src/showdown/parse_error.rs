@@ -0,0 +1,22 @@
+use crate::core::pokemon::pokemon_validation_error::PokemonValidationError;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ShowdownParseErrorKind {
+    UnknownSpecies(String),
+    UnknownItem(String),
+    UnknownAbility(String),
+    UnknownMove(String),
+    UnknownNature(String),
+    UnknownStat(String),
+    UnknownType(String),
+    InvalidNumber(String),
+    MissingAbility,
+    MalformedLine(String),
+    Invalid(PokemonValidationError), // parsed, but the pokemon can't be built
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShowdownParseError {
+    pub pokemon_idx: usize, // position of the set in the team
+    pub kind: ShowdownParseErrorKind,
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    core::{
+        ability::ability::Ability,
+        item::item::Item,
+        pokemon::{
+            nature::Nature, pokemon::Pokemon, pokemon_validation_error::MAX_IV, species::Species,
+            stat_enum::StatEnum,
+        },
+        pokemove::move_name::MoveName,
+        poketype::poketype::PokeType,
+    },
+    dex::pokemon::pokedex,
+    showdown::{names, parse_error::ShowdownParseErrorKind},
+};
+
+// A set as written in a Showdown team, before stats are calculated
+pub struct ShowdownSet {
+    pub species: Species,
+    pub item: Option<Item>,
+    pub ability: Ability,
+    pub level: u8,
+    pub moves: Vec<MoveName>,
+    pub evs: HashMap<StatEnum, u8>,
+    pub ivs: HashMap<StatEnum, u8>,
+    pub nature: Nature,
+    pub tera_type: Option<PokeType>, // None keeps the default, the primary type
+}
+
+impl ShowdownSet {
+    pub fn into_pokemon(self) -> Result<Pokemon, Vec<ShowdownParseErrorKind>> {
+        let tera_type = self.tera_type;
+        Pokemon::new(
+            pokedex::get_base_pokemon(&self.species).clone(),
+            self.level,
+            self.moves,
+            self.ability,
+            self.item,
+            self.ivs,
+            self.evs,
+            self.nature,
+        )
+        .map(|mut pokemon| {
+            if let Some(tera_type) = tera_type {
+                pokemon.tera_type = tera_type;
+            }
+            pokemon
+        })
+        .map_err(|errors| {
+            errors
+                .into_iter()
+                .map(ShowdownParseErrorKind::Invalid)
+                .collect()
+        })
+    }
+
+    pub fn from_pokemon(pokemon: &Pokemon) -> Self {
+        Self {
+            species: pokemon.species,
+            item: pokemon.item,
+            ability: pokemon.ability,
+            level: pokemon.level,
+            moves: pokemon
+                .moves
+                .iter()
+                .copied()
+                .filter(|move_name| *move_name != MoveName::Empty)
+                .collect(),
+            evs: pokemon.evs.clone(),
+            ivs: pokemon.ivs.clone(),
+            nature: pokemon.nature,
+            tera_type: Some(pokemon.tera_type)
+                .filter(|tera_type| *tera_type != pokemon.typing.primary_type()),
+        }
+    }
+}
+
+// Showdown leaves out EVs as 0 and IVs as 31
+pub fn default_evs() -> HashMap<StatEnum, u8> {
+    StatEnum::iter().map(|stat| (stat, 0)).collect()
+}
+
+pub fn default_ivs() -> HashMap<StatEnum, u8> {
+    StatEnum::iter().map(|stat| (stat, MAX_IV)).collect()
+}
+
+// A neutral nature, used when none is given
+pub const DEFAULT_NATURE: Nature = Nature::Serious;
+
+pub fn stat_abbreviation(stat: StatEnum) -> &'static str {
+    match stat {
+        StatEnum::HP => "HP",
+        StatEnum::Attack => "Atk",
+        StatEnum::Defense => "Def",
+        StatEnum::SpecialAttack => "SpA",
+        StatEnum::SpecialDefense => "SpD",
+        StatEnum::Speed => "Spe",
+    }
+}
+
+pub fn parse_species(name: &str) -> Result<Species, ShowdownParseErrorKind> {
+    names::find_by_name(name).ok_or_else(|| ShowdownParseErrorKind::UnknownSpecies(name.into()))
+}
+
+pub fn parse_item(name: &str) -> Result<Item, ShowdownParseErrorKind> {
+    names::find_by_name(name).ok_or_else(|| ShowdownParseErrorKind::UnknownItem(name.into()))
+}
+
+pub fn parse_ability(name: &str) -> Result<Ability, ShowdownParseErrorKind> {
+    names::find_by_name(name).ok_or_else(|| ShowdownParseErrorKind::UnknownAbility(name.into()))
+}
+
+pub fn parse_nature(name: &str) -> Result<Nature, ShowdownParseErrorKind> {
+    names::find_by_name(name).ok_or_else(|| ShowdownParseErrorKind::UnknownNature(name.into()))
+}
+
+pub fn parse_move(name: &str) -> Result<MoveName, ShowdownParseErrorKind> {
+    names::find_by_name(name)
+        .filter(|move_name| *move_name != MoveName::Empty)
+        .ok_or_else(|| ShowdownParseErrorKind::UnknownMove(name.into()))
+}
+
+pub fn parse_type(name: &str) -> Result<PokeType, ShowdownParseErrorKind> {
+    names::find_by_name(name)
+        .filter(|poke_type| *poke_type != PokeType::Typeless)
+        .ok_or_else(|| ShowdownParseErrorKind::UnknownType(name.into()))
+}
+
+pub fn parse_stat_value(value: &str) -> Result<u8, ShowdownParseErrorKind> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| ShowdownParseErrorKind::InvalidNumber(value.into()))
+}
@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    core::pokemon::{pokemon::Pokemon, pokemon_validation_error::MAX_IV, stat_enum::StatEnum},
+    showdown::{
+        names,
+        parse_error::{ShowdownParseError, ShowdownParseErrorKind},
+        showdown_set::{self, DEFAULT_NATURE, ShowdownSet},
+    },
+};
+
+// Lines that only affect how the pokemon looks, or mechanics that aren't simulated
+const IGNORED_KEYS: [&str; 7] = [
+    "Shiny",
+    "Happiness",
+    "Gigantamax",
+    "Dynamax Level",
+    "Pokeball",
+    "Hidden Power",
+    "Gender",
+];
+
+// Parses a team in Showdown's export format, with sets separated by blank lines
+pub fn parse_team(text: &str) -> Result<Vec<Pokemon>, Vec<ShowdownParseError>> {
+    let mut team = vec![];
+    let mut errors = vec![];
+
+    for (pokemon_idx, set_text) in split_sets(text).into_iter().enumerate() {
+        match parse_set(&set_text).and_then(ShowdownSet::into_pokemon) {
+            Ok(pokemon) => team.push(pokemon),
+            Err(kinds) => errors.extend(
+                kinds
+                    .into_iter()
+                    .map(|kind| ShowdownParseError { pokemon_idx, kind }),
+            ),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(team)
+    } else {
+        Err(errors)
+    }
+}
+
+fn split_sets(text: &str) -> Vec<Vec<&str>> {
+    let mut sets: Vec<Vec<&str>> = vec![];
+    let mut current: Vec<&str> = vec![];
+    for line in text.lines().map(str::trim) {
+        // team headers from the teambuilder, e.g. "=== [gen9ou] Team ==="
+        if line.is_empty() || line.starts_with("===") {
+            if !current.is_empty() {
+                sets.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        sets.push(current);
+    }
+    sets
+}
+
+fn parse_set(lines: &[&str]) -> Result<ShowdownSet, Vec<ShowdownParseErrorKind>> {
+    let mut errors = vec![];
+
+    // "Nickname (Species) (M) @ Item"
+    let (name_part, item_part) = match lines[0].split_once(" @ ") {
+        Some((name, item)) => (name.trim(), Some(item.trim())),
+        None => (lines[0], None),
+    };
+    let species = showdown_set::parse_species(strip_nickname_and_gender(name_part))
+        .map_err(|err| errors.push(err))
+        .ok();
+    let item = item_part.and_then(|item| {
+        showdown_set::parse_item(item)
+            .map_err(|err| errors.push(err))
+            .ok()
+    });
+
+    let mut ability = None;
+    let mut level = 100;
+    let mut nature = DEFAULT_NATURE;
+    let mut evs = showdown_set::default_evs();
+    let mut ivs = showdown_set::default_ivs();
+    let mut moves = vec![];
+    let mut tera_type = None;
+
+    for line in &lines[1..] {
+        let result = if let Some(move_name) = line.strip_prefix('-').or(line.strip_prefix('~')) {
+            showdown_set::parse_move(move_name.trim()).map(|move_name| moves.push(move_name))
+        } else if let Some(nature_name) = line.strip_suffix(" Nature") {
+            showdown_set::parse_nature(nature_name).map(|parsed| nature = parsed)
+        } else if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim();
+            match key.trim() {
+                "Ability" => {
+                    showdown_set::parse_ability(value).map(|parsed| ability = Some(parsed))
+                }
+                "Level" => showdown_set::parse_stat_value(value).map(|parsed| level = parsed),
+                "EVs" => parse_spread(value, &mut evs),
+                "IVs" => parse_spread(value, &mut ivs),
+                "Tera Type" => {
+                    showdown_set::parse_type(value).map(|parsed| tera_type = Some(parsed))
+                }
+                key if IGNORED_KEYS.contains(&key) => Ok(()),
+                _ => Err(ShowdownParseErrorKind::MalformedLine(line.to_string())),
+            }
+        } else {
+            Err(ShowdownParseErrorKind::MalformedLine(line.to_string()))
+        };
+
+        if let Err(err) = result {
+            errors.push(err);
+        }
+    }
+
+    if ability.is_none() {
+        errors.push(ShowdownParseErrorKind::MissingAbility);
+    }
+
+    match (species, ability) {
+        (Some(species), Some(ability)) if errors.is_empty() => Ok(ShowdownSet {
+            species,
+            item,
+            ability,
+            level,
+            moves,
+            evs,
+            ivs,
+            nature,
+            tera_type,
+        }),
+        _ => Err(errors),
+    }
+}
+
+fn strip_nickname_and_gender(name: &str) -> &str {
+    let name = name
+        .strip_suffix(" (M)")
+        .or(name.strip_suffix(" (F)"))
+        .unwrap_or(name);
+
+    // "Nickname (Species)"
+    match (name.rfind(" ("), name.ends_with(')')) {
+        (Some(open), true) => &name[open + 2..name.len() - 1],
+        _ => name,
+    }
+}
+
+// "252 Atk / 4 SpD / 252 Spe", stats that aren't listed keep their default
+fn parse_spread(
+    value: &str,
+    spread: &mut HashMap<StatEnum, u8>,
+) -> Result<(), ShowdownParseErrorKind> {
+    for entry in value.split('/').map(str::trim) {
+        let (amount, stat_name) = entry
+            .split_once(' ')
+            .ok_or_else(|| ShowdownParseErrorKind::MalformedLine(entry.to_string()))?;
+        let stat = StatEnum::iter()
+            .find(|stat| {
+                showdown_set::stat_abbreviation(*stat).eq_ignore_ascii_case(stat_name.trim())
+            })
+            .ok_or_else(|| ShowdownParseErrorKind::UnknownStat(stat_name.to_string()))?;
+        spread.insert(stat, showdown_set::parse_stat_value(amount)?);
+    }
+    Ok(())
+}
+
+pub fn export_team(team: &[Pokemon]) -> String {
+    team.iter()
+        .map(export_pokemon)
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn export_pokemon(pokemon: &Pokemon) -> String {
+    let set = ShowdownSet::from_pokemon(pokemon);
+    let mut lines = vec![];
+
+    lines.push(match set.item {
+        Some(item) => format!(
+            "{} @ {}",
+            names::display_name(&set.species),
+            names::display_name(&item)
+        ),
+        None => names::display_name(&set.species),
+    });
+    lines.push(format!("Ability: {}", names::display_name(&set.ability)));
+    if set.level != 100 {
+        lines.push(format!("Level: {}", set.level));
+    }
+    if let Some(evs) = export_spread(&set.evs, 0) {
+        lines.push(format!("EVs: {evs}"));
+    }
+    lines.push(format!("{:?} Nature", set.nature));
+    if let Some(ivs) = export_spread(&set.ivs, MAX_IV) {
+        lines.push(format!("IVs: {ivs}"));
+    }
+    if let Some(tera_type) = set.tera_type {
+        lines.push(format!("Tera Type: {tera_type:?}"));
+    }
+    for move_name in &set.moves {
+        lines.push(format!("- {}", names::display_name(move_name)));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+// Only stats that differ from the default are written
+fn export_spread(spread: &HashMap<StatEnum, u8>, default: u8) -> Option<String> {
+    let entries: Vec<String> = StatEnum::iter()
+        .filter(|stat| spread[stat] != default)
+        .map(|stat| {
+            format!(
+                "{} {}",
+                spread[&stat],
+                showdown_set::stat_abbreviation(stat)
+            )
+        })
+        .collect();
+
+    if entries.is_empty() {
+        None
+    } else {
+        Some(entries.join(" / "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        item::item::Item, pokemon::species::Species, pokemove::move_name::MoveName,
+        poketype::poketype::PokeType,
+    };
+
+    const TEAM: &str = "Snorlax @ Choice Band
+Ability: Thick Fat
+Level: 50
+EVs: 252 HP / 252 Atk / 4 SpD
+Adamant Nature
+IVs: 0 Spe
+Tera Type: Ghost
+- Return
+- Earthquake
+
+Starmie
+Ability: Technician
+Timid Nature
+- Recover
+";
+
+    #[test]
+    fn export_round_trips() {
+        let team = parse_team(TEAM).unwrap();
+        assert_eq!(export_team(&team), TEAM);
+    }
+
+    #[test]
+    fn parses_every_field() {
+        let team = parse_team(TEAM).unwrap();
+        let snorlax = &team[0];
+        assert_eq!(snorlax.species, Species::Snorlax);
+        assert_eq!(snorlax.item, Some(Item::ChoiceBand));
+        assert_eq!(snorlax.level, 50);
+        assert_eq!(snorlax.evs[&StatEnum::Attack], 252);
+        assert_eq!(snorlax.ivs[&StatEnum::Speed], 0);
+        assert_eq!(snorlax.ivs[&StatEnum::HP], MAX_IV);
+        assert_eq!(snorlax.tera_type, PokeType::Ghost);
+        assert_eq!(snorlax.moves[1], MoveName::Earthquake);
+        assert_eq!(team[1].item, None);
+    }
+
+    #[test]
+    fn nicknames_gender_and_headers_are_skipped() {
+        let team = parse_team(
+            "=== [gen9ou] Team ===\n\nLax (Snorlax) (M) @ Choice Band\nAbility: Thick Fat\nShiny: Yes\n- Return",
+        )
+        .unwrap();
+        assert_eq!(team.len(), 1);
+        assert_eq!(team[0].species, Species::Snorlax);
+    }
+
+    #[test]
+    fn errors_name_the_set_they_came_from() {
+        let errors = parse_team(
+            "Snorlax\nAbility: Thick Fat\n- Return\n\nMissingno\n- Return\n- Splash Dance",
+        )
+        .err()
+        .expect("Team should fail to parse");
+        assert_eq!(
+            errors,
+            vec![
+                ShowdownParseError {
+                    pokemon_idx: 1,
+                    kind: ShowdownParseErrorKind::UnknownSpecies("Missingno".to_string()),
+                },
+                ShowdownParseError {
+                    pokemon_idx: 1,
+                    kind: ShowdownParseErrorKind::UnknownMove("Splash Dance".to_string()),
+                },
+                ShowdownParseError {
+                    pokemon_idx: 1,
+                    kind: ShowdownParseErrorKind::MissingAbility,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use std::{collections::HashMap, fs, io, path::Path};
+
+use serde::Deserialize;
+use strum::IntoEnumIterator;
+
+use crate::{
+    core::{
+        ability::ability::Ability,
+        item::item::Item,
+        pokemon::{nature::Nature, species::Species, stat_enum::StatEnum},
+        pokemove::move_name::MoveName,
+        poketype::poketype::PokeType,
+    },
+    showdown::showdown_set,
+};
+
+// Smogon's chaos JSON, e.g. gen9ou-1825.json. Every field is a weighted count keyed by
+//  name, and everything else in the file is ignored
+#[derive(Deserialize)]
+struct RawUsageStats {
+    data: HashMap<String, RawSpeciesUsage>,
+}
+
+#[derive(Deserialize)]
+struct RawSpeciesUsage {
+    #[serde(rename = "Abilities", default)]
+    abilities: HashMap<String, f64>,
+    #[serde(rename = "Items", default)]
+    items: HashMap<String, f64>,
+    #[serde(rename = "Spreads", default)]
+    spreads: HashMap<String, f64>,
+    #[serde(rename = "Moves", default)]
+    moves: HashMap<String, f64>,
+    #[serde(rename = "Tera Types", default)]
+    tera_types: HashMap<String, f64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Spread {
+    pub nature: Nature,
+    pub evs: HashMap<StatEnum, u8>,
+}
+
+// Usage of one species, each list sorted from most to least likely. Names this dex
+//  doesn't know are dropped, and the rest are renormalized
+#[derive(Clone)]
+pub struct SpeciesUsage {
+    pub abilities: Vec<(Ability, f64)>,
+    pub items: Vec<(Option<Item>, f64)>, // None for sets without an item
+    pub spreads: Vec<(Spread, f64)>,
+    pub moves: Vec<(MoveName, f64)>, // chance a set has the move, adding up to about 4
+    pub tera_types: Vec<(PokeType, f64)>,
+}
+
+pub struct UsageStats {
+    species: HashMap<Species, SpeciesUsage>,
+}
+
+impl UsageStats {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::from_json(&fs::read_to_string(path)?).map_err(io::Error::other)
+    }
+
+    // Species this dex doesn't know are skipped
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let raw: RawUsageStats = serde_json::from_str(json)?;
+        let species = raw
+            .data
+            .into_iter()
+            .filter_map(|(name, usage)| {
+                showdown_set::parse_species(&name)
+                    .ok()
+                    .map(|species| (species, SpeciesUsage::from_raw(usage)))
+            })
+            .collect();
+        Ok(Self { species })
+    }
+
+    pub fn get(&self, species: &Species) -> Option<&SpeciesUsage> {
+        self.species.get(species)
+    }
+}
+
+impl SpeciesUsage {
+    fn from_raw(raw: RawSpeciesUsage) -> Self {
+        // every set has exactly one ability, so their total is the number of sets
+        let num_sets: f64 = raw.abilities.values().sum();
+        let moves = parse_weights(raw.moves, |name| showdown_set::parse_move(name).ok())
+            .into_iter()
+            .map(|(move_name, weight)| (move_name, (weight / num_sets.max(1.0)).min(1.0)))
+            .collect();
+
+        Self {
+            abilities: normalize(parse_weights(raw.abilities, |name| {
+                showdown_set::parse_ability(name).ok()
+            })),
+            items: normalize(parse_weights(raw.items, |name| match name {
+                "" | "nothing" => Some(None),
+                _ => showdown_set::parse_item(name).ok().map(Some),
+            })),
+            spreads: normalize(parse_weights(raw.spreads, parse_spread)),
+            moves,
+            tera_types: normalize(parse_weights(raw.tera_types, |name| {
+                showdown_set::parse_type(name).ok()
+            })),
+        }
+    }
+}
+
+// Parses every name, dropping the ones that fail, sorted from the highest weight
+fn parse_weights<T>(
+    weights: HashMap<String, f64>,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Vec<(T, f64)> {
+    let mut parsed: Vec<(T, f64)> = weights
+        .into_iter()
+        .filter(|(_, weight)| *weight > 0.0)
+        .filter_map(|(name, weight)| parse(&name).map(|value| (value, weight)))
+        .collect();
+    parsed.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    parsed
+}
+
+fn normalize<T>(weights: Vec<(T, f64)>) -> Vec<(T, f64)> {
+    let total: f64 = weights.iter().map(|(_, weight)| weight).sum();
+    weights
+        .into_iter()
+        .map(|(value, weight)| (value, weight / total))
+        .collect()
+}
+
+// "Nature:hp/atk/def/spa/spd/spe"
+fn parse_spread(spread: &str) -> Option<Spread> {
+    let (nature, evs) = spread.split_once(':')?;
+    let nature = showdown_set::parse_nature(nature).ok()?;
+    let values: Vec<&str> = evs.split('/').collect();
+    if values.len() != StatEnum::iter().count() {
+        return None;
+    }
+    let evs = StatEnum::iter()
+        .zip(values)
+        .map(|(stat, value)| {
+            showdown_set::parse_stat_value(value)
+                .ok()
+                .map(|ev| (stat, ev))
+        })
+        .collect::<Option<_>>()?;
+    Some(Spread { nature, evs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USAGE_JSON: &str = r#"{"info": {"metagame": "gen9ou"}, "data": {
+        "Snorlax": {
+            "Abilities": {"technician": 25, "thickfat": 75, "notanability": 10},
+            "Items": {"nothing": 15, "choiceband": 60, "leftovers": 5, "choicescarf": 25},
+            "Spreads": {"Careful:252/4/0/0/252/0": 30, "Adamant:252/252/0/0/4/0": 70,
+                "Adamant:252/252": 10},
+            "Moves": {"rest": 40, "return": 150, "earthquake": 80, "": 20},
+            "Tera Types": {"Ghost": 30, "Normal": 70}
+        },
+        "Missingno": {"Abilities": {"thickfat": 1}}
+    }}"#;
+
+    fn assert_close<T: PartialEq + std::fmt::Debug>(actual: &[(T, f64)], expected: &[(T, f64)]) {
+        assert_eq!(actual.len(), expected.len());
+        for ((value, weight), (expected_value, expected_weight)) in actual.iter().zip(expected) {
+            assert_eq!(value, expected_value);
+            assert!((weight - expected_weight).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn usage_is_parsed_sorted_and_normalized() {
+        let usage_stats = UsageStats::from_json(USAGE_JSON).unwrap();
+        assert!(usage_stats.get(&Species::Pikachu).is_none());
+        let usage = usage_stats.get(&Species::Snorlax).unwrap();
+
+        assert_close(
+            &usage.abilities,
+            &[(Ability::ThickFat, 0.75), (Ability::Technician, 0.25)],
+        );
+        // leftovers isn't in this dex, so the rest make up every set
+        assert_close(
+            &usage.items,
+            &[
+                (Some(Item::ChoiceBand), 0.6),
+                (Some(Item::ChoiceScarf), 0.25),
+                (None, 0.15),
+            ],
+        );
+        assert_close(
+            &usage.tera_types,
+            &[(PokeType::Normal, 0.7), (PokeType::Ghost, 0.3)],
+        );
+
+        let natures: Vec<Nature> = usage
+            .spreads
+            .iter()
+            .map(|(spread, _)| spread.nature)
+            .collect();
+        assert_eq!(natures, vec![Nature::Adamant, Nature::Careful]);
+        assert!((usage.spreads[0].1 - 0.7).abs() < 1e-9);
+        assert_eq!(usage.spreads[0].0.evs[&StatEnum::Attack], 252);
+    }
+
+    #[test]
+    fn move_chances_are_per_set_and_capped() {
+        let usage_stats = UsageStats::from_json(USAGE_JSON).unwrap();
+        let usage = usage_stats.get(&Species::Snorlax).unwrap();
+        // 110 sets by ability count, so a move's chance is its weight over 110
+        assert_close(
+            &usage.moves,
+            &[
+                (MoveName::Return, 1.0),
+                (MoveName::Earthquake, 80.0 / 110.0),
+                (MoveName::Rest, 40.0 / 110.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn malformed_json_is_an_error() {
+        assert!(UsageStats::from_json("{\"info\": {}}").is_err());
+    }
+}
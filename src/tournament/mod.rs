@@ -0,0 +1,3 @@
+pub mod rating;
+pub mod results;
+pub mod tournament;
@@ -0,0 +1,139 @@
+use std::f64::consts::PI;
+
+use serde::Serialize;
+
+const ELO_K: f64 = 32.0;
+const INITIAL_RATING: f64 = 1500.0;
+const INITIAL_DEVIATION: f64 = 350.0;
+const GLICKO_Q: f64 = std::f64::consts::LN_10 / 400.0;
+
+// Expected score of a player against an opponent, from 0 to 1
+fn expected_score(rating: f64, opponent_rating: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent_rating - rating) / 400.0))
+}
+
+// Updates both ratings after every game, so game order matters
+#[derive(Clone, Copy, Serialize)]
+pub struct EloRating {
+    pub rating: f64,
+}
+
+impl EloRating {
+    pub fn new() -> Self {
+        Self {
+            rating: INITIAL_RATING,
+        }
+    }
+
+    // score is 1 for a win, 0.5 for a draw and 0 for a loss
+    pub fn update(&mut self, opponent: EloRating, score: f64) {
+        self.rating += ELO_K * (score - expected_score(self.rating, opponent.rating));
+    }
+}
+
+impl Default for EloRating {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Glicko-1, where every game in a rating period is scored against the ratings the
+//  players had at its start. The deviation shrinks as a player's games add up. The c
+//  term that grows the deviation between periods is left out (c = 0), since an agent
+//  plays the same way in every round
+#[derive(Clone, Copy, Serialize)]
+pub struct GlickoRating {
+    pub rating: f64,
+    pub deviation: f64,
+}
+
+impl GlickoRating {
+    pub fn new() -> Self {
+        Self {
+            rating: INITIAL_RATING,
+            deviation: INITIAL_DEVIATION,
+        }
+    }
+
+    // Applies a rating period's games, given as each opponent's rating and the score
+    pub fn update(&mut self, games: &[(GlickoRating, f64)]) {
+        if games.is_empty() {
+            return;
+        }
+
+        let mut inverse_d_squared = 0.0;
+        let mut score_difference = 0.0;
+        for (opponent, score) in games {
+            let g = Self::g(opponent.deviation);
+            let expected = 1.0 / (1.0 + 10f64.powf(-g * (self.rating - opponent.rating) / 400.0));
+            inverse_d_squared += GLICKO_Q.powi(2) * g.powi(2) * expected * (1.0 - expected);
+            score_difference += g * (score - expected);
+        }
+
+        let inverse_variance = 1.0 / self.deviation.powi(2) + inverse_d_squared;
+        self.rating += GLICKO_Q / inverse_variance * score_difference;
+        self.deviation = (1.0 / inverse_variance).sqrt();
+    }
+
+    // Weights a game by how certain the opponent's rating is
+    fn g(deviation: f64) -> f64 {
+        1.0 / (1.0 + 3.0 * GLICKO_Q.powi(2) * deviation.powi(2) / PI.powi(2)).sqrt()
+    }
+}
+
+impl Default for GlickoRating {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 95% Wilson score interval for a win rate, counting draws as half a win
+pub fn win_rate_interval(score: f64, games: u32) -> (f64, f64) {
+    if games == 0 {
+        return (0.0, 1.0);
+    }
+
+    const Z: f64 = 1.96;
+    let n = games as f64;
+    let p = score / n;
+    let denominator = 1.0 + Z * Z / n;
+    let center = (p + Z * Z / (2.0 * n)) / denominator;
+    let margin = Z * (p * (1.0 - p) / n + Z * Z / (4.0 * n * n)).sqrt() / denominator;
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elo_moves_by_k_times_the_surprise() {
+        let mut rating = EloRating::default();
+        rating.update(EloRating::default(), 1.0);
+        assert_eq!(rating.rating, INITIAL_RATING + ELO_K / 2.0);
+    }
+
+    // The worked example from Glickman's description of the Glicko system
+    #[test]
+    fn glicko_matches_the_reference_example() {
+        let mut rating = GlickoRating {
+            rating: 1500.0,
+            deviation: 200.0,
+        };
+        let opponent = |rating, deviation| GlickoRating { rating, deviation };
+        rating.update(&[
+            (opponent(1400.0, 30.0), 1.0),
+            (opponent(1550.0, 100.0), 0.0),
+            (opponent(1700.0, 300.0), 0.0),
+        ]);
+        assert!((rating.rating - 1464.1).abs() < 0.1);
+        assert!((rating.deviation - 151.4).abs() < 0.1);
+    }
+
+    #[test]
+    fn glicko_is_unchanged_without_games() {
+        let mut rating = GlickoRating::default();
+        rating.update(&[]);
+        assert_eq!(rating.deviation, INITIAL_DEVIATION);
+    }
+}
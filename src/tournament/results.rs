@@ -0,0 +1,283 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use serde::Serialize;
+
+use crate::{
+    battle::battle_request::Winner,
+    tournament::rating::{self, EloRating, GlickoRating},
+};
+
+#[derive(Clone, Copy, Serialize)]
+pub struct BattleRecord {
+    pub round: u32,
+    pub agents: (usize, usize), // trainer 1 and trainer 2
+    pub teams: (usize, usize),  // indices into the team pool
+    pub seed: u64,
+    pub winner: Winner,
+}
+
+impl BattleRecord {
+    // Scores for trainer 1 and trainer 2
+    fn get_scores(&self) -> (f64, f64) {
+        match self.winner {
+            Winner::Trainer1 => (1.0, 0.0),
+            Winner::Trainer2 => (0.0, 1.0),
+            Winner::Draw => (0.5, 0.5),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Standing {
+    pub name: String,
+    pub battles: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub win_rate: f64, // draws count as half a win
+    pub win_rate_low: f64,
+    pub win_rate_high: f64,
+    pub elo: EloRating,
+    pub glicko: GlickoRating,
+}
+
+// Standings are in the order the agents were registered
+#[derive(Serialize)]
+pub struct TournamentResults {
+    pub standings: Vec<Standing>,
+    pub battles: Vec<BattleRecord>,
+}
+
+impl TournamentResults {
+    pub fn new(agent_names: Vec<String>, records: &[BattleRecord]) -> Self {
+        let mut standings: Vec<Standing> = agent_names
+            .into_iter()
+            .map(|name| Standing {
+                name,
+                battles: 0,
+                wins: 0,
+                losses: 0,
+                draws: 0,
+                win_rate: 0.0,
+                win_rate_low: 0.0,
+                win_rate_high: 1.0,
+                elo: EloRating::default(),
+                glicko: GlickoRating::default(),
+            })
+            .collect();
+
+        for record in records {
+            let (a, b) = record.agents;
+            let (score_a, score_b) = record.get_scores();
+            for (agent, is_trainer_1) in [(a, true), (b, false)] {
+                let standing = &mut standings[agent];
+                standing.battles += 1;
+                match (record.winner, is_trainer_1) {
+                    (Winner::Draw, _) => standing.draws += 1,
+                    (Winner::Trainer1, true) | (Winner::Trainer2, false) => standing.wins += 1,
+                    _ => standing.losses += 1,
+                }
+            }
+
+            let (elo_a, elo_b) = (standings[a].elo, standings[b].elo);
+            standings[a].elo.update(elo_b, score_a);
+            standings[b].elo.update(elo_a, score_b);
+        }
+
+        // each round is a Glicko rating period
+        let last_round = records.iter().map(|record| record.round).max();
+        for round in 0..last_round.map_or(0, |round| round + 1) {
+            let ratings: Vec<GlickoRating> = standings.iter().map(|s| s.glicko).collect();
+            let mut games: Vec<Vec<(GlickoRating, f64)>> = vec![vec![]; standings.len()];
+            for record in records.iter().filter(|record| record.round == round) {
+                let (a, b) = record.agents;
+                let (score_a, score_b) = record.get_scores();
+                games[a].push((ratings[b], score_a));
+                games[b].push((ratings[a], score_b));
+            }
+            for (standing, games) in standings.iter_mut().zip(games) {
+                standing.glicko.update(&games);
+            }
+        }
+
+        for standing in standings.iter_mut() {
+            let score = standing.wins as f64 + 0.5 * standing.draws as f64;
+            if standing.battles > 0 {
+                standing.win_rate = score / standing.battles as f64;
+            }
+            (standing.win_rate_low, standing.win_rate_high) =
+                rating::win_rate_interval(score, standing.battles);
+        }
+
+        Self {
+            standings,
+            battles: records.to_vec(),
+        }
+    }
+
+    // Total score of each agent, a point per win and half a point per draw
+    pub fn get_scores(&self) -> Vec<f64> {
+        self.standings
+            .iter()
+            .map(|standing| standing.wins as f64 + 0.5 * standing.draws as f64)
+            .collect()
+    }
+
+    pub fn write_json(&self, path: &Path) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, self).map_err(io::Error::other)
+    }
+
+    pub fn write_standings_csv(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(
+            writer,
+            "name,battles,wins,losses,draws,win_rate,win_rate_low,win_rate_high,elo,glicko,glicko_deviation"
+        )?;
+        for s in &self.standings {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{:.4},{:.4},{:.4},{:.1},{:.1},{:.1}",
+                escape_csv(&s.name),
+                s.battles,
+                s.wins,
+                s.losses,
+                s.draws,
+                s.win_rate,
+                s.win_rate_low,
+                s.win_rate_high,
+                s.elo.rating,
+                s.glicko.rating,
+                s.glicko.deviation
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn write_battles_csv(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "round,agent_1,agent_2,team_1,team_2,seed,winner")?;
+        for record in &self.battles {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{:?}",
+                record.round,
+                escape_csv(&self.standings[record.agents.0].name),
+                escape_csv(&self.standings[record.agents.1].name),
+                record.teams.0,
+                record.teams.1,
+                record.seed,
+                record.winner
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Agent names are free text, so any with a separator, quote or line break get quoted
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn get_results() -> TournamentResults {
+        let records = [
+            (0, (0, 1), Winner::Trainer1),
+            (0, (1, 0), Winner::Draw),
+            (1, (0, 2), Winner::Trainer2),
+        ]
+        .map(|(round, agents, winner)| BattleRecord {
+            round,
+            agents,
+            teams: (0, 1),
+            seed: 7,
+            winner,
+        });
+        TournamentResults::new(
+            vec![
+                "max damage".to_string(),
+                "mcts, 200 iterations".to_string(),
+                "\"random\"".to_string(),
+            ],
+            &records,
+        )
+    }
+
+    fn get_output_path(file_name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("{}_{file_name}", std::process::id()))
+    }
+
+    #[test]
+    fn standings_tally_every_battle() {
+        let results = get_results();
+        let tallies: Vec<(u32, u32, u32, u32)> = results
+            .standings
+            .iter()
+            .map(|s| (s.battles, s.wins, s.losses, s.draws))
+            .collect();
+        assert_eq!(tallies, vec![(3, 1, 1, 1), (2, 0, 1, 1), (1, 1, 0, 0)]);
+        assert_eq!(results.get_scores(), vec![1.5, 0.5, 1.0]);
+        assert_eq!(results.standings[0].win_rate, 0.5);
+        assert!(results.standings[2].elo.rating > results.standings[1].elo.rating);
+    }
+
+    #[test]
+    fn csv_output_quotes_agent_names() {
+        let results = get_results();
+        let standings_path = get_output_path("standings.csv");
+        let battles_path = get_output_path("battles.csv");
+        results.write_standings_csv(&standings_path).unwrap();
+        results.write_battles_csv(&battles_path).unwrap();
+        let standings = fs::read_to_string(&standings_path).unwrap();
+        let battles = fs::read_to_string(&battles_path).unwrap();
+        fs::remove_file(standings_path).unwrap();
+        fs::remove_file(battles_path).unwrap();
+
+        let names: Vec<&str> = standings
+            .lines()
+            .skip(1)
+            .map(|line| line.rsplitn(11, ',').last().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "max damage",
+                "\"mcts, 200 iterations\"",
+                "\"\"\"random\"\"\""
+            ]
+        );
+        assert_eq!(
+            battles.lines().nth(1),
+            Some("0,max damage,\"mcts, 200 iterations\",0,1,7,Trainer1")
+        );
+        assert_eq!(battles.lines().count(), 4);
+    }
+
+    #[test]
+    fn json_output_holds_standings_and_battles() {
+        let results = get_results();
+        let path = get_output_path("results.json");
+        results.write_json(&path).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(json["standings"][1]["name"], "mcts, 200 iterations");
+        assert_eq!(json["standings"][0]["wins"], 1);
+        assert_eq!(json["battles"].as_array().unwrap().len(), 3);
+        assert_eq!(json["battles"][2]["winner"], "Trainer2");
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    collections::HashSet,
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+};
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use typed_builder::TypedBuilder;
+
+use crate::{
+    agent::{agent::Agent, runner},
+    battle::battle_request::Winner,
+    core::pokemon::pokemon::Pokemon,
+    tournament::results::{BattleRecord, TournamentResults},
+};
+
+// Builds a fresh agent for each battle from that battle's seed
+pub type AgentFactory = Box<dyn Fn(u64) -> Box<dyn Agent> + Send + Sync>;
+
+#[derive(Clone, Copy)]
+pub enum TournamentFormat {
+    RoundRobin,
+    Swiss(u32), // number of rounds
+}
+
+#[derive(TypedBuilder)]
+pub struct TournamentConfig {
+    #[builder(default = TournamentFormat::RoundRobin)]
+    pub format: TournamentFormat,
+    #[builder(default = 2)]
+    pub battles_per_pairing: u32, // agents swap sides between a pairing's battles, on the same teams
+    #[builder(default)]
+    pub seed: u64,
+    #[builder(default = thread::available_parallelism().map_or(1, |n| n.get()))]
+    pub threads: usize,
+}
+
+// A battle waiting to be played, with everything needed to replay it exactly
+#[derive(Clone, Copy)]
+struct BattleJob {
+    round: u32,
+    agents: (usize, usize),
+    teams: (usize, usize),
+    seed: u64,
+}
+
+pub struct Tournament {
+    config: TournamentConfig,
+    agents: Vec<(String, AgentFactory)>,
+    teams: Vec<Vec<Pokemon>>,
+}
+
+impl Tournament {
+    pub fn new(config: TournamentConfig, teams: Vec<Vec<Pokemon>>) -> Self {
+        if teams.is_empty() {
+            panic!("Tournament needs at least one team");
+        }
+        Self {
+            config,
+            agents: vec![],
+            teams,
+        }
+    }
+
+    pub fn register_agent(&mut self, name: &str, factory: AgentFactory) {
+        self.agents.push((name.to_string(), factory));
+    }
+
+    pub fn run(&self) -> TournamentResults {
+        if self.agents.len() < 2 {
+            panic!("Tournament needs at least two agents");
+        }
+
+        let mut records: Vec<BattleRecord> = vec![];
+        match self.config.format {
+            TournamentFormat::RoundRobin => {
+                let pairings: Vec<(usize, usize)> = (0..self.agents.len())
+                    .flat_map(|a| (a + 1..self.agents.len()).map(move |b| (a, b)))
+                    .collect();
+                records.extend(self.play_round(0, &pairings, records.len()));
+            }
+            TournamentFormat::Swiss(rounds) => {
+                for round in 0..rounds {
+                    let standings = TournamentResults::new(self.get_agent_names(), &records);
+                    let pairings = Self::get_swiss_pairings(&standings.get_scores(), &records);
+                    records.extend(self.play_round(round, &pairings, records.len()));
+                }
+            }
+        }
+
+        TournamentResults::new(self.get_agent_names(), &records)
+    }
+
+    fn get_agent_names(&self) -> Vec<String> {
+        self.agents.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    // Pairs agents with similar scores who haven't met yet, falling back to a rematch
+    //  when there's no one else left. With an odd number of agents the one left over
+    //  sits the round out
+    fn get_swiss_pairings(scores: &[f64], records: &[BattleRecord]) -> Vec<(usize, usize)> {
+        let played: HashSet<(usize, usize)> = records
+            .iter()
+            .map(|record| {
+                let (a, b) = record.agents;
+                (a.min(b), a.max(b))
+            })
+            .collect();
+
+        let mut order: Vec<usize> = (0..scores.len()).collect();
+        order.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]).then(a.cmp(b)));
+
+        let mut pairings = vec![];
+        while order.len() > 1 {
+            let agent = order.remove(0);
+            let opponent_idx = order
+                .iter()
+                .position(|other| !played.contains(&(agent.min(*other), agent.max(*other))))
+                .unwrap_or(0);
+            let opponent = order.remove(opponent_idx);
+            pairings.push((agent, opponent));
+        }
+        pairings
+    }
+
+    fn play_round(
+        &self,
+        round: u32,
+        pairings: &[(usize, usize)],
+        first_battle_idx: usize,
+    ) -> Vec<BattleRecord> {
+        self.play_battles(&self.get_round_jobs(round, pairings, first_battle_idx))
+    }
+
+    // A pairing's first battle draws the teams and the rest reuse them, so with the agents
+    //  swapping sides each one plays both teams
+    fn get_round_jobs(
+        &self,
+        round: u32,
+        pairings: &[(usize, usize)],
+        first_battle_idx: usize,
+    ) -> Vec<BattleJob> {
+        let mut jobs: Vec<BattleJob> = vec![];
+        for (a, b) in pairings {
+            let mut teams = None;
+            for game in 0..self.config.battles_per_pairing {
+                let seed = Self::get_battle_seed(self.config.seed, first_battle_idx + jobs.len());
+                let teams = *teams.get_or_insert_with(|| {
+                    let mut rng = StdRng::seed_from_u64(seed);
+                    (
+                        rng.gen_range(0..self.teams.len()),
+                        rng.gen_range(0..self.teams.len()),
+                    )
+                });
+                jobs.push(BattleJob {
+                    round,
+                    agents: if game % 2 == 0 { (*a, *b) } else { (*b, *a) },
+                    teams,
+                    seed,
+                });
+            }
+        }
+        jobs
+    }
+
+    // Seeds are spread out from the tournament seed, so a battle can be replayed alone
+    fn get_battle_seed(seed: u64, battle_idx: usize) -> u64 {
+        StdRng::seed_from_u64(seed ^ (battle_idx as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+            .r#gen()
+    }
+
+    // Worker threads take battles in order, results keep the order of the jobs
+    fn play_battles(&self, jobs: &[BattleJob]) -> Vec<BattleRecord> {
+        let next_job = AtomicUsize::new(0);
+        let winners: Mutex<Vec<Option<Winner>>> = Mutex::new(vec![None; jobs.len()]);
+
+        thread::scope(|scope| {
+            for _ in 0..self.config.threads.max(1) {
+                scope.spawn(|| {
+                    loop {
+                        let idx = next_job.fetch_add(1, Ordering::Relaxed);
+                        let Some(job) = jobs.get(idx) else {
+                            break;
+                        };
+                        let winner = self.play_battle(job);
+                        winners.lock().expect("Worker thread panicked")[idx] = Some(winner);
+                    }
+                });
+            }
+        });
+
+        let winners = winners.into_inner().expect("Worker thread panicked");
+        jobs.iter()
+            .zip(winners)
+            .map(|(job, winner)| BattleRecord {
+                round: job.round,
+                agents: job.agents,
+                teams: job.teams,
+                seed: job.seed,
+                winner: winner.expect("Battle was not played"),
+            })
+            .collect()
+    }
+
+    fn play_battle(&self, job: &BattleJob) -> Winner {
+        let mut agent_1 = (self.agents[job.agents.0].1)(job.seed);
+        let mut agent_2 = (self.agents[job.agents.1].1)(job.seed.wrapping_add(1));
+        runner::run_battle(
+            agent_1.as_mut(),
+            agent_2.as_mut(),
+            (
+                self.teams[job.teams.0].clone(),
+                self.teams[job.teams.1].clone(),
+            ),
+            job.seed,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{agent::random_agent::RandomAgent, showdown::text};
+
+    fn get_record(round: u32, agents: (usize, usize), winner: Winner) -> BattleRecord {
+        BattleRecord {
+            round,
+            agents,
+            teams: (0, 0),
+            seed: 0,
+            winner,
+        }
+    }
+
+    #[test]
+    fn swiss_pairs_agents_by_score() {
+        let pairings = Tournament::get_swiss_pairings(&[0.0, 2.0, 1.0, 3.0], &[]);
+        assert_eq!(pairings, vec![(3, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn swiss_avoids_rematches() {
+        let records = [get_record(0, (0, 1), Winner::Trainer1)];
+        let pairings = Tournament::get_swiss_pairings(&[1.0, 0.0, 0.0, 0.0], &records);
+        assert_eq!(pairings, vec![(0, 2), (1, 3)]);
+    }
+
+    #[test]
+    fn swiss_falls_back_to_a_rematch() {
+        let records = [get_record(0, (0, 1), Winner::Trainer1)];
+        let pairings = Tournament::get_swiss_pairings(&[1.0, 0.0], &records);
+        assert_eq!(pairings, vec![(0, 1)]);
+    }
+
+    #[test]
+    fn swiss_gives_the_lowest_agent_a_bye() {
+        let pairings = Tournament::get_swiss_pairings(&[2.0, 1.0, 0.0], &[]);
+        assert_eq!(pairings, vec![(0, 1)]);
+    }
+
+    #[test]
+    fn pairings_swap_sides_on_the_same_teams() {
+        let teams: Vec<Vec<Pokemon>> = ["Pikachu", "Snorlax", "Gengar", "Machamp"]
+            .iter()
+            .map(|species| {
+                text::parse_team(&format!("{species}\nAbility: Technician\n- Tackle"))
+                    .expect("team should parse")
+            })
+            .collect();
+        let mut tournament = Tournament::new(
+            TournamentConfig::builder()
+                .battles_per_pairing(4)
+                .seed(3)
+                .build(),
+            teams,
+        );
+        for name in ["a", "b", "c"] {
+            tournament.register_agent(name, Box::new(|seed| Box::new(RandomAgent::new(seed))));
+        }
+
+        let jobs = tournament.get_round_jobs(0, &[(0, 1), (1, 2)], 0);
+        assert_eq!(jobs.len(), 8);
+        for pairing in jobs.chunks(4) {
+            let (a, b) = pairing[0].agents;
+            for (game, job) in pairing.iter().enumerate() {
+                assert_eq!(job.teams, pairing[0].teams);
+                let agents = if game % 2 == 0 { (a, b) } else { (b, a) };
+                assert_eq!(job.agents, agents);
+            }
+        }
+        let seeds: HashSet<u64> = jobs.iter().map(|job| job.seed).collect();
+        assert_eq!(seeds.len(), jobs.len());
+    }
+}